@@ -1,3 +1,5 @@
+use crate::infrastructure::blockchain::BloomCredentials;
+use crate::infrastructure::blockchain::bloom::regions::{record_region_probe, region_from_url};
 use crate::infrastructure::blockchain::bloom::types::{BloomSwapPayload, BloomWallet};
 use chrono::{DateTime, Utc};
 use parking_lot::Mutex;
@@ -65,10 +67,10 @@ pub async fn run_warmer(state_arc: WarmerState) {
                     },
                     _ => (WarmupStatus::Failed, latency_ms),
                 }
-            } else if url.contains("bloom-ext.app") {
-                let auth_token = match std::env::var("BLOOM_AUTH_TOKEN") {
-                    Ok(token) => token,
-                    Err(_) => {
+            } else if region_from_url(&url).is_some() {
+                let auth_token = match BloomCredentials::from_env() {
+                    Some(credentials) => credentials.token,
+                    None => {
                         log::error!("BLOOM_AUTH_TOKEN not set, cannot warm up Bloom API.");
                         String::new()
                     }
@@ -97,16 +99,20 @@ pub async fn run_warmer(state_arc: WarmerState) {
                         }],
                     };
 
-                    let api_url = "http://eu1.bloom-ext.app/api/extension-swap";
-                    let response = client.post(api_url).json(&payload).send().await;
+                    let api_url = format!("{}/api/extension-swap", url.trim_end_matches('/'));
+                    let response = client.post(&api_url).json(&payload).send().await;
                     let duration = start_time.elapsed();
                     let latency_ms = Some(duration.as_micros() as f64 / 1000.0);
 
-                    match response {
-                        Ok(res) if !res.status().is_server_error() => {
-                            (WarmupStatus::Success, latency_ms)
-                        }
-                        _ => (WarmupStatus::Failed, latency_ms),
+                    let healthy =
+                        matches!(response, Ok(ref res) if !res.status().is_server_error());
+                    if let Some(region) = region_from_url(&url) {
+                        record_region_probe(&region, healthy, latency_ms);
+                    }
+                    if healthy {
+                        (WarmupStatus::Success, latency_ms)
+                    } else {
+                        (WarmupStatus::Failed, latency_ms)
                     }
                 }
             } else if url.contains("quiknode")
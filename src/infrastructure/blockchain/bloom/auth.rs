@@ -0,0 +1,61 @@
+use anyhow::{Result, anyhow};
+use chrono::Utc;
+
+const ENV_ACCOUNT_KEY: &str = "env";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BloomCredentials {
+    pub account_key: String,
+    pub token: String,
+    pub expires_at: Option<i64>,
+}
+
+impl BloomCredentials {
+    pub fn new(account_key: impl Into<String>, token: &str, expires_at: Option<i64>) -> Self {
+        BloomCredentials {
+            account_key: account_key.into(),
+            token: token.trim().to_string(),
+            expires_at,
+        }
+    }
+
+    pub fn from_env() -> Option<Self> {
+        let token = std::env::var("BLOOM_AUTH_TOKEN").ok()?;
+        if token.trim().is_empty() {
+            return None;
+        }
+        let expires_at = std::env::var("BLOOM_AUTH_TOKEN_EXPIRES_AT")
+            .ok()
+            .and_then(|value| value.trim().parse::<i64>().ok());
+        Some(BloomCredentials::new(ENV_ACCOUNT_KEY, &token, expires_at))
+    }
+
+    pub fn expires_in_ms(&self) -> Option<i64> {
+        self.expires_at
+            .map(|expires_at| expires_at - Utc::now().timestamp_millis())
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_in_ms().is_some_and(|remaining| remaining <= 0)
+    }
+
+    pub fn usable_token(&self) -> Result<&str> {
+        if self.token.is_empty() {
+            return Err(anyhow!(
+                "Bloom auth token for account {} is empty",
+                self.account_key
+            ));
+        }
+        if self.is_expired() {
+            return Err(anyhow!(
+                "Bloom auth token for account {} expired",
+                self.account_key
+            ));
+        }
+        Ok(&self.token)
+    }
+}
+
+pub fn account_key(chat_id: i64, account_name: &str) -> String {
+    format!("{}:{}", chat_id, account_name)
+}
@@ -1,11 +1,148 @@
-use super::types::{BloomSwapPayload, BloomSwapResponse, BloomWallet};
-use crate::infrastructure::blockchain::config::BLOOM_SWAP_URL;
-use crate::{ACTIVE_BLOOM_SWAPS, BloomSwapTracker, HTTP_CLIENT};
+use super::auth::BloomCredentials;
+use super::regions::{ranked_regions, record_region_failure, region_swap_url};
+use super::registry::{discard_swap, register_swap};
+use super::types::{BloomSwapOptions, BloomSwapPayload, BloomSwapResponse, BloomWallet};
+use crate::HTTP_CLIENT;
 use anyhow::{Result, anyhow};
+use futures::StreamExt;
+use futures::stream::FuturesUnordered;
 use std::time::Instant;
 use uuid::Uuid;
 
+enum RegionAttempt {
+    Accepted(BloomSwapResponse),
+    Rejected(anyhow::Error),
+    Unavailable(anyhow::Error),
+}
+
+async fn send_to_region(region: &str, payload: &BloomSwapPayload<'_>) -> RegionAttempt {
+    let started = Instant::now();
+    let response = match HTTP_CLIENT
+        .post(region_swap_url(region))
+        .json(payload)
+        .send()
+        .await
+    {
+        Ok(response) => response,
+        Err(e) => {
+            record_region_failure(region);
+            return RegionAttempt::Unavailable(anyhow!(
+                "Bloom region {} unreachable: {}",
+                region,
+                e
+            ));
+        }
+    };
+
+    let status = response.status();
+    if status.is_server_error() {
+        record_region_failure(region);
+        let text = response.text().await.unwrap_or_default();
+        return RegionAttempt::Unavailable(anyhow!(
+            "Bloom region {} returned status {}: {}",
+            region,
+            status,
+            text
+        ));
+    }
+    if !status.is_success() {
+        let text = response.text().await.unwrap_or_default();
+        return RegionAttempt::Rejected(anyhow!(
+            "Bloom API request failed with status {}: {}",
+            status,
+            text
+        ));
+    }
+
+    match response.json::<BloomSwapResponse>().await {
+        Ok(swap_response) => {
+            log::debug!(
+                "bloom_regions: region={} answered swap id={} in {} ms",
+                region,
+                payload.id,
+                started.elapsed().as_millis()
+            );
+            RegionAttempt::Accepted(swap_response)
+        }
+        Err(e) => RegionAttempt::Rejected(e.into()),
+    }
+}
+
+async fn submit_hedged(
+    primary: &str,
+    secondary: &str,
+    payload: &BloomSwapPayload<'_>,
+) -> RegionAttempt {
+    let mut legs: FuturesUnordered<_> = [primary, secondary]
+        .into_iter()
+        .map(|region| send_to_region(region, payload))
+        .collect();
+    let mut rejected = None;
+    let mut unavailable = None;
+    while let Some(attempt) = legs.next().await {
+        match attempt {
+            RegionAttempt::Accepted(swap_response) => {
+                return RegionAttempt::Accepted(swap_response);
+            }
+            RegionAttempt::Rejected(e) => rejected = Some(e),
+            RegionAttempt::Unavailable(e) => unavailable = Some(e),
+        }
+    }
+    match (rejected, unavailable) {
+        (Some(e), _) => RegionAttempt::Rejected(e),
+        (None, Some(e)) => RegionAttempt::Unavailable(e),
+        (None, None) => RegionAttempt::Unavailable(anyhow!("No hedged Bloom regions answered")),
+    }
+}
+
+async fn submit_swap(payload: &BloomSwapPayload<'_>, hedged: bool) -> Result<BloomSwapResponse> {
+    let mut regions = ranked_regions();
+    let mut last_error = None;
+
+    if hedged && regions.len() >= 2 {
+        let primary = regions.remove(0);
+        let secondary = regions.remove(0);
+        log::info!(
+            "bloom_regions: hedging swap id={} across {} and {}",
+            payload.id,
+            primary,
+            secondary
+        );
+        match submit_hedged(&primary, &secondary, payload).await {
+            RegionAttempt::Accepted(swap_response) => return Ok(swap_response),
+            RegionAttempt::Rejected(e) => return Err(e),
+            RegionAttempt::Unavailable(e) => {
+                log::warn!(
+                    "bloom_regions: hedged regions failed for swap id={} err=\"{}\"",
+                    payload.id,
+                    e
+                );
+                last_error = Some(e);
+            }
+        }
+    }
+
+    for region in regions {
+        match send_to_region(&region, payload).await {
+            RegionAttempt::Accepted(swap_response) => return Ok(swap_response),
+            RegionAttempt::Rejected(e) => return Err(e),
+            RegionAttempt::Unavailable(e) => {
+                log::warn!(
+                    "bloom_regions: failing over swap id={} from region={} err=\"{}\"",
+                    payload.id,
+                    region,
+                    e
+                );
+                last_error = Some(e);
+            }
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| anyhow!("No Bloom regions configured")))
+}
+
 async fn execute_swap(
+    credentials: &BloomCredentials,
     mint_address: &str,
     amount: f64,
     slippage_percent: u32,
@@ -13,22 +150,12 @@ async fn execute_swap(
     wallet_address: &str,
     wallet_label: &str,
     side: &str,
-) -> Result<()> {
-    let auth_token = std::env::var("BLOOM_AUTH_TOKEN")
-        .map_err(|_| anyhow!("BLOOM_AUTH_TOKEN environment variable not set"))?;
+    options: &BloomSwapOptions,
+) -> Result<String> {
+    let auth_token = credentials.usable_token()?.to_string();
 
     let swap_id = format!("QT-{}", Uuid::new_v4());
-    {
-        let mut swaps = ACTIVE_BLOOM_SWAPS.lock();
-        swaps.insert(
-            swap_id.clone(),
-            BloomSwapTracker {
-                mint: mint_address.to_string(),
-                side: side.to_string(),
-                started_at: Instant::now(),
-            },
-        );
-    }
+    register_swap(&swap_id, mint_address, side, &credentials.account_key);
 
     let payload = BloomSwapPayload {
         id: swap_id.clone(),
@@ -36,13 +163,13 @@ async fn execute_swap(
         address: mint_address,
         amount,
         priority_fee,
-        processor_tip: priority_fee,
+        processor_tip: options.processor_tip,
         slippage: slippage_percent,
         side,
-        skip_if_bought: false,
-        anti_mev: false,
-        auto_tip: false,
-        dev_sell: None,
+        skip_if_bought: options.skip_if_bought,
+        anti_mev: options.anti_mev,
+        auto_tip: options.auto_tip,
+        dev_sell: options.dev_sell.clone(),
         amount_type: "exact_in",
         wallets: vec![BloomWallet {
             address: wallet_address,
@@ -50,29 +177,18 @@ async fn execute_swap(
         }],
     };
 
-    let response = HTTP_CLIENT
-        .post(BLOOM_SWAP_URL)
-        .json(&payload)
-        .send()
-        .await?;
-
-    if !response.status().is_success() {
-        ACTIVE_BLOOM_SWAPS.lock().remove(&swap_id);
-        let status = response.status();
-        let text = response.text().await.unwrap_or_default();
-        return Err(anyhow!(
-            "Bloom API request failed with status {}: {}",
-            status,
-            text
-        ));
-    }
-
-    let swap_response: BloomSwapResponse = response.json().await?;
+    let swap_response = match submit_swap(&payload, options.hedged).await {
+        Ok(swap_response) => swap_response,
+        Err(e) => {
+            discard_swap(&swap_id);
+            return Err(e);
+        }
+    };
 
     if swap_response.success {
-        Ok(())
+        Ok(swap_id)
     } else {
-        ACTIVE_BLOOM_SWAPS.lock().remove(&swap_id);
+        discard_swap(&swap_id);
         Err(anyhow!(swap_response.error.unwrap_or_else(|| {
             "Unknown error from Bloom API".to_string()
         })))
@@ -80,14 +196,17 @@ async fn execute_swap(
 }
 
 pub async fn buy(
+    credentials: &BloomCredentials,
     mint_address: &str,
     sol_amount: f64,
     slippage_percent: u32,
     priority_fee: f64,
     wallet_address: &str,
     wallet_label: &str,
-) -> Result<()> {
+    options: &BloomSwapOptions,
+) -> Result<String> {
     execute_swap(
+        credentials,
         mint_address,
         sol_amount,
         slippage_percent,
@@ -95,6 +214,7 @@ pub async fn buy(
         wallet_address,
         wallet_label,
         "Buy",
+        options,
     )
     .await
 }
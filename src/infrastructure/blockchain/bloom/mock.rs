@@ -0,0 +1,396 @@
+use anyhow::{Result, anyhow};
+use futures_util::{SinkExt, StreamExt};
+use once_cell::sync::Lazy;
+use serde_json::{Value, json};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+use tokio::time::{Duration, sleep};
+use tokio_tungstenite::accept_hdr_async;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use uuid::Uuid;
+
+const DEFAULT_MOCK_SCRIPT: &str = "confirm@500";
+const DEFAULT_MOCK_WALLET: &str = "MockWa11et1111111111111111111111111111111111";
+const MAX_REQUEST_BYTES: usize = 64 * 1024;
+
+static MOCK_BIND_ADDR: Lazy<Option<String>> = Lazy::new(|| {
+    std::env::var("BLOOM_MOCK_ADDR")
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+});
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum MockOutcome {
+    Confirm,
+    Fail(String),
+    Reject(String),
+    Unavailable,
+    Silent,
+}
+
+#[derive(Clone, Debug)]
+struct MockStep {
+    outcome: MockOutcome,
+    delay: Duration,
+}
+
+impl MockStep {
+    fn parse(text: &str) -> Result<Self> {
+        let (head, error) = match text.split_once('=') {
+            Some((head, error)) => (head.trim(), Some(error.trim().to_string())),
+            None => (text.trim(), None),
+        };
+        let (kind, delay_ms) = match head.split_once('@') {
+            Some((kind, delay)) => (
+                kind.trim(),
+                delay
+                    .trim()
+                    .parse::<u64>()
+                    .map_err(|_| anyhow!("invalid delay '{}' in mock step '{}'", delay, text))?,
+            ),
+            None => (head, 0),
+        };
+        let outcome = match kind.to_lowercase().as_str() {
+            "confirm" => MockOutcome::Confirm,
+            "fail" => MockOutcome::Fail(error.unwrap_or_else(|| "Transaction failed".to_string())),
+            "reject" => MockOutcome::Reject(error.unwrap_or_else(|| "Swap rejected".to_string())),
+            "unavailable" => MockOutcome::Unavailable,
+            "silent" => MockOutcome::Silent,
+            other => return Err(anyhow!("unknown mock outcome '{}'", other)),
+        };
+        Ok(MockStep {
+            outcome,
+            delay: Duration::from_millis(delay_ms),
+        })
+    }
+}
+
+fn parse_mock_script(script: &str) -> Result<Vec<MockStep>> {
+    let steps = script
+        .split(';')
+        .map(str::trim)
+        .filter(|step| !step.is_empty())
+        .map(MockStep::parse)
+        .collect::<Result<Vec<_>>>()?;
+    if steps.is_empty() {
+        return Err(anyhow!("mock script has no steps"));
+    }
+    Ok(steps)
+}
+
+struct MockState {
+    steps: Vec<MockStep>,
+    cursor: AtomicUsize,
+    wallets: Vec<Value>,
+    events: broadcast::Sender<(String, String)>,
+}
+
+impl MockState {
+    fn next_step(&self) -> MockStep {
+        let index = self.cursor.fetch_add(1, Ordering::Relaxed);
+        self.steps[index % self.steps.len()].clone()
+    }
+}
+
+pub fn mock_bind_addr() -> Option<&'static str> {
+    MOCK_BIND_ADDR.as_deref()
+}
+
+fn mock_wallets() -> Vec<Value> {
+    let configured = std::env::var("BLOOM_MOCK_WALLETS").unwrap_or_default();
+    let wallets: Vec<Value> = configured
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| match entry.split_once(':') {
+            Some((address, label)) => json!({ "address": address, "label": label }),
+            None => json!({ "address": entry, "label": Value::Null }),
+        })
+        .collect();
+    if wallets.is_empty() {
+        vec![json!({ "address": DEFAULT_MOCK_WALLET, "label": "mock" })]
+    } else {
+        wallets
+    }
+}
+
+pub async fn start_mock_bloom_server(addr: &str) -> Result<()> {
+    let script =
+        std::env::var("BLOOM_MOCK_SCRIPT").unwrap_or_else(|_| DEFAULT_MOCK_SCRIPT.to_string());
+    serve_mock_bloom(addr, &script).await?;
+    Ok(())
+}
+
+pub async fn serve_mock_bloom(addr: &str, script: &str) -> Result<SocketAddr> {
+    let steps = parse_mock_script(script)?;
+    let listener = TcpListener::bind(addr).await?;
+    let (events, _) = broadcast::channel(256);
+    let state = Arc::new(MockState {
+        steps,
+        cursor: AtomicUsize::new(0),
+        wallets: mock_wallets(),
+        events,
+    });
+    let local_addr = listener.local_addr()?;
+    log::info!(
+        "bloom_mock: listening on {} script=\"{}\"",
+        local_addr,
+        script
+    );
+
+    tokio::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, _)) => {
+                    let state = state.clone();
+                    tokio::spawn(async move {
+                        if let Err(e) = handle_connection(stream, state).await {
+                            log::warn!("bloom_mock: connection failed err=\"{}\"", e);
+                        }
+                    });
+                }
+                Err(e) => {
+                    log::warn!("bloom_mock: accept failed err=\"{}\"", e);
+                    sleep(Duration::from_millis(100)).await;
+                }
+            }
+        }
+    });
+    Ok(local_addr)
+}
+
+async fn handle_connection(stream: TcpStream, state: Arc<MockState>) -> Result<()> {
+    let mut method = [0u8; 4];
+    let mut peeked = 0;
+    while peeked < method.len() {
+        peeked = stream.peek(&mut method).await?;
+        if peeked == 0 {
+            return Ok(());
+        }
+        if peeked < method.len() {
+            sleep(Duration::from_millis(5)).await;
+        }
+    }
+    if &method == b"GET " {
+        serve_websocket(stream, state).await
+    } else {
+        serve_http(stream, state).await
+    }
+}
+
+async fn serve_websocket(stream: TcpStream, state: Arc<MockState>) -> Result<()> {
+    let mut token = String::new();
+    let mut ws_stream = accept_hdr_async(
+        stream,
+        |request: &Request, response: Response| -> Result<Response, ErrorResponse> {
+            token = request.uri().query().unwrap_or_default().to_string();
+            Ok(response)
+        },
+    )
+    .await?;
+    let mut events = state.events.subscribe();
+    log::info!("bloom_mock: websocket client connected");
+
+    loop {
+        tokio::select! {
+            event = events.recv() => {
+                match event {
+                    Ok((event_token, payload)) => {
+                        if event_token == token {
+                            ws_stream.send(Message::Text(payload)).await?;
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        log::warn!("bloom_mock: websocket client lagged skipped={}", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+            msg = ws_stream.next() => {
+                match msg {
+                    Some(Ok(Message::Close(_))) | None => break,
+                    Some(Ok(_)) => {}
+                    Some(Err(e)) => return Err(e.into()),
+                }
+            }
+        }
+    }
+    log::info!("bloom_mock: websocket client disconnected");
+    Ok(())
+}
+
+async fn read_http_request(stream: &mut TcpStream) -> Result<(String, String, Vec<u8>)> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let header_end = loop {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            return Err(anyhow!("connection closed before request completed"));
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+        if let Some(position) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+            break position + 4;
+        }
+        if buffer.len() > MAX_REQUEST_BYTES {
+            return Err(anyhow!("request headers too large"));
+        }
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..header_end]).to_string();
+    let mut lines = head.lines();
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default().to_string();
+    let path = request_line.next().unwrap_or_default().to_string();
+    let content_length = lines
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+        .unwrap_or(0);
+    if content_length > MAX_REQUEST_BYTES {
+        return Err(anyhow!("request body too large"));
+    }
+
+    let mut body = buffer[header_end..].to_vec();
+    while body.len() < content_length {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            return Err(anyhow!("connection closed before body completed"));
+        }
+        body.extend_from_slice(&chunk[..read]);
+    }
+    body.truncate(content_length);
+    Ok((method, path, body))
+}
+
+async fn write_json(stream: &mut TcpStream, status: u16, body: Value) -> Result<()> {
+    let reason = match status {
+        200 => "OK",
+        404 => "Not Found",
+        503 => "Service Unavailable",
+        _ => "Bad Request",
+    };
+    let body = body.to_string();
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+async fn serve_http(mut stream: TcpStream, state: Arc<MockState>) -> Result<()> {
+    let (method, path, body) = read_http_request(&mut stream).await?;
+    let path = path.split('?').next().unwrap_or_default().to_string();
+    if method != "POST" {
+        return write_json(&mut stream, 404, json!({ "error": "not found" })).await;
+    }
+    let request: Value = serde_json::from_slice(&body).unwrap_or(Value::Null);
+
+    if path.ends_with("/get-wallets") {
+        write_json(&mut stream, 200, json!({ "wallets": state.wallets })).await
+    } else if path.ends_with("/api/extension-swap") {
+        let (status, response) = handle_swap(&state, &request).await;
+        write_json(&mut stream, status, response).await
+    } else {
+        write_json(&mut stream, 404, json!({ "error": "not found" })).await
+    }
+}
+
+async fn handle_swap(state: &Arc<MockState>, request: &Value) -> (u16, Value) {
+    let field = |name: &str| request[name].as_str().unwrap_or_default().to_string();
+    let id = field("id");
+    if id.is_empty() {
+        return (400, json!({ "success": false, "error": "missing swap id" }));
+    }
+    if id.starts_with("QT-WARMER-") {
+        return (200, json!({ "success": true, "error": Value::Null }));
+    }
+
+    let step = state.next_step();
+    log::info!(
+        "bloom_mock: swap id={} mint={} outcome={:?} delay_ms={}",
+        id,
+        field("address"),
+        step.outcome,
+        step.delay.as_millis()
+    );
+    match step.outcome {
+        MockOutcome::Unavailable => {
+            sleep(step.delay).await;
+            (
+                503,
+                json!({ "success": false, "error": "mock region unavailable" }),
+            )
+        }
+        MockOutcome::Reject(error) => {
+            sleep(step.delay).await;
+            (200, json!({ "success": false, "error": error }))
+        }
+        MockOutcome::Silent => (200, json!({ "success": true, "error": Value::Null })),
+        outcome => {
+            let token = field("auth_token");
+            let wallet = request["wallets"][0]["address"]
+                .as_str()
+                .unwrap_or(DEFAULT_MOCK_WALLET)
+                .to_string();
+            let event = json!({
+                "id": id,
+                "side": field("side").to_lowercase(),
+                "token_name": "Mock Token",
+                "token_address": field("address"),
+                "wallet_address": wallet,
+            });
+            let events = state.events.clone();
+            tokio::spawn(async move {
+                publish_status(&events, &token, &event, 0, None);
+                sleep(step.delay).await;
+                match outcome {
+                    MockOutcome::Fail(error) => {
+                        publish_status(&events, &token, &event, 2, Some(error));
+                    }
+                    _ => publish_status(&events, &token, &event, 1, None),
+                }
+            });
+            (200, json!({ "success": true, "error": Value::Null }))
+        }
+    }
+}
+
+fn publish_status(
+    events: &broadcast::Sender<(String, String)>,
+    token: &str,
+    event: &Value,
+    status: u8,
+    error: Option<String>,
+) {
+    let mut payload = event.clone();
+    payload["status"] = json!(status);
+    payload["tx_hash"] = match status {
+        1 => json!(format!("mock{}", Uuid::new_v4().simple())),
+        _ => Value::Null,
+    };
+    payload["error"] = json!(error);
+    payload["error_code"] = match status {
+        2 => json!(1),
+        _ => Value::Null,
+    };
+    if events
+        .send((token.to_string(), payload.to_string()))
+        .is_err()
+    {
+        log::warn!(
+            "bloom_mock: no websocket client subscribed for swap id={}",
+            event["id"]
+        );
+    }
+}
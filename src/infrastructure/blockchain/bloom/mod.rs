@@ -1,6 +1,19 @@
+pub mod auth;
 pub mod client;
+#[cfg(feature = "mock-bloom")]
+pub mod mock;
+pub mod regions;
+pub mod registry;
 pub mod types;
 pub mod ws;
 
+pub use auth::{BloomCredentials, account_key};
 pub use client::buy;
-pub use ws::run_bloom_ws_listener;
+#[cfg(feature = "mock-bloom")]
+pub use mock::{mock_bind_addr, serve_mock_bloom, start_mock_bloom_server};
+pub use registry::{SwapEvent, SwapOutcome, apply_mint_event, subscribe_swap};
+pub use types::{BloomFailure, BloomSwapOptions};
+pub use ws::{
+    ensure_bloom_ws_listener, listener_account_keys, listener_status, run_bloom_ws_listener,
+    stop_bloom_ws_listener,
+};
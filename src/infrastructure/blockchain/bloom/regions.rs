@@ -0,0 +1,158 @@
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+const DEFAULT_BLOOM_REGIONS: [&str; 3] = ["eu1", "us1", "ap1"];
+const BLOOM_HOST_SUFFIX: &str = ".bloom-ext.app";
+const LOCAL_REGION: &str = "local";
+const REGION_FAILURE_COOLDOWN: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Debug, Default)]
+struct RegionHealth {
+    healthy: Option<bool>,
+    latency_ms: Option<f64>,
+    last_failure: Option<Instant>,
+}
+
+impl RegionHealth {
+    fn cooling_down(&self) -> bool {
+        self.last_failure
+            .is_some_and(|failed_at| failed_at.elapsed() < REGION_FAILURE_COOLDOWN)
+    }
+
+    fn available(&self) -> bool {
+        self.healthy != Some(false) && !self.cooling_down()
+    }
+}
+
+static BLOOM_API_BASE_URL: Lazy<Option<String>> = Lazy::new(|| {
+    std::env::var("BLOOM_API_BASE_URL")
+        .ok()
+        .map(|value| value.trim().trim_end_matches('/').to_string())
+        .filter(|value| !value.is_empty())
+        .or_else(mock_base_url)
+});
+
+#[cfg(feature = "mock-bloom")]
+fn mock_base_url() -> Option<String> {
+    super::mock::mock_bind_addr().map(|addr| format!("http://{}", addr))
+}
+
+#[cfg(not(feature = "mock-bloom"))]
+fn mock_base_url() -> Option<String> {
+    None
+}
+
+static BLOOM_REGIONS: Lazy<Vec<String>> = Lazy::new(|| {
+    if BLOOM_API_BASE_URL.is_some() {
+        return vec![LOCAL_REGION.to_string()];
+    }
+    let mut regions: Vec<String> = std::env::var("BLOOM_REGIONS")
+        .ok()
+        .map(|value| {
+            value
+                .split(',')
+                .map(|region| region.trim().to_lowercase())
+                .filter(|region| !region.is_empty())
+                .collect()
+        })
+        .filter(|regions: &Vec<String>| !regions.is_empty())
+        .unwrap_or_else(|| {
+            DEFAULT_BLOOM_REGIONS
+                .iter()
+                .map(|region| region.to_string())
+                .collect()
+        });
+    if let Some(preferred) = preferred_region() {
+        regions.retain(|region| *region != preferred);
+        regions.insert(0, preferred);
+    }
+    regions
+});
+
+static REGION_HEALTH: Lazy<Mutex<HashMap<String, RegionHealth>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn preferred_region() -> Option<String> {
+    std::env::var("BLOOM_REGION")
+        .ok()
+        .map(|region| region.trim().to_lowercase())
+        .filter(|region| !region.is_empty())
+}
+
+pub fn bloom_regions() -> &'static [String] {
+    &BLOOM_REGIONS
+}
+
+pub fn api_base_url_override() -> Option<&'static str> {
+    BLOOM_API_BASE_URL.as_deref()
+}
+
+pub fn region_base_url(region: &str) -> String {
+    match api_base_url_override() {
+        Some(base_url) => base_url.to_string(),
+        None => format!("http://{}{}", region, BLOOM_HOST_SUFFIX),
+    }
+}
+
+pub fn region_swap_url(region: &str) -> String {
+    format!("{}/api/extension-swap", region_base_url(region))
+}
+
+pub fn region_wallets_url(region: &str) -> String {
+    match api_base_url_override() {
+        Some(base_url) => format!("{}/get-wallets", base_url),
+        None => format!("https://{}-tg{}/get-wallets", region, BLOOM_HOST_SUFFIX),
+    }
+}
+
+pub fn region_from_url(url: &str) -> Option<String> {
+    if let Some(base_url) = api_base_url_override() {
+        return url.starts_with(base_url).then(|| LOCAL_REGION.to_string());
+    }
+    let host = url
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .split('/')
+        .next()?;
+    let region = host.strip_suffix(BLOOM_HOST_SUFFIX)?;
+    bloom_regions()
+        .iter()
+        .find(|known| known.as_str() == region)
+        .cloned()
+}
+
+pub fn record_region_probe(region: &str, healthy: bool, latency_ms: Option<f64>) {
+    let mut health = REGION_HEALTH.lock();
+    let entry = health.entry(region.to_string()).or_default();
+    entry.healthy = Some(healthy);
+    if healthy {
+        entry.latency_ms = latency_ms;
+    }
+}
+
+pub fn record_region_failure(region: &str) {
+    let mut health = REGION_HEALTH.lock();
+    let entry = health.entry(region.to_string()).or_default();
+    entry.last_failure = Some(Instant::now());
+}
+
+pub fn ranked_regions() -> Vec<String> {
+    let health = REGION_HEALTH.lock();
+    let mut ranked: Vec<(usize, String, bool, f64)> = bloom_regions()
+        .iter()
+        .enumerate()
+        .map(|(index, region)| {
+            let state = health.get(region).cloned().unwrap_or_default();
+            (
+                index,
+                region.clone(),
+                state.available(),
+                state.latency_ms.unwrap_or(f64::MAX),
+            )
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.2.cmp(&a.2).then(a.3.total_cmp(&b.3)).then(a.0.cmp(&b.0)));
+    ranked.into_iter().map(|(_, region, _, _)| region).collect()
+}
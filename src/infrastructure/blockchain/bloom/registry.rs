@@ -0,0 +1,244 @@
+use super::types::BloomFailure;
+use super::ws::is_listener_connected;
+use crate::BloomBuyAck;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::sync::oneshot;
+
+const TERMINAL_RETENTION: Duration = Duration::from_secs(120);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SwapState {
+    Submitted,
+    Pending,
+    Confirmed,
+    Failed,
+    Expired,
+}
+
+impl SwapState {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SwapState::Submitted => "submitted",
+            SwapState::Pending => "pending",
+            SwapState::Confirmed => "confirmed",
+            SwapState::Failed => "failed",
+            SwapState::Expired => "expired",
+        }
+    }
+
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            SwapState::Confirmed | SwapState::Failed | SwapState::Expired
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum SwapEvent {
+    Pending,
+    Confirmed {
+        token_name: Option<String>,
+        signature: Option<String>,
+    },
+    Failed {
+        status: u8,
+        error: Option<String>,
+        error_code: Option<i64>,
+    },
+    Expired,
+}
+
+#[derive(Clone, Debug)]
+pub enum SwapOutcome {
+    Confirmed(BloomBuyAck),
+    Failed(BloomFailure),
+    Expired,
+}
+
+struct SwapRecord {
+    mint: String,
+    side: String,
+    account: String,
+    state: SwapState,
+    started_at: Instant,
+    updated_at: Instant,
+    outcome: Option<SwapOutcome>,
+    waiters: Vec<oneshot::Sender<SwapOutcome>>,
+}
+
+static SWAP_REGISTRY: Lazy<Mutex<HashMap<String, SwapRecord>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+pub fn register_swap(id: &str, mint: &str, side: &str, account: &str) {
+    let now = Instant::now();
+    SWAP_REGISTRY.lock().insert(
+        id.to_string(),
+        SwapRecord {
+            mint: mint.to_string(),
+            side: side.to_string(),
+            account: account.to_string(),
+            state: SwapState::Submitted,
+            started_at: now,
+            updated_at: now,
+            outcome: None,
+            waiters: Vec::new(),
+        },
+    );
+    log::debug!(
+        "bloom_swaps: submitted id={} mint={} side={} account={}",
+        id,
+        mint,
+        side,
+        account
+    );
+}
+
+pub fn discard_swap(id: &str) {
+    SWAP_REGISTRY.lock().remove(id);
+}
+
+pub fn subscribe_swap(id: &str) -> Option<oneshot::Receiver<SwapOutcome>> {
+    let mut registry = SWAP_REGISTRY.lock();
+    let record = registry.get_mut(id)?;
+    let (tx, rx) = oneshot::channel();
+    match record.outcome.clone() {
+        Some(outcome) => {
+            let _ = tx.send(outcome);
+        }
+        None => record.waiters.push(tx),
+    }
+    Some(rx)
+}
+
+fn resolve_outcome(record: &SwapRecord, event: SwapEvent) -> (SwapState, Option<SwapOutcome>) {
+    match event {
+        SwapEvent::Pending => (SwapState::Pending, None),
+        SwapEvent::Confirmed {
+            token_name,
+            signature,
+        } => (
+            SwapState::Confirmed,
+            Some(SwapOutcome::Confirmed(BloomBuyAck {
+                pending_time: record.started_at,
+                success_time: Instant::now(),
+                token_name,
+                signature,
+            })),
+        ),
+        SwapEvent::Failed {
+            status,
+            error,
+            error_code,
+        } => (
+            SwapState::Failed,
+            Some(SwapOutcome::Failed(BloomFailure::new(
+                status, error, error_code,
+            ))),
+        ),
+        SwapEvent::Expired => (SwapState::Expired, Some(SwapOutcome::Expired)),
+    }
+}
+
+fn apply_to_record(id: &str, record: &mut SwapRecord, event: SwapEvent, source: &str) -> bool {
+    if record.state.is_terminal() {
+        log::debug!(
+            "bloom_swaps: ignoring {:?} for {} swap id={} source={}",
+            event,
+            record.state.label(),
+            id,
+            source
+        );
+        return false;
+    }
+    let (state, outcome) = resolve_outcome(record, event);
+    if state == record.state {
+        return false;
+    }
+    let elapsed_ms = record.started_at.elapsed().as_millis();
+    log::info!(
+        "bloom_swaps: transition id={} mint={} side={} {}->{} source={} elapsed_ms={} waiters={}",
+        id,
+        record.mint,
+        record.side,
+        record.state.label(),
+        state.label(),
+        source,
+        elapsed_ms,
+        record.waiters.len()
+    );
+    record.state = state;
+    record.updated_at = Instant::now();
+    if let Some(outcome) = outcome {
+        for waiter in record.waiters.drain(..) {
+            let _ = waiter.send(outcome.clone());
+        }
+        record.outcome = Some(outcome);
+    }
+    true
+}
+
+pub fn apply_swap_event(id: &str, event: SwapEvent, source: &str) -> bool {
+    let mut registry = SWAP_REGISTRY.lock();
+    match registry.get_mut(id) {
+        Some(record) => apply_to_record(id, record, event, source),
+        None => {
+            log::warn!(
+                "bloom_swaps: event {:?} for unknown swap id={} source={}",
+                event,
+                id,
+                source
+            );
+            false
+        }
+    }
+}
+
+pub fn apply_mint_event(mint: &str, event: SwapEvent, source: &str) -> Option<String> {
+    let mut registry = SWAP_REGISTRY.lock();
+    let candidates: Vec<String> = registry
+        .iter()
+        .filter(|(_, record)| {
+            record.mint == mint
+                && !record.state.is_terminal()
+                && !is_listener_connected(&record.account)
+        })
+        .map(|(id, _)| id.clone())
+        .collect();
+    let [id] = candidates.as_slice() else {
+        if candidates.len() > 1 {
+            log::warn!(
+                "bloom_swaps: dropping ambiguous {:?} mint={} open_swaps={} source={}",
+                event,
+                mint,
+                candidates.len(),
+                source
+            );
+        }
+        return None;
+    };
+    let id = id.clone();
+    let record = registry.get_mut(&id)?;
+    apply_to_record(&id, record, event, source).then_some(id)
+}
+
+pub fn expire_stale_swaps(max_age: Duration) -> usize {
+    let mut registry = SWAP_REGISTRY.lock();
+    let stale: Vec<String> = registry
+        .iter()
+        .filter(|(_, record)| !record.state.is_terminal() && record.started_at.elapsed() > max_age)
+        .map(|(id, _)| id.clone())
+        .collect();
+    for id in &stale {
+        if let Some(record) = registry.get_mut(id) {
+            apply_to_record(id, record, SwapEvent::Expired, "cleanup");
+        }
+    }
+    registry.retain(|_, record| {
+        !record.state.is_terminal() || record.updated_at.elapsed() < TERMINAL_RETENTION
+    });
+    stale.len()
+}
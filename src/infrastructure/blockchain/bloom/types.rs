@@ -6,6 +6,16 @@ pub struct BloomWallet<'a> {
     pub label: &'a str,
 }
 
+#[derive(Clone, Debug, Default)]
+pub struct BloomSwapOptions {
+    pub processor_tip: f64,
+    pub skip_if_bought: bool,
+    pub anti_mev: bool,
+    pub auto_tip: bool,
+    pub dev_sell: Option<String>,
+    pub hedged: bool,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "snake_case")]
 pub struct BloomSwapPayload<'a> {
@@ -30,3 +40,168 @@ pub(super) struct BloomSwapResponse {
     pub success: bool,
     pub error: Option<String>,
 }
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BloomFailureKind {
+    InsufficientBalance,
+    SlippageExceeded,
+    NotTradable,
+    AlreadyBought,
+    AuthExpired,
+    Unknown,
+}
+
+impl BloomFailureKind {
+    pub fn classify(error: Option<&str>, error_code: Option<i64>) -> Self {
+        if matches!(error_code, Some(401 | 403)) {
+            return BloomFailureKind::AuthExpired;
+        }
+        let Some(error) = error else {
+            return BloomFailureKind::Unknown;
+        };
+        let error = error.to_lowercase();
+        let matches_any = |needles: &[&str]| needles.iter().any(|needle| error.contains(needle));
+        if matches_any(&["insufficient", "not enough", "balance too low"]) {
+            BloomFailureKind::InsufficientBalance
+        } else if matches_any(&["slippage", "price moved", "too much sol required"]) {
+            BloomFailureKind::SlippageExceeded
+        } else if matches_any(&["already bought", "already holding", "skip if bought"]) {
+            BloomFailureKind::AlreadyBought
+        } else if matches_any(&[
+            "auth expired",
+            "auth token expired",
+            "invalid auth token",
+            "authentication failed",
+            "not authenticated",
+            "token expired",
+            "session expired",
+            "unauthorized",
+            "login required",
+            "log in again",
+        ]) {
+            BloomFailureKind::AuthExpired
+        } else if matches_any(&[
+            "not tradable",
+            "not tradeable",
+            "no pool",
+            "pool not found",
+            "no route",
+            "invalid token",
+            "token not found",
+        ]) {
+            BloomFailureKind::NotTradable
+        } else {
+            BloomFailureKind::Unknown
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            BloomFailureKind::InsufficientBalance => "Insufficient Balance",
+            BloomFailureKind::SlippageExceeded => "Slippage Exceeded",
+            BloomFailureKind::NotTradable => "Token Not Tradable",
+            BloomFailureKind::AlreadyBought => "Already Bought",
+            BloomFailureKind::AuthExpired => "Bloom Auth Expired",
+            BloomFailureKind::Unknown => "Unknown Error",
+        }
+    }
+
+    pub fn suggested_action(&self) -> &'static str {
+        match self {
+            BloomFailureKind::InsufficientBalance => {
+                "Top up the Bloom wallet or lower the task buy amount."
+            }
+            BloomFailureKind::SlippageExceeded => {
+                "Raise the buy slippage or enable retries with a slippage step."
+            }
+            BloomFailureKind::NotTradable => {
+                "Wait until the token has a tradable pool or check the mint address."
+            }
+            BloomFailureKind::AlreadyBought => {
+                "No action needed; disable Skip If Bought to buy again."
+            }
+            BloomFailureKind::AuthExpired => {
+                "Send a fresh token for this account from Bloom Accounts in the bot menu."
+            }
+            BloomFailureKind::Unknown => "Check the Bloom bot chat for details and retry manually.",
+        }
+    }
+
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            BloomFailureKind::SlippageExceeded | BloomFailureKind::Unknown
+        )
+    }
+
+    pub fn allows_native_fallback(&self) -> bool {
+        matches!(
+            self,
+            BloomFailureKind::AuthExpired | BloomFailureKind::Unknown
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct BloomFailure {
+    pub kind: BloomFailureKind,
+    pub status: u8,
+    pub error: Option<String>,
+    pub error_code: Option<i64>,
+}
+
+impl BloomFailure {
+    pub fn new(status: u8, error: Option<String>, error_code: Option<i64>) -> Self {
+        BloomFailure {
+            kind: BloomFailureKind::classify(error.as_deref(), error_code),
+            status,
+            error,
+            error_code,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        let detail = self.error.as_deref().unwrap_or("no error message");
+        match self.error_code {
+            Some(code) => format!("{}: {} (code {})", self.kind.label(), detail, code),
+            None => format!("{}: {}", self.kind.label(), detail),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_expired_auth() {
+        for error in [
+            "Auth token expired",
+            "Unauthorized",
+            "Session expired, login required",
+        ] {
+            assert_eq!(
+                BloomFailureKind::classify(Some(error), None),
+                BloomFailureKind::AuthExpired
+            );
+        }
+        assert_eq!(
+            BloomFailureKind::classify(None, Some(401)),
+            BloomFailureKind::AuthExpired
+        );
+    }
+
+    #[test]
+    fn authority_errors_are_not_auth_failures() {
+        for error in [
+            "Invalid mint authority",
+            "Freeze authority is enabled",
+            "Program failed: incorrect authority",
+        ] {
+            assert_ne!(
+                BloomFailureKind::classify(Some(error), None),
+                BloomFailureKind::AuthExpired
+            );
+        }
+    }
+}
@@ -1,12 +1,13 @@
-use crate::{
-    ACTIVE_BLOOM_SWAPS, BLOOM_WS_CONNECTION, BloomBuyAck, BloomSwapTracker,
-    BloomWsConnectionStatus, PENDING_BLOOM_RESPONSES,
-};
+use super::auth::BloomCredentials;
+use super::registry::{SwapEvent, apply_swap_event, expire_stale_swaps};
+use crate::{BLOOM_WS_CONNECTION, BloomWsConnectionStatus};
 use anyhow::Result;
 use futures_util::{SinkExt, StreamExt};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
 use serde::Deserialize;
-use std::convert::TryFrom;
-use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use std::collections::HashMap;
+use tokio::task::JoinHandle;
 use tokio::time::{Duration, MissedTickBehavior, sleep};
 use tokio_tungstenite::connect_async;
 use tokio_tungstenite::tungstenite::Message;
@@ -34,11 +35,16 @@ enum ConnectOutcome {
     Skipped { reason: String },
 }
 
-struct BloomAuthData {
-    token: Option<String>,
-    expires_at: Option<i64>,
+struct BloomWsListener {
+    credentials: BloomCredentials,
+    status: BloomWsConnectionStatus,
+    message: String,
+    handle: JoinHandle<()>,
 }
 
+static BLOOM_WS_LISTENERS: Lazy<Mutex<HashMap<String, BloomWsListener>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
 #[derive(Debug, Deserialize, Default)]
 #[serde(default)]
 struct BloomWsMessage {
@@ -55,20 +61,105 @@ struct BloomWsMessage {
     error_code: Option<i64>,
 }
 
-pub async fn run_bloom_ws_listener() {
-    let mut backoff_ms = 1000u64;
+fn bloom_ws_base_url() -> String {
+    std::env::var("BLOOM_WS_URL")
+        .ok()
+        .map(|value| value.trim().trim_end_matches('/').to_string())
+        .filter(|value| !value.is_empty())
+        .or_else(mock_ws_url)
+        .unwrap_or_else(|| format!("wss://{}", BLOOM_WS_HOST))
+}
 
-    update_ws_status(
-        BloomWsConnectionStatus::Connecting,
-        "Bloom WebSocket connection is starting.",
-    );
+#[cfg(feature = "mock-bloom")]
+fn mock_ws_url() -> Option<String> {
+    super::mock::mock_bind_addr().map(|addr| format!("ws://{}", addr))
+}
 
+#[cfg(not(feature = "mock-bloom"))]
+fn mock_ws_url() -> Option<String> {
+    None
+}
+
+pub async fn run_bloom_ws_listener() {
     tokio::spawn(async {
         cleanup_stale_swaps().await;
     });
 
+    match BloomCredentials::from_env() {
+        Some(credentials) => {
+            ensure_bloom_ws_listener(credentials);
+        }
+        None => refresh_aggregate_status(),
+    }
+}
+
+pub fn ensure_bloom_ws_listener(credentials: BloomCredentials) -> bool {
+    let mut listeners = BLOOM_WS_LISTENERS.lock();
+    if let Some(existing) = listeners.get(&credentials.account_key) {
+        if existing.credentials == credentials && !existing.handle.is_finished() {
+            return false;
+        }
+        existing.handle.abort();
+        log::info!(
+            "bloom_ws: restarting listener account={} after credential update",
+            credentials.account_key
+        );
+    } else {
+        log::info!(
+            "bloom_ws: starting listener account={}",
+            credentials.account_key
+        );
+    }
+    let handle = tokio::spawn(run_listener_loop(credentials.clone()));
+    listeners.insert(
+        credentials.account_key.clone(),
+        BloomWsListener {
+            credentials,
+            status: BloomWsConnectionStatus::Connecting,
+            message: "Bloom WebSocket connection is starting.".to_string(),
+            handle,
+        },
+    );
+    drop(listeners);
+    refresh_aggregate_status();
+    true
+}
+
+pub fn stop_bloom_ws_listener(account_key: &str) -> bool {
+    let removed = BLOOM_WS_LISTENERS.lock().remove(account_key);
+    let Some(listener) = removed else {
+        return false;
+    };
+    listener.handle.abort();
+    log::info!("bloom_ws: stopped listener account={}", account_key);
+    refresh_aggregate_status();
+    true
+}
+
+pub fn listener_account_keys() -> Vec<String> {
+    BLOOM_WS_LISTENERS.lock().keys().cloned().collect()
+}
+
+pub fn listener_status(account_key: &str) -> Option<(BloomWsConnectionStatus, String)> {
+    BLOOM_WS_LISTENERS
+        .lock()
+        .get(account_key)
+        .map(|listener| (listener.status, listener.message.clone()))
+}
+
+pub fn is_listener_connected(account_key: &str) -> bool {
+    BLOOM_WS_LISTENERS
+        .lock()
+        .get(account_key)
+        .is_some_and(|listener| listener.status == BloomWsConnectionStatus::Connected)
+}
+
+async fn run_listener_loop(credentials: BloomCredentials) {
+    let account_key = credentials.account_key.clone();
+    let mut backoff_ms = 1000u64;
+
     loop {
-        match connect_once().await {
+        match connect_once(&credentials).await {
             Ok(ConnectOutcome::SessionClosed { reason }) => {
                 backoff_ms = 1000;
                 let delay_secs = ((backoff_ms + 999) / 1000).max(1);
@@ -79,7 +170,7 @@ pub async fn run_bloom_ws_listener() {
                     delay_secs,
                     unit
                 );
-                update_ws_status(BloomWsConnectionStatus::Disconnected, message);
+                update_ws_status(&account_key, BloomWsConnectionStatus::Disconnected, message);
             }
             Ok(ConnectOutcome::Skipped { reason }) => {
                 backoff_ms = 30000;
@@ -91,7 +182,7 @@ pub async fn run_bloom_ws_listener() {
                     delay_secs,
                     unit
                 );
-                update_ws_status(BloomWsConnectionStatus::Unavailable, message);
+                update_ws_status(&account_key, BloomWsConnectionStatus::Unavailable, message);
             }
             Err(err) => {
                 backoff_ms = (backoff_ms * 2).min(30000);
@@ -103,17 +194,69 @@ pub async fn run_bloom_ws_listener() {
                     delay_secs,
                     unit
                 );
-                update_ws_status(BloomWsConnectionStatus::Disconnected, message);
+                update_ws_status(&account_key, BloomWsConnectionStatus::Disconnected, message);
             }
         }
         sleep(Duration::from_millis(backoff_ms)).await;
     }
 }
 
-fn update_ws_status(status: BloomWsConnectionStatus, message: impl Into<String>) {
+fn update_ws_status(
+    account_key: &str,
+    status: BloomWsConnectionStatus,
+    message: impl Into<String>,
+) {
+    {
+        let mut listeners = BLOOM_WS_LISTENERS.lock();
+        let Some(listener) = listeners.get_mut(account_key) else {
+            return;
+        };
+        listener.status = status;
+        listener.message = message.into();
+    }
+    refresh_aggregate_status();
+}
+
+fn refresh_aggregate_status() {
+    let (status, message) = {
+        let listeners = BLOOM_WS_LISTENERS.lock();
+        let total = listeners.len();
+        let connected = listeners
+            .values()
+            .filter(|listener| listener.status == BloomWsConnectionStatus::Connected)
+            .count();
+        if total == 0 {
+            (
+                BloomWsConnectionStatus::Unavailable,
+                "Bloom WS: No Bloom account configured.".to_string(),
+            )
+        } else if total == 1 {
+            let listener = listeners.values().next().expect("one listener");
+            (listener.status, listener.message.clone())
+        } else {
+            let status = if connected == total {
+                BloomWsConnectionStatus::Connected
+            } else if connected > 0
+                || listeners
+                    .values()
+                    .any(|listener| listener.status == BloomWsConnectionStatus::Connecting)
+            {
+                BloomWsConnectionStatus::Connecting
+            } else {
+                BloomWsConnectionStatus::Disconnected
+            };
+            (
+                status,
+                format!(
+                    "Bloom WS: {} of {} account listeners connected.",
+                    connected, total
+                ),
+            )
+        }
+    };
     let mut state = BLOOM_WS_CONNECTION.lock();
     state.status = status;
-    state.message = message.into();
+    state.message = message;
 }
 
 fn normalize_reason(reason: &str) -> String {
@@ -196,30 +339,26 @@ fn build_bloom_ws_request(url: Url) -> Result<Request<()>> {
         HeaderName::from_static("sec-ch-ua-platform"),
         HeaderValue::from_static(BLOOM_WS_SEC_CH_UA_PLATFORM),
     );
-    headers.insert(header::HOST, HeaderValue::from_static(BLOOM_WS_HOST));
+    if request.uri().host() == Some(BLOOM_WS_HOST) {
+        request
+            .headers_mut()
+            .insert(header::HOST, HeaderValue::from_static(BLOOM_WS_HOST));
+    }
     Ok(request)
 }
 
-async fn connect_once() -> Result<ConnectOutcome> {
-    let BloomAuthData { token, expires_at } = load_bloom_auth_data();
-    let token = match token.map(|value| value.trim().to_string()) {
-        Some(value) if !value.is_empty() => value,
-        _ => {
-            log::info!("bloom_ws: connection skipped: invalid or expired auth token");
-            update_ws_status(
-                BloomWsConnectionStatus::Unavailable,
-                "Bloom WS: Authentication token missing or expired.",
+async fn connect_once(credentials: &BloomCredentials) -> Result<ConnectOutcome> {
+    let account_key = credentials.account_key.as_str();
+    let token = match credentials.usable_token() {
+        Ok(token) => token.to_string(),
+        Err(err) => {
+            log::info!(
+                "bloom_ws: connection skipped account={} err=\"{}\"",
+                account_key,
+                err
             );
-            return Ok(ConnectOutcome::Skipped {
-                reason: "Authentication token missing or expired".to_string(),
-            });
-        }
-    };
-    if let Some(expires_at) = expires_at {
-        let now_ms = current_time_millis()?;
-        if now_ms > expires_at {
-            log::info!("bloom_ws: connection skipped: invalid or expired auth token");
             update_ws_status(
+                account_key,
                 BloomWsConnectionStatus::Unavailable,
                 "Bloom WS: Authentication token missing or expired.",
             );
@@ -227,11 +366,12 @@ async fn connect_once() -> Result<ConnectOutcome> {
                 reason: "Authentication token missing or expired".to_string(),
             });
         }
-    }
-    let url = match Url::parse(&format!("wss://{}?{}", BLOOM_WS_HOST, token)) {
+    };
+    let url = match Url::parse(&format!("{}?{}", bloom_ws_base_url(), token)) {
         Ok(parsed) => parsed,
         Err(err) => {
             update_ws_status(
+                account_key,
                 BloomWsConnectionStatus::Disconnected,
                 format!("Bloom WS: URL parsing failed ({})", err),
             );
@@ -243,6 +383,7 @@ async fn connect_once() -> Result<ConnectOutcome> {
         Ok(value) => value,
         Err(err) => {
             update_ws_status(
+                account_key,
                 BloomWsConnectionStatus::Disconnected,
                 format!("Bloom WS: Request construction failed ({})", err),
             );
@@ -252,6 +393,7 @@ async fn connect_once() -> Result<ConnectOutcome> {
     let (mut ws_stream, response) = match connect_async(request).await {
         Err(err) => {
             update_ws_status(
+                account_key,
                 BloomWsConnectionStatus::Disconnected,
                 format!("Bloom WS: Handshake failed ({})", err),
             );
@@ -264,8 +406,9 @@ async fn connect_once() -> Result<ConnectOutcome> {
         response.status(),
         response.headers()
     );
-    log::info!("bloom_ws: connected");
+    log::info!("bloom_ws: connected account={}", account_key);
     update_ws_status(
+        account_key,
         BloomWsConnectionStatus::Connected,
         "Bloom WebSocket connection established.",
     );
@@ -278,12 +421,12 @@ async fn connect_once() -> Result<ConnectOutcome> {
             msg = ws_stream.next() => {
                 match msg {
                     Some(Ok(Message::Text(text))) => {
-                        handle_message_text(&text);
+                        handle_message_text(account_key, &text);
                     }
                     Some(Ok(Message::Binary(data))) => {
                         let len = data.len();
                         if let Ok(text) = String::from_utf8(data) {
-                            handle_message_text(&text);
+                            handle_message_text(account_key, &text);
                         } else {
                             log::warn!(
                                 "bloom_ws: binary message not valid utf8 len={}",
@@ -295,6 +438,7 @@ async fn connect_once() -> Result<ConnectOutcome> {
                     Some(Ok(Message::Ping(payload))) => {
                         if let Err(err) = ws_stream.send(Message::Pong(payload)).await {
                             update_ws_status(
+                                account_key,
                                 BloomWsConnectionStatus::Disconnected,
                                 format!("Bloom WebSocket ping response failed: {}", err),
                             );
@@ -307,6 +451,7 @@ async fn connect_once() -> Result<ConnectOutcome> {
                     }
                     Some(Err(err)) => {
                         update_ws_status(
+                            account_key,
                             BloomWsConnectionStatus::Disconnected,
                             format!("Bloom WebSocket stream error: {}", err),
                         );
@@ -321,6 +466,7 @@ async fn connect_once() -> Result<ConnectOutcome> {
             _ = keepalive.tick() => {
                 if let Err(err) = ws_stream.send(Message::Text("keepalive".to_string())).await {
                     update_ws_status(
+                        account_key,
                         BloomWsConnectionStatus::Disconnected,
                         format!("Bloom WebSocket keepalive failed: {}", err),
                     );
@@ -336,6 +482,7 @@ async fn connect_once() -> Result<ConnectOutcome> {
         close_reason
     };
     update_ws_status(
+        account_key,
         BloomWsConnectionStatus::Disconnected,
         resolved_reason.clone(),
     );
@@ -361,12 +508,12 @@ fn describe_close_frame(frame: Option<CloseFrame<'_>>) -> String {
     }
 }
 
-fn handle_message_text(text: &str) {
+fn handle_message_text(account_key: &str, text: &str) {
     if text.trim().is_empty() || text.trim().eq_ignore_ascii_case("keepalive") {
         return;
     }
     match serde_json::from_str::<BloomWsMessage>(text) {
-        Ok(message) => process_ws_message(message),
+        Ok(message) => process_ws_message(account_key, message),
         Err(err) => {
             log::warn!(
                 "bloom_ws: failed parsing message err=\"{}\" text={} ",
@@ -377,7 +524,7 @@ fn handle_message_text(text: &str) {
     }
 }
 
-fn process_ws_message(mut message: BloomWsMessage) {
+fn process_ws_message(account_key: &str, mut message: BloomWsMessage) {
     let id = match message.id.clone() {
         Some(id) => id,
         None => {
@@ -394,121 +541,34 @@ fn process_ws_message(mut message: BloomWsMessage) {
     };
     message.status = Some(status);
 
-    let token_name = message.token_name.clone();
-    let tx_hash = message.tx_hash.clone();
-    let error = message.error.clone();
-
-    if status == 0 {
-        let exists = ACTIVE_BLOOM_SWAPS.lock().contains_key(&id);
-        if exists {
-            log::debug!("bloom_ws: pending swap id={} side={:?}", id, message.side);
-        } else {
-            log::debug!(
-                "bloom_ws: pending swap ignored id={} side={:?}",
-                id,
-                message.side
-            );
-        }
-        return;
-    }
-
-    let tracker_option = {
-        let mut swaps = ACTIVE_BLOOM_SWAPS.lock();
-        swaps.remove(&id)
-    };
-
-    let tracker = match tracker_option {
-        Some(tracker) => tracker,
-        None => {
-            log::warn!("bloom_ws: no tracker for id={} status={}", id, status);
-            return;
-        }
-    };
-
-    match status {
-        1 => handle_success(&id, &tracker, token_name, tx_hash),
-        _ if status >= 2 => handle_failure(&id, &tracker, status, error),
+    let event = match status {
+        0 => SwapEvent::Pending,
+        1 => SwapEvent::Confirmed {
+            token_name: message.token_name.clone(),
+            signature: message.tx_hash.clone(),
+        },
         _ => {
-            log::warn!("bloom_ws: unhandled status={} id={}", status, id);
-        }
-    }
-}
-
-fn current_time_millis() -> Result<i64> {
-    let duration = SystemTime::now().duration_since(UNIX_EPOCH)?;
-    Ok(i64::try_from(duration.as_millis())?)
-}
-
-fn load_bloom_auth_data() -> BloomAuthData {
-    let token = std::env::var("BLOOM_AUTH_TOKEN").ok();
-    let expires_at = std::env::var("BLOOM_AUTH_TOKEN_EXPIRES_AT")
-        .ok()
-        .and_then(|value| {
-            let trimmed = value.trim();
-            if trimmed.is_empty() {
-                return None;
-            }
-            trimmed.parse::<i64>().ok()
-        });
-    BloomAuthData { token, expires_at }
-}
-
-fn handle_success(
-    id: &str,
-    tracker: &BloomSwapTracker,
-    token_name: Option<String>,
-    tx_hash: Option<String>,
-) {
-    let mut responses = PENDING_BLOOM_RESPONSES.lock();
-    if let Some(sender) = responses.remove(&tracker.mint) {
-        let ack = BloomBuyAck {
-            pending_time: tracker.started_at,
-            success_time: Instant::now(),
-            token_name,
-            signature: tx_hash.clone(),
-        };
-        if sender.send(ack).is_err() {
             log::warn!(
-                "bloom_ws: ack channel closed id={} mint={}",
+                "bloom_ws: failure id={} side={:?} status={} error={:?} error_code={:?}",
                 id,
-                tracker.mint
-            );
-        } else {
-            log::info!(
-                "bloom_ws: success id={} mint={} side={} tx_hash={:?}",
-                id,
-                tracker.mint,
-                tracker.side,
-                tx_hash
+                message.side,
+                status,
+                message.error,
+                message.error_code
             );
+            SwapEvent::Failed {
+                status,
+                error: message.error.clone(),
+                error_code: message.error_code,
+            }
         }
-    } else {
-        log::warn!(
-            "bloom_ws: success but no pending sender id={} mint={}",
-            id,
-            tracker.mint
-        );
-    }
-}
-
-fn handle_failure(id: &str, tracker: &BloomSwapTracker, status: u8, error: Option<String>) {
-    let mut responses = PENDING_BLOOM_RESPONSES.lock();
-    if responses.remove(&tracker.mint).is_some() {
-        log::warn!(
-            "bloom_ws: failure id={} mint={} side={} status={} error={:?}",
-            id,
-            tracker.mint,
-            tracker.side,
-            status,
-            error
-        );
-    } else {
-        log::warn!(
-            "bloom_ws: failure without pending sender id={} mint={} status={} error={:?}",
+    };
+    if !apply_swap_event(&id, event, &format!("ws:{}", account_key)) {
+        log::debug!(
+            "bloom_ws: swap event not applied id={} status={} side={:?}",
             id,
-            tracker.mint,
             status,
-            error
+            message.side
         );
     }
 }
@@ -518,31 +578,9 @@ async fn cleanup_stale_swaps() {
     interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
     loop {
         interval.tick().await;
-        let now = Instant::now();
-        let mut stale_entries = Vec::new();
-        {
-            let swaps = ACTIVE_BLOOM_SWAPS.lock();
-            for (id, tracker) in swaps.iter() {
-                if now.duration_since(tracker.started_at) > Duration::from_secs(300) {
-                    stale_entries.push((id.clone(), tracker.mint.clone()));
-                }
-            }
-        }
-        if stale_entries.is_empty() {
-            continue;
-        }
-        {
-            let mut swaps = ACTIVE_BLOOM_SWAPS.lock();
-            for (id, _) in &stale_entries {
-                swaps.remove(id);
-            }
-        }
-        {
-            let mut responses = PENDING_BLOOM_RESPONSES.lock();
-            for (_, mint) in &stale_entries {
-                responses.remove(mint);
-            }
+        let expired = expire_stale_swaps(Duration::from_secs(300));
+        if expired > 0 {
+            log::warn!("bloom_ws: expired {} stale swaps", expired);
         }
-        log::warn!("bloom_ws: removed {} stale swaps", stale_entries.len());
     }
 }
@@ -0,0 +1,220 @@
+use super::types::{
+    BroadcastOutcome, EndpointAccept, EndpointRanking, EndpointSendResult, EndpointStats,
+};
+use anyhow::{Result, anyhow};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+use solana_rpc_client_api::config::RpcSendTransactionConfig;
+use solana_sdk::transaction::Transaction;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::mpsc;
+
+static ENDPOINT_STATS: Lazy<Mutex<HashMap<String, EndpointStats>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn record_send(result: &EndpointSendResult, first: bool) {
+    let mut stats = ENDPOINT_STATS.lock();
+    let entry = stats.entry(result.endpoint.clone()).or_default();
+    entry.sends += 1;
+    if result.error.is_some() {
+        entry.errors += 1;
+        return;
+    }
+    entry.total_accept_latency_ms += result.latency_ms;
+    entry.last_accept_latency_ms = Some(result.latency_ms);
+    if first {
+        entry.first_accepts += 1;
+    }
+}
+
+fn accept_from(result: &EndpointSendResult, fallback_signature: &str) -> EndpointAccept {
+    EndpointAccept {
+        endpoint: result.endpoint.clone(),
+        signature: result
+            .signature
+            .clone()
+            .unwrap_or_else(|| fallback_signature.to_string()),
+        latency_ms: result.latency_ms,
+    }
+}
+
+pub async fn broadcast_transaction(
+    senders: &[(String, Arc<RpcClient>)],
+    transaction: &Transaction,
+) -> Result<BroadcastOutcome> {
+    if senders.is_empty() {
+        return Err(anyhow!("No broadcast endpoints configured"));
+    }
+
+    let (tx, mut rx) = mpsc::unbounded_channel::<EndpointSendResult>();
+    let started = Instant::now();
+    for (endpoint, client) in senders {
+        let endpoint = endpoint.clone();
+        let client = Arc::clone(client);
+        let transaction = transaction.clone();
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            let result = client
+                .send_transaction_with_config(
+                    &transaction,
+                    RpcSendTransactionConfig {
+                        skip_preflight: true,
+                        max_retries: Some(0),
+                        ..Default::default()
+                    },
+                )
+                .await;
+            let latency_ms = started.elapsed().as_secs_f64() * 1000.0;
+            let (signature, error) = match result {
+                Ok(signature) => (Some(signature.to_string()), None),
+                Err(e) => (None, Some(e.to_string())),
+            };
+            let _ = tx.send(EndpointSendResult {
+                endpoint,
+                latency_ms,
+                signature,
+                error,
+            });
+        });
+    }
+    drop(tx);
+
+    let signature = transaction
+        .signatures
+        .first()
+        .map(|sig| sig.to_string())
+        .ok_or_else(|| anyhow!("Transaction is not signed"))?;
+
+    let mut failures = Vec::new();
+    while let Some(result) = rx.recv().await {
+        if let Some(error) = result.error.as_ref() {
+            log::warn!(
+                "broadcast.send_error endpoint={} latency_ms={:.2} err=\"{}\"",
+                result.endpoint,
+                result.latency_ms,
+                error
+            );
+            record_send(&result, false);
+            failures.push(format!("{}: {}", result.endpoint, error));
+            continue;
+        }
+
+        record_send(&result, true);
+        log::info!(
+            "broadcast.first_accept endpoint={} latency_ms={:.2} signature={}",
+            result.endpoint,
+            result.latency_ms,
+            signature
+        );
+        let accepts = Arc::new(Mutex::new(vec![accept_from(&result, &signature)]));
+        let outcome = BroadcastOutcome {
+            signature: signature.clone(),
+            first_accept_endpoint: result.endpoint.clone(),
+            accepts: Arc::clone(&accepts),
+        };
+        tokio::spawn(async move {
+            while let Some(rest) = rx.recv().await {
+                log::info!(
+                    "broadcast.send endpoint={} latency_ms={:.2} ok={}",
+                    rest.endpoint,
+                    rest.latency_ms,
+                    if rest.error.is_none() { 1 } else { 0 }
+                );
+                record_send(&rest, false);
+                if rest.error.is_none() {
+                    accepts.lock().push(accept_from(&rest, &signature));
+                }
+            }
+        });
+        return Ok(outcome);
+    }
+
+    Err(anyhow!(
+        "All broadcast endpoints rejected the transaction ({})",
+        failures.join("; ")
+    ))
+}
+
+pub fn record_accepted_landing(
+    outcome: &BroadcastOutcome,
+    confirmed_signature: &str,
+    confirmed_at_ms: f64,
+) {
+    let accepts = outcome.accepts.lock().clone();
+    let mut stats = ENDPOINT_STATS.lock();
+    for accept in &accepts {
+        if accept.signature != confirmed_signature {
+            log::warn!(
+                "broadcast.signature_mismatch endpoint={} returned={} confirmed={}",
+                accept.endpoint,
+                accept.signature,
+                confirmed_signature
+            );
+            continue;
+        }
+        let accept_to_confirm_ms = (confirmed_at_ms - accept.latency_ms).max(0.0);
+        let entry = stats.entry(accept.endpoint.clone()).or_default();
+        entry.accepted_landed += 1;
+        entry.total_accept_to_confirm_ms += accept_to_confirm_ms;
+        log::info!(
+            "broadcast.accepted_landed endpoint={} accept_to_confirm_ms={:.2} signature={}",
+            accept.endpoint,
+            accept_to_confirm_ms,
+            confirmed_signature
+        );
+    }
+}
+
+pub fn endpoint_ranking() -> Vec<EndpointRanking> {
+    let stats = ENDPOINT_STATS.lock();
+    let mut ranking: Vec<EndpointRanking> = stats
+        .iter()
+        .map(|(endpoint, entry)| {
+            let accepted = entry.sends.saturating_sub(entry.errors);
+            EndpointRanking {
+                endpoint: endpoint.clone(),
+                sends: entry.sends,
+                errors: entry.errors,
+                first_accepts: entry.first_accepts,
+                accepted_landed: entry.accepted_landed,
+                avg_accept_latency_ms: (accepted > 0)
+                    .then(|| entry.total_accept_latency_ms / accepted as f64),
+                last_accept_latency_ms: entry.last_accept_latency_ms,
+                avg_accept_to_confirm_ms: (entry.accepted_landed > 0)
+                    .then(|| entry.total_accept_to_confirm_ms / entry.accepted_landed as f64),
+            }
+        })
+        .collect();
+    ranking.sort_by(|a, b| {
+        first_accept_rate(b)
+            .total_cmp(&first_accept_rate(a))
+            .then_with(|| {
+                a.avg_accept_latency_ms
+                    .unwrap_or(f64::MAX)
+                    .total_cmp(&b.avg_accept_latency_ms.unwrap_or(f64::MAX))
+            })
+            .then_with(|| a.errors.cmp(&b.errors))
+    });
+    ranking
+}
+
+fn first_accept_rate(entry: &EndpointRanking) -> f64 {
+    if entry.sends == 0 {
+        0.0
+    } else {
+        entry.first_accepts as f64 / entry.sends as f64
+    }
+}
+
+pub fn rank_senders(senders: &mut [(String, Arc<RpcClient>)]) {
+    let ranking = endpoint_ranking();
+    senders.sort_by_key(|(endpoint, _)| {
+        ranking
+            .iter()
+            .position(|entry| entry.endpoint == *endpoint)
+            .unwrap_or(ranking.len())
+    });
+}
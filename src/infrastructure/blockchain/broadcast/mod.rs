@@ -0,0 +1,5 @@
+pub mod client;
+pub mod types;
+
+pub use client::{broadcast_transaction, endpoint_ranking, rank_senders, record_accepted_landing};
+pub use types::{BroadcastOutcome, EndpointRanking};
@@ -0,0 +1,47 @@
+use parking_lot::Mutex;
+use std::sync::Arc;
+
+#[derive(Clone, Debug)]
+pub struct EndpointSendResult {
+    pub endpoint: String,
+    pub latency_ms: f64,
+    pub signature: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct EndpointAccept {
+    pub endpoint: String,
+    pub signature: String,
+    pub latency_ms: f64,
+}
+
+#[derive(Clone, Debug)]
+pub struct BroadcastOutcome {
+    pub signature: String,
+    pub first_accept_endpoint: String,
+    pub accepts: Arc<Mutex<Vec<EndpointAccept>>>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct EndpointStats {
+    pub sends: u64,
+    pub errors: u64,
+    pub first_accepts: u64,
+    pub accepted_landed: u64,
+    pub total_accept_latency_ms: f64,
+    pub total_accept_to_confirm_ms: f64,
+    pub last_accept_latency_ms: Option<f64>,
+}
+
+#[derive(Clone, Debug)]
+pub struct EndpointRanking {
+    pub endpoint: String,
+    pub sends: u64,
+    pub errors: u64,
+    pub first_accepts: u64,
+    pub accepted_landed: u64,
+    pub avg_accept_latency_ms: Option<f64>,
+    pub last_accept_latency_ms: Option<f64>,
+    pub avg_accept_to_confirm_ms: Option<f64>,
+}
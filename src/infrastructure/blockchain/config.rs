@@ -4,5 +4,4 @@ pub const SHYFT_RPC_URL: &str = "https://rpc.fra.shyft.to?api_key=Rwo9yFrzzUT5sY
 pub const NODE1_RPC_URL: &str = "http://fra.node1.me";
 pub const ZEROSLOT_RPC_URL: &str = "http://de1.0slot.trade";
 
-pub const BLOOM_SWAP_URL: &str = "http://eu1.bloom-ext.app/api/extension-swap";
 pub const SHYFT_GRAPHQL_URL: &str = "https://programs.shyft.to/v0/graphql/";
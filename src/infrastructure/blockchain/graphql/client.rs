@@ -1,5 +1,3 @@
-#![allow(dead_code)]
-
 use super::types::*;
 use crate::HTTP_CLIENT;
 use crate::infrastructure::blockchain::config::SHYFT_GRAPHQL_URL;
@@ -35,7 +33,7 @@ async fn execute_query<T: for<'de> serde::Deserialize<'de>>(
     })
 }
 
-pub async fn fetch_pump_swap_params(mint: &str) -> Result<PumpFunAmmPool> {
+pub async fn find_pump_swap_params(mint: &str) -> Result<Option<PumpFunAmmPool>> {
     let query = format!(
         r#"
         query MyQuery {{
@@ -59,14 +57,10 @@ pub async fn fetch_pump_swap_params(mint: &str) -> Result<PumpFunAmmPool> {
     let resp: GraphQLResponse<PumpFunAmmPoolResponse> =
         execute_query(query, "pump_fun_amm_Pool").await?;
 
-    resp.data
-        .pump_fun_amm_pool
-        .first()
-        .cloned()
-        .ok_or_else(|| anyhow!("Pool not found for mint: {}", mint))
+    Ok(resp.data.pump_fun_amm_pool.first().cloned())
 }
 
-pub async fn fetch_raydium_v4_params(mint: &str) -> Result<RaydiumV4Pool> {
+pub async fn find_raydium_v4_params(mint: &str) -> Result<Option<RaydiumV4Pool>> {
     let query = format!(
         r#"
         query MyQuery {{
@@ -99,14 +93,10 @@ pub async fn fetch_raydium_v4_params(mint: &str) -> Result<RaydiumV4Pool> {
     let resp: GraphQLResponse<RaydiumV4PoolResponse> =
         execute_query(query, "Raydium_LiquidityPoolv4").await?;
 
-    resp.data
-        .raydium_liquidity_pool_v4
-        .first()
-        .cloned()
-        .ok_or_else(|| anyhow!("Pool not found for mint: {}", mint))
+    Ok(resp.data.raydium_liquidity_pool_v4.first().cloned())
 }
 
-pub async fn fetch_raydium_cpmm_params(mint: &str) -> Result<RaydiumCpmmPool> {
+pub async fn find_raydium_cpmm_params(mint: &str) -> Result<Option<RaydiumCpmmPool>> {
     let query = format!(
         r#"
         query MyQuery {{
@@ -135,9 +125,5 @@ pub async fn fetch_raydium_cpmm_params(mint: &str) -> Result<RaydiumCpmmPool> {
     let resp: GraphQLResponse<RaydiumCpmmPoolResponse> =
         execute_query(query, "raydium_cp_swap_PoolState").await?;
 
-    resp.data
-        .raydium_cp_swap_pool_state
-        .first()
-        .cloned()
-        .ok_or_else(|| anyhow!("Pool not found for mint: {}", mint))
+    Ok(resp.data.raydium_cp_swap_pool_state.first().cloned())
 }
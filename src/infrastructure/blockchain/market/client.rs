@@ -0,0 +1,204 @@
+use super::types::{PoolSnapshot, PoolSource, WSOL_MINT};
+use crate::infrastructure::blockchain::graphql::client::{
+    find_pump_swap_params, find_raydium_cpmm_params, find_raydium_v4_params,
+};
+use crate::infrastructure::blockchain::native::pump::{bonding_curve_pda, parse_bonding_curve};
+use crate::infrastructure::blockchain::types::RpcClients;
+use anyhow::{Result, anyhow};
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+const PUMP_TOKEN_DECIMALS_FACTOR: f64 = 1_000_000.0;
+
+struct PoolVaults {
+    source: PoolSource,
+    sol_vault: String,
+    token_vault: String,
+}
+
+fn orient_vaults(
+    source: PoolSource,
+    mint: &str,
+    mint_a: &str,
+    vault_a: &str,
+    mint_b: &str,
+    vault_b: &str,
+) -> Option<PoolVaults> {
+    if mint_a == mint && mint_b == WSOL_MINT {
+        Some(PoolVaults {
+            source,
+            sol_vault: vault_b.to_string(),
+            token_vault: vault_a.to_string(),
+        })
+    } else if mint_b == mint && mint_a == WSOL_MINT {
+        Some(PoolVaults {
+            source,
+            sol_vault: vault_a.to_string(),
+            token_vault: vault_b.to_string(),
+        })
+    } else {
+        None
+    }
+}
+
+async fn resolve_pool_vaults(mint: &str) -> Result<Option<PoolVaults>> {
+    let (pump_swap, cpmm, v4) = tokio::join!(
+        find_pump_swap_params(mint),
+        find_raydium_cpmm_params(mint),
+        find_raydium_v4_params(mint)
+    );
+    let lookup_error = [
+        pump_swap.as_ref().err(),
+        cpmm.as_ref().err(),
+        v4.as_ref().err(),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|e| e.to_string())
+    .next();
+
+    if let Ok(Some(pool)) = pump_swap {
+        if let Some(vaults) = orient_vaults(
+            PoolSource::PumpSwap,
+            mint,
+            &pool.base_mint,
+            &pool.pool_base_token_account,
+            &pool.quote_mint,
+            &pool.pool_quote_token_account,
+        ) {
+            return Ok(Some(vaults));
+        }
+    }
+    if let Ok(Some(pool)) = cpmm {
+        if let Some(vaults) = orient_vaults(
+            PoolSource::RaydiumCpmm,
+            mint,
+            &pool.token0_mint,
+            &pool.token0_vault,
+            &pool.token1_mint,
+            &pool.token1_vault,
+        ) {
+            return Ok(Some(vaults));
+        }
+    }
+    if let Ok(Some(pool)) = v4 {
+        if let Some(vaults) = orient_vaults(
+            PoolSource::RaydiumV4,
+            mint,
+            &pool.base_mint,
+            &pool.base_vault,
+            &pool.quote_mint,
+            &pool.quote_vault,
+        ) {
+            return Ok(Some(vaults));
+        }
+    }
+
+    match lookup_error {
+        Some(e) => Err(anyhow!("Pool lookup failed for mint {}: {}", mint, e)),
+        None => Ok(None),
+    }
+}
+
+async fn read_vault_amount(rpc_clients: &RpcClients, vault: &str) -> Result<f64> {
+    let pubkey = Pubkey::from_str(vault).map_err(|e| anyhow!("Invalid vault {}: {}", vault, e))?;
+    let balance = rpc_clients
+        .helius_client
+        .get_token_account_balance(&pubkey)
+        .await?;
+    balance
+        .ui_amount
+        .ok_or_else(|| anyhow!("Vault {} returned no UI amount", vault))
+}
+
+async fn build_bonding_curve_snapshot(
+    rpc_clients: &RpcClients,
+    mint_pubkey: &Pubkey,
+    started: Instant,
+) -> Result<Option<PoolSnapshot>> {
+    let account = rpc_clients
+        .helius_client
+        .get_account_with_commitment(
+            &bonding_curve_pda(mint_pubkey),
+            CommitmentConfig::processed(),
+        )
+        .await?
+        .value;
+    let Some(account) = account else {
+        return Ok(None);
+    };
+    let curve = parse_bonding_curve(&account.data)?;
+    if curve.complete || curve.virtual_token_reserves == 0 {
+        return Ok(None);
+    }
+
+    let sol_reserve = curve.virtual_sol_reserves as f64 / LAMPORTS_PER_SOL as f64;
+    let token_reserve = curve.virtual_token_reserves as f64 / PUMP_TOKEN_DECIMALS_FACTOR;
+    let price_sol = sol_reserve / token_reserve;
+    Ok(Some(PoolSnapshot {
+        source: PoolSource::PumpBondingCurve,
+        sol_reserve,
+        token_reserve,
+        price_sol,
+        liquidity_sol: curve.real_sol_reserves as f64 / LAMPORTS_PER_SOL as f64 * 2.0,
+        market_cap_sol: price_sol * curve.token_total_supply as f64 / PUMP_TOKEN_DECIMALS_FACTOR,
+        elapsed_ms: started.elapsed().as_millis(),
+    }))
+}
+
+async fn build_pool_snapshot(
+    rpc_clients: &RpcClients,
+    mint: &str,
+    started: Instant,
+) -> Result<Option<PoolSnapshot>> {
+    let mint_pubkey =
+        Pubkey::from_str(mint).map_err(|e| anyhow!("Invalid mint address {}: {}", mint, e))?;
+    if let Some(snapshot) = build_bonding_curve_snapshot(rpc_clients, &mint_pubkey, started).await?
+    {
+        return Ok(Some(snapshot));
+    }
+
+    let Some(vaults) = resolve_pool_vaults(mint).await? else {
+        return Ok(None);
+    };
+
+    let (sol_reserve, token_reserve, supply) = tokio::join!(
+        read_vault_amount(rpc_clients, &vaults.sol_vault),
+        read_vault_amount(rpc_clients, &vaults.token_vault),
+        rpc_clients.helius_client.get_token_supply(&mint_pubkey)
+    );
+    let sol_reserve = sol_reserve?;
+    let token_reserve = token_reserve?;
+    let supply = supply?
+        .ui_amount
+        .ok_or_else(|| anyhow!("Mint {} returned no UI supply", mint))?;
+
+    if token_reserve <= 0.0 {
+        return Err(anyhow!("Pool for mint {} has an empty token vault", mint));
+    }
+
+    let price_sol = sol_reserve / token_reserve;
+    Ok(Some(PoolSnapshot {
+        source: vaults.source,
+        sol_reserve,
+        token_reserve,
+        price_sol,
+        liquidity_sol: sol_reserve * 2.0,
+        market_cap_sol: price_sol * supply,
+        elapsed_ms: started.elapsed().as_millis(),
+    }))
+}
+
+pub async fn fetch_pool_snapshot(
+    rpc_clients: &RpcClients,
+    mint: &str,
+    budget: Duration,
+) -> Result<Option<PoolSnapshot>> {
+    let started = Instant::now();
+    tokio::time::timeout(budget, build_pool_snapshot(rpc_clients, mint, started))
+        .await
+        .map_err(|_| anyhow!("Pool lookup exceeded {} ms budget", budget.as_millis()))?
+}
@@ -0,0 +1,5 @@
+pub mod client;
+pub mod types;
+
+pub use client::fetch_pool_snapshot;
+pub use types::{MARKET_FILTER_BUDGET, PoolSnapshot, PoolSource};
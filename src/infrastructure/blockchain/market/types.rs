@@ -0,0 +1,34 @@
+use std::time::Duration;
+
+pub const MARKET_FILTER_BUDGET: Duration = Duration::from_millis(1500);
+pub const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PoolSource {
+    PumpBondingCurve,
+    PumpSwap,
+    RaydiumCpmm,
+    RaydiumV4,
+}
+
+impl PoolSource {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PoolSource::PumpBondingCurve => "pump.fun Curve",
+            PoolSource::PumpSwap => "PumpSwap",
+            PoolSource::RaydiumCpmm => "Raydium CPMM",
+            PoolSource::RaydiumV4 => "Raydium V4",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PoolSnapshot {
+    pub source: PoolSource,
+    pub sol_reserve: f64,
+    pub token_reserve: f64,
+    pub price_sol: f64,
+    pub liquidity_sol: f64,
+    pub market_cap_sol: f64,
+    pub elapsed_ms: u128,
+}
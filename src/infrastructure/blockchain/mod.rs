@@ -1,11 +1,21 @@
 pub mod bloom;
+pub mod broadcast;
 pub mod config;
 pub mod graphql;
+pub mod market;
+pub mod native;
 pub mod rpc;
+pub mod safety;
 pub mod types;
 
 pub use bloom::buy as bloom_buy;
 pub use bloom::run_bloom_ws_listener;
+pub use bloom::{BloomCredentials, BloomFailure, BloomSwapOptions};
+pub use bloom::{SwapOutcome, subscribe_swap};
+pub use broadcast::{EndpointRanking, endpoint_ranking};
 pub use config::*;
+pub use market::{MARKET_FILTER_BUDGET, PoolSnapshot, fetch_pool_snapshot};
+pub use native::{NativeBuyResult, execute_native_buy, keypair_from_base58, native_rpc_client};
 pub use rpc::create_rpc_clients;
+pub use safety::{MintSafetyReport, SAFETY_CHECK_BUDGET, check_mint_safety};
 pub use types::RpcClients;
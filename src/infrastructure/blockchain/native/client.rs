@@ -0,0 +1,374 @@
+use super::pump::{self, bonding_curve_pda};
+use super::pump_swap::{self, canonical_pool_pda, global_config_pda};
+use super::types::{
+    BONDING_CURVE_COMPUTE_UNITS, NativeBuyResult, NativeRoute, PUMP_SWAP_COMPUTE_UNITS, WSOL_MINT,
+    apply_slippage_up, read_u64,
+};
+use crate::infrastructure::blockchain::broadcast::{
+    broadcast_transaction, record_accepted_landing,
+};
+use crate::infrastructure::blockchain::config::HELIUS_RPC_URL;
+use anyhow::{Result, anyhow};
+use once_cell::sync::Lazy;
+use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature};
+use solana_sdk::signer::Signer;
+use solana_sdk::transaction::Transaction;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const TOKEN_ACCOUNT_AMOUNT_OFFSET: usize = 64;
+const MINT_DECIMALS_OFFSET: usize = 44;
+const CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(20);
+const CONFIRMATION_POLL_INTERVAL: Duration = Duration::from_millis(400);
+
+static NATIVE_RPC_URL: Lazy<Option<String>> = Lazy::new(|| {
+    std::env::var("NATIVE_RPC_URL")
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+});
+
+static NATIVE_RPC_CLIENT: Lazy<Arc<RpcClient>> = Lazy::new(|| {
+    let url = NATIVE_RPC_URL
+        .clone()
+        .unwrap_or_else(|| HELIUS_RPC_URL.to_string());
+    Arc::new(RpcClient::new_with_commitment(
+        url,
+        CommitmentConfig::confirmed(),
+    ))
+});
+
+pub fn native_rpc_client() -> Arc<RpcClient> {
+    Arc::clone(&NATIVE_RPC_CLIENT)
+}
+
+fn native_senders() -> Vec<(String, Arc<RpcClient>)> {
+    if NATIVE_RPC_URL.is_some() {
+        return vec![("native".to_string(), native_rpc_client())];
+    }
+    crate::RPC_CLIENTS.senders()
+}
+
+pub fn keypair_from_base58(private_key: &str) -> Result<Keypair> {
+    let bytes = bs58::decode(private_key.trim())
+        .into_vec()
+        .map_err(|e| anyhow!("Invalid wallet private key: {}", e))?;
+    Keypair::try_from(bytes.as_slice()).map_err(|e| anyhow!("Invalid wallet keypair: {}", e))
+}
+
+fn compute_budget_instructions(priority_fee_sol: f64, compute_units: u32) -> [Instruction; 2] {
+    let fee_lamports = (priority_fee_sol.max(0.0) * LAMPORTS_PER_SOL as f64) as u128;
+    let micro_lamports = fee_lamports * 1_000_000 / compute_units as u128;
+    [
+        ComputeBudgetInstruction::set_compute_unit_limit(compute_units),
+        ComputeBudgetInstruction::set_compute_unit_price(
+            micro_lamports.min(u64::MAX as u128) as u64
+        ),
+    ]
+}
+
+async fn build_bonding_curve_buy(
+    rpc: &RpcClient,
+    user: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    curve: &pump::BondingCurveState,
+    lamports_in: u64,
+    slippage_percent: u32,
+) -> Result<(Vec<Instruction>, u64, u64)> {
+    let global_account = rpc.get_account(&pump::global_pda()).await?;
+    let global = pump::parse_global(&global_account.data)?;
+    let tokens_out = pump::quote_tokens_out(curve, lamports_in, global.fee_basis_points);
+    if tokens_out == 0 {
+        return Err(anyhow!("Bonding curve quote returned zero tokens"));
+    }
+    let max_sol_cost = apply_slippage_up(lamports_in, slippage_percent);
+
+    let instructions = vec![
+        create_associated_token_account_idempotent(user, user, mint, token_program),
+        pump::build_buy_instruction(
+            user,
+            mint,
+            token_program,
+            curve,
+            &global,
+            tokens_out,
+            max_sol_cost,
+        ),
+    ];
+    Ok((instructions, tokens_out, max_sol_cost))
+}
+
+async fn build_pump_swap_buy(
+    rpc: &RpcClient,
+    user: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    lamports_in: u64,
+    slippage_percent: u32,
+) -> Result<(Vec<Instruction>, u64, u64)> {
+    let pool_address = canonical_pool_pda(mint);
+    let accounts = rpc
+        .get_multiple_accounts(&[global_config_pda(), pool_address])
+        .await?;
+    let [config_account, pool_account] = accounts.as_slice() else {
+        return Err(anyhow!("Unexpected PumpSwap account response"));
+    };
+    let config_account = config_account
+        .as_ref()
+        .ok_or_else(|| anyhow!("PumpSwap global config not found"))?;
+    let config = pump_swap::parse_global_config(&config_account.data)?;
+    let pool_account = pool_account
+        .as_ref()
+        .ok_or_else(|| anyhow!("PumpSwap pool {} not found for mint {}", pool_address, mint))?;
+    let pool_accounts = pump_swap::parse_pool(pool_address, &pool_account.data)?;
+    if pool_accounts.base_mint != *mint {
+        return Err(anyhow!(
+            "PumpSwap pool {} is not a token/SOL pool for mint {}",
+            pool_address,
+            mint
+        ));
+    }
+
+    let vaults = rpc
+        .get_multiple_accounts(&[
+            pool_accounts.pool_base_token_account,
+            pool_accounts.pool_quote_token_account,
+        ])
+        .await?;
+    let [base_vault, quote_vault] = vaults.as_slice() else {
+        return Err(anyhow!("Unexpected PumpSwap vault response"));
+    };
+    let vault_amount = |account: &Option<solana_sdk::account::Account>| {
+        account
+            .as_ref()
+            .and_then(|acc| read_u64(&acc.data, TOKEN_ACCOUNT_AMOUNT_OFFSET))
+            .ok_or_else(|| anyhow!("PumpSwap pool vault unavailable"))
+    };
+    let base_reserve = vault_amount(base_vault)?;
+    let quote_reserve = vault_amount(quote_vault)?;
+
+    let base_out = pump_swap::quote_base_out(
+        base_reserve,
+        quote_reserve,
+        lamports_in,
+        config.total_fee_basis_points,
+    );
+    if base_out == 0 {
+        return Err(anyhow!("PumpSwap quote returned zero tokens"));
+    }
+    let max_quote_in = apply_slippage_up(lamports_in, slippage_percent);
+
+    let quote_token_program = spl_token::id();
+    let user_wsol =
+        get_associated_token_address_with_program_id(user, &WSOL_MINT, &quote_token_program);
+    let instructions = vec![
+        create_associated_token_account_idempotent(user, user, mint, token_program),
+        create_associated_token_account_idempotent(user, user, &WSOL_MINT, &quote_token_program),
+        solana_system_interface::instruction::transfer(user, &user_wsol, max_quote_in),
+        spl_token::instruction::sync_native(&quote_token_program, &user_wsol)
+            .map_err(|e| anyhow!("Failed to build sync_native: {}", e))?,
+        pump_swap::build_buy_instruction(
+            user,
+            &pool_accounts,
+            token_program,
+            &config,
+            base_out,
+            max_quote_in,
+        ),
+        spl_token::instruction::close_account(&quote_token_program, &user_wsol, user, user, &[])
+            .map_err(|e| anyhow!("Failed to build close_account: {}", e))?,
+    ];
+    Ok((instructions, base_out, max_quote_in))
+}
+
+async fn await_confirmation(rpc: &RpcClient, signature: &Signature) -> Result<bool> {
+    let started = Instant::now();
+    while started.elapsed() < CONFIRMATION_TIMEOUT {
+        let statuses = rpc.get_signature_statuses(&[*signature]).await?;
+        if let Some(Some(status)) = statuses.value.first() {
+            if let Some(err) = &status.err {
+                return Err(anyhow!("Transaction {} failed: {}", signature, err));
+            }
+            if status.satisfies_commitment(CommitmentConfig::confirmed()) {
+                return Ok(true);
+            }
+        }
+        tokio::time::sleep(CONFIRMATION_POLL_INTERVAL).await;
+    }
+    Ok(false)
+}
+
+pub async fn execute_native_buy(
+    rpc: &RpcClient,
+    payer: &Keypair,
+    mint_address: &str,
+    sol_amount: f64,
+    slippage_percent: u32,
+    priority_fee_sol: f64,
+) -> Result<NativeBuyResult> {
+    execute_native_buy_via(
+        rpc,
+        &native_senders(),
+        payer,
+        mint_address,
+        sol_amount,
+        slippage_percent,
+        priority_fee_sol,
+    )
+    .await
+}
+
+async fn execute_native_buy_via(
+    rpc: &RpcClient,
+    senders: &[(String, Arc<RpcClient>)],
+    payer: &Keypair,
+    mint_address: &str,
+    sol_amount: f64,
+    slippage_percent: u32,
+    priority_fee_sol: f64,
+) -> Result<NativeBuyResult> {
+    let user = payer.pubkey();
+    let mint = Pubkey::from_str(mint_address)
+        .map_err(|e| anyhow!("Invalid mint address {}: {}", mint_address, e))?;
+    let lamports_in = (sol_amount * LAMPORTS_PER_SOL as f64) as u64;
+    if lamports_in == 0 {
+        return Err(anyhow!("Buy amount must be greater than zero"));
+    }
+
+    let accounts = rpc
+        .get_multiple_accounts(&[mint, bonding_curve_pda(&mint)])
+        .await?;
+    let mint_account = accounts
+        .first()
+        .cloned()
+        .flatten()
+        .ok_or_else(|| anyhow!("Mint account {} not found", mint_address))?;
+    let token_program = mint_account.owner;
+    let token_decimals = mint_account
+        .data
+        .get(MINT_DECIMALS_OFFSET)
+        .copied()
+        .ok_or_else(|| anyhow!("Mint account {} is truncated", mint_address))?;
+    let curve = accounts
+        .get(1)
+        .cloned()
+        .flatten()
+        .map(|account| pump::parse_bonding_curve(&account.data))
+        .transpose()?
+        .filter(|curve| !curve.complete);
+
+    let (route, compute_units, (mut instructions, tokens_out, max_sol_cost)) = match curve {
+        Some(curve) => (
+            NativeRoute::BondingCurve,
+            BONDING_CURVE_COMPUTE_UNITS,
+            build_bonding_curve_buy(
+                rpc,
+                &user,
+                &mint,
+                &token_program,
+                &curve,
+                lamports_in,
+                slippage_percent,
+            )
+            .await?,
+        ),
+        None => (
+            NativeRoute::PumpSwap,
+            PUMP_SWAP_COMPUTE_UNITS,
+            build_pump_swap_buy(
+                rpc,
+                &user,
+                &mint,
+                &token_program,
+                lamports_in,
+                slippage_percent,
+            )
+            .await?,
+        ),
+    };
+
+    let mut all_instructions =
+        compute_budget_instructions(priority_fee_sol, compute_units).to_vec();
+    all_instructions.append(&mut instructions);
+
+    let blockhash = rpc.get_latest_blockhash().await?;
+    let transaction =
+        Transaction::new_signed_with_payer(&all_instructions, Some(&user), &[payer], blockhash);
+    let signature = transaction.signatures[0];
+    let sent_at = Instant::now();
+    let outcome = broadcast_transaction(senders, &transaction).await?;
+    log::info!(
+        "native.buy_sent route={} signature={} mint={} first_accept_endpoint={}",
+        route.label(),
+        signature,
+        mint_address,
+        outcome.first_accept_endpoint
+    );
+
+    let confirmed = await_confirmation(rpc, &signature).await?;
+    if confirmed {
+        record_accepted_landing(
+            &outcome,
+            &signature.to_string(),
+            sent_at.elapsed().as_secs_f64() * 1000.0,
+        );
+    }
+    Ok(NativeBuyResult {
+        route,
+        signature: signature.to_string(),
+        tokens_out,
+        token_decimals,
+        max_sol_cost_lamports: max_sol_cost,
+        confirmed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOCAL_VALIDATOR_URL: &str = "http://127.0.0.1:8899";
+
+    #[tokio::test]
+    #[ignore = "needs solana-test-validator with the pump.fun accounts of NATIVE_TEST_MINT cloned"]
+    async fn buys_against_local_validator() {
+        let url = std::env::var("NATIVE_TEST_RPC_URL")
+            .unwrap_or_else(|_| LOCAL_VALIDATOR_URL.to_string());
+        let mint = std::env::var("NATIVE_TEST_MINT").expect("NATIVE_TEST_MINT must be set");
+        let rpc = Arc::new(RpcClient::new_with_commitment(
+            url,
+            CommitmentConfig::confirmed(),
+        ));
+        let payer = Keypair::new();
+
+        let airdrop = rpc
+            .request_airdrop(&payer.pubkey(), 2 * LAMPORTS_PER_SOL)
+            .await
+            .unwrap();
+        assert!(await_confirmation(&rpc, &airdrop).await.unwrap());
+
+        let senders = vec![("local".to_string(), Arc::clone(&rpc))];
+        let result = execute_native_buy_via(&rpc, &senders, &payer, &mint, 0.1, 25, 0.0)
+            .await
+            .unwrap();
+        assert!(result.confirmed);
+        assert!(result.tokens_out > 0);
+
+        let mint = Pubkey::from_str(&mint).unwrap();
+        let token_program = rpc.get_account(&mint).await.unwrap().owner;
+        let user_ata =
+            get_associated_token_address_with_program_id(&payer.pubkey(), &mint, &token_program);
+        let balance = rpc.get_token_account_balance(&user_ata).await.unwrap();
+        assert!(balance.amount.parse::<u64>().unwrap() >= result.tokens_out);
+    }
+}
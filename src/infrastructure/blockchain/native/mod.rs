@@ -0,0 +1,7 @@
+pub mod client;
+pub mod pump;
+pub mod pump_swap;
+pub mod types;
+
+pub use client::{execute_native_buy, keypair_from_base58, native_rpc_client};
+pub use types::{NativeBuyResult, NativeRoute};
@@ -0,0 +1,284 @@
+use super::types::{
+    BUY_DISCRIMINATOR, PUMP_FEE_PROGRAM_ID, PUMP_PROGRAM_ID, read_pubkey, read_u64,
+};
+use anyhow::{Result, anyhow};
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+
+const BONDING_CURVE_MIN_LEN: usize = 8 + 8 * 5 + 1 + 32;
+const GLOBAL_FEE_RECIPIENT_OFFSET: usize = 8 + 1 + 32;
+const GLOBAL_FEE_BPS_OFFSET: usize = GLOBAL_FEE_RECIPIENT_OFFSET + 32 + 8 * 4;
+
+pub struct BondingCurveState {
+    pub virtual_token_reserves: u64,
+    pub virtual_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub token_total_supply: u64,
+    pub complete: bool,
+    pub creator: Pubkey,
+}
+
+pub struct PumpGlobalState {
+    pub fee_recipient: Pubkey,
+    pub fee_basis_points: u64,
+}
+
+pub fn global_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"global"], &PUMP_PROGRAM_ID).0
+}
+
+pub fn bonding_curve_pda(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"bonding-curve", mint.as_ref()], &PUMP_PROGRAM_ID).0
+}
+
+fn creator_vault_pda(creator: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"creator-vault", creator.as_ref()], &PUMP_PROGRAM_ID).0
+}
+
+fn event_authority_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"__event_authority"], &PUMP_PROGRAM_ID).0
+}
+
+fn global_volume_accumulator_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"global_volume_accumulator"], &PUMP_PROGRAM_ID).0
+}
+
+fn user_volume_accumulator_pda(user: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"user_volume_accumulator", user.as_ref()],
+        &PUMP_PROGRAM_ID,
+    )
+    .0
+}
+
+fn fee_config_pda() -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"fee_config", PUMP_PROGRAM_ID.as_ref()],
+        &PUMP_FEE_PROGRAM_ID,
+    )
+    .0
+}
+
+pub fn parse_bonding_curve(data: &[u8]) -> Result<BondingCurveState> {
+    if data.len() < BONDING_CURVE_MIN_LEN {
+        return Err(anyhow!(
+            "Bonding curve account too short ({} bytes)",
+            data.len()
+        ));
+    }
+    let field = |offset| read_u64(data, offset).ok_or_else(|| anyhow!("Bonding curve truncated"));
+    Ok(BondingCurveState {
+        virtual_token_reserves: field(8)?,
+        virtual_sol_reserves: field(16)?,
+        real_token_reserves: field(24)?,
+        real_sol_reserves: field(32)?,
+        token_total_supply: field(40)?,
+        complete: data[48] != 0,
+        creator: read_pubkey(data, 49).ok_or_else(|| anyhow!("Bonding curve truncated"))?,
+    })
+}
+
+pub fn parse_global(data: &[u8]) -> Result<PumpGlobalState> {
+    Ok(PumpGlobalState {
+        fee_recipient: read_pubkey(data, GLOBAL_FEE_RECIPIENT_OFFSET)
+            .ok_or_else(|| anyhow!("pump.fun global account truncated"))?,
+        fee_basis_points: read_u64(data, GLOBAL_FEE_BPS_OFFSET)
+            .ok_or_else(|| anyhow!("pump.fun global account truncated"))?,
+    })
+}
+
+pub fn quote_tokens_out(curve: &BondingCurveState, sol_in: u64, fee_basis_points: u64) -> u64 {
+    let net_sol = sol_in as u128 * 10_000 / (10_000 + fee_basis_points as u128);
+    let denominator = curve.virtual_sol_reserves as u128 + net_sol;
+    if denominator == 0 {
+        return 0;
+    }
+    let tokens = curve.virtual_token_reserves as u128 * net_sol / denominator;
+    tokens.min(curve.real_token_reserves as u128) as u64
+}
+
+pub fn build_buy_instruction(
+    user: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    curve: &BondingCurveState,
+    global: &PumpGlobalState,
+    tokens_out: u64,
+    max_sol_cost: u64,
+) -> Instruction {
+    let bonding_curve = bonding_curve_pda(mint);
+    let associated_bonding_curve =
+        get_associated_token_address_with_program_id(&bonding_curve, mint, token_program);
+    let associated_user = get_associated_token_address_with_program_id(user, mint, token_program);
+
+    let mut data = Vec::with_capacity(8 + 8 + 8 + 1);
+    data.extend_from_slice(&BUY_DISCRIMINATOR);
+    data.extend_from_slice(&tokens_out.to_le_bytes());
+    data.extend_from_slice(&max_sol_cost.to_le_bytes());
+    data.push(0);
+
+    Instruction {
+        program_id: PUMP_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(global_pda(), false),
+            AccountMeta::new(global.fee_recipient, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(bonding_curve, false),
+            AccountMeta::new(associated_bonding_curve, false),
+            AccountMeta::new(associated_user, false),
+            AccountMeta::new(*user, true),
+            AccountMeta::new_readonly(solana_system_interface::program::ID, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new(creator_vault_pda(&curve.creator), false),
+            AccountMeta::new_readonly(event_authority_pda(), false),
+            AccountMeta::new_readonly(PUMP_PROGRAM_ID, false),
+            AccountMeta::new(global_volume_accumulator_pda(), false),
+            AccountMeta::new(user_volume_accumulator_pda(user), false),
+            AccountMeta::new_readonly(fee_config_pda(), false),
+            AccountMeta::new_readonly(PUMP_FEE_PROGRAM_ID, false),
+        ],
+        data,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::pubkey;
+
+    const MINT: Pubkey = pubkey!("9BB6NFEcjBCtnNLFko2FqVQBq8HHM13kCyYcdQbgpump");
+
+    fn curve() -> BondingCurveState {
+        BondingCurveState {
+            virtual_token_reserves: 1_073_000_000_000_000,
+            virtual_sol_reserves: 30_000_000_000,
+            real_token_reserves: 793_100_000_000_000,
+            real_sol_reserves: 0,
+            token_total_supply: 1_000_000_000_000_000,
+            complete: false,
+            creator: Pubkey::new_unique(),
+        }
+    }
+
+    #[test]
+    fn derives_known_program_addresses() {
+        assert_eq!(
+            global_pda(),
+            pubkey!("4wTV1YmiEkRvAtNtsSGPtUrqRYQMe5SKy2uB4Jjaxnjf")
+        );
+        assert_eq!(
+            event_authority_pda(),
+            pubkey!("Ce6TQqeHC9p8KetsN6JsjHK7UTZk7nasjjnr7XxXp9F1")
+        );
+        assert_eq!(
+            bonding_curve_pda(&MINT),
+            pubkey!("TBHe5tJnuT4CQbHorJ1uVdfUoaYGPKgfCpiv2jgesVN")
+        );
+    }
+
+    #[test]
+    fn parses_bonding_curve_layout() {
+        let creator = Pubkey::new_unique();
+        let mut data = vec![0u8; 8];
+        for value in [1u64, 2, 3, 4, 5] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.push(1);
+        data.extend_from_slice(creator.as_ref());
+
+        let state = parse_bonding_curve(&data).unwrap();
+        assert_eq!(state.virtual_token_reserves, 1);
+        assert_eq!(state.virtual_sol_reserves, 2);
+        assert_eq!(state.real_token_reserves, 3);
+        assert_eq!(state.real_sol_reserves, 4);
+        assert_eq!(state.token_total_supply, 5);
+        assert!(state.complete);
+        assert_eq!(state.creator, creator);
+
+        assert!(parse_bonding_curve(&data[..data.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn parses_global_fee_fields() {
+        let fee_recipient = Pubkey::new_unique();
+        let mut data = vec![0u8; GLOBAL_FEE_BPS_OFFSET + 8];
+        data[GLOBAL_FEE_RECIPIENT_OFFSET..GLOBAL_FEE_RECIPIENT_OFFSET + 32]
+            .copy_from_slice(fee_recipient.as_ref());
+        data[GLOBAL_FEE_BPS_OFFSET..].copy_from_slice(&95u64.to_le_bytes());
+
+        let global = parse_global(&data).unwrap();
+        assert_eq!(global.fee_recipient, fee_recipient);
+        assert_eq!(global.fee_basis_points, 95);
+
+        assert!(parse_global(&data[..GLOBAL_FEE_BPS_OFFSET]).is_err());
+    }
+
+    #[test]
+    fn quotes_tokens_net_of_fee() {
+        assert_eq!(
+            quote_tokens_out(&curve(), 1_000_000_000, 100),
+            34_281_150_129_545
+        );
+        assert_eq!(quote_tokens_out(&curve(), 0, 100), 0);
+    }
+
+    #[test]
+    fn quote_is_capped_by_real_token_reserves() {
+        let mut state = curve();
+        state.real_token_reserves = 1_000;
+        assert_eq!(quote_tokens_out(&state, 1_000_000_000, 100), 1_000);
+
+        state.virtual_sol_reserves = 0;
+        state.virtual_token_reserves = 0;
+        assert_eq!(quote_tokens_out(&state, 0, 0), 0);
+    }
+
+    #[test]
+    fn builds_buy_instruction() {
+        let user = Pubkey::new_unique();
+        let token_program = spl_token::id();
+        let state = curve();
+        let global = PumpGlobalState {
+            fee_recipient: Pubkey::new_unique(),
+            fee_basis_points: 100,
+        };
+
+        let instruction =
+            build_buy_instruction(&user, &MINT, &token_program, &state, &global, 7, 9);
+
+        assert_eq!(instruction.program_id, PUMP_PROGRAM_ID);
+        assert_eq!(instruction.data.len(), 25);
+        assert_eq!(instruction.data[..8], BUY_DISCRIMINATOR);
+        assert_eq!(instruction.data[8..16], 7u64.to_le_bytes());
+        assert_eq!(instruction.data[16..24], 9u64.to_le_bytes());
+        assert_eq!(instruction.data[24], 0);
+
+        let accounts = &instruction.accounts;
+        assert_eq!(accounts.len(), 16);
+        assert_eq!(accounts.iter().filter(|meta| meta.is_signer).count(), 1);
+        assert_eq!(accounts[0].pubkey, global_pda());
+        assert_eq!(accounts[1].pubkey, global.fee_recipient);
+        assert_eq!(accounts[2].pubkey, MINT);
+        assert_eq!(accounts[3].pubkey, bonding_curve_pda(&MINT));
+        assert_eq!(
+            accounts[4].pubkey,
+            get_associated_token_address_with_program_id(
+                &bonding_curve_pda(&MINT),
+                &MINT,
+                &token_program
+            )
+        );
+        assert_eq!(
+            accounts[5].pubkey,
+            get_associated_token_address_with_program_id(&user, &MINT, &token_program)
+        );
+        assert_eq!(accounts[6].pubkey, user);
+        assert!(accounts[6].is_signer && accounts[6].is_writable);
+        assert_eq!(accounts[8].pubkey, token_program);
+        assert_eq!(accounts[9].pubkey, creator_vault_pda(&state.creator));
+        assert_eq!(accounts[13].pubkey, user_volume_accumulator_pda(&user));
+        assert_eq!(accounts[15].pubkey, PUMP_FEE_PROGRAM_ID);
+    }
+}
@@ -0,0 +1,338 @@
+use super::types::{
+    BUY_DISCRIMINATOR, PUMP_AMM_PROGRAM_ID, PUMP_FEE_PROGRAM_ID, PUMP_PROGRAM_ID, WSOL_MINT,
+    read_pubkey, read_u64,
+};
+use anyhow::{Result, anyhow};
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+
+const GLOBAL_CONFIG_LP_FEE_OFFSET: usize = 8 + 32;
+const GLOBAL_CONFIG_PROTOCOL_FEE_OFFSET: usize = GLOBAL_CONFIG_LP_FEE_OFFSET + 8;
+const GLOBAL_CONFIG_FEE_RECIPIENTS_OFFSET: usize = GLOBAL_CONFIG_PROTOCOL_FEE_OFFSET + 8 + 1;
+const GLOBAL_CONFIG_CREATOR_FEE_OFFSET: usize = GLOBAL_CONFIG_FEE_RECIPIENTS_OFFSET + 32 * 8;
+const POOL_BASE_MINT_OFFSET: usize = 8 + 1 + 2 + 32;
+const POOL_QUOTE_MINT_OFFSET: usize = POOL_BASE_MINT_OFFSET + 32;
+const POOL_BASE_TOKEN_ACCOUNT_OFFSET: usize = POOL_QUOTE_MINT_OFFSET + 32 * 2;
+const POOL_QUOTE_TOKEN_ACCOUNT_OFFSET: usize = POOL_BASE_TOKEN_ACCOUNT_OFFSET + 32;
+const POOL_COIN_CREATOR_OFFSET: usize = POOL_QUOTE_TOKEN_ACCOUNT_OFFSET + 32 + 8;
+const CANONICAL_POOL_INDEX: u16 = 0;
+
+pub struct PumpSwapGlobalConfig {
+    pub protocol_fee_recipient: Pubkey,
+    pub total_fee_basis_points: u64,
+}
+
+pub struct PumpSwapPoolAccounts {
+    pub pool: Pubkey,
+    pub base_mint: Pubkey,
+    pub pool_base_token_account: Pubkey,
+    pub pool_quote_token_account: Pubkey,
+    pub coin_creator: Pubkey,
+}
+
+pub fn global_config_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"global_config"], &PUMP_AMM_PROGRAM_ID).0
+}
+
+fn pool_authority_pda(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"pool-authority", mint.as_ref()], &PUMP_PROGRAM_ID).0
+}
+
+pub fn canonical_pool_pda(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"pool",
+            &CANONICAL_POOL_INDEX.to_le_bytes(),
+            pool_authority_pda(mint).as_ref(),
+            mint.as_ref(),
+            WSOL_MINT.as_ref(),
+        ],
+        &PUMP_AMM_PROGRAM_ID,
+    )
+    .0
+}
+
+fn event_authority_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"__event_authority"], &PUMP_AMM_PROGRAM_ID).0
+}
+
+fn coin_creator_vault_authority_pda(coin_creator: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"creator_vault", coin_creator.as_ref()],
+        &PUMP_AMM_PROGRAM_ID,
+    )
+    .0
+}
+
+fn global_volume_accumulator_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"global_volume_accumulator"], &PUMP_AMM_PROGRAM_ID).0
+}
+
+fn user_volume_accumulator_pda(user: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"user_volume_accumulator", user.as_ref()],
+        &PUMP_AMM_PROGRAM_ID,
+    )
+    .0
+}
+
+fn fee_config_pda() -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"fee_config", PUMP_AMM_PROGRAM_ID.as_ref()],
+        &PUMP_FEE_PROGRAM_ID,
+    )
+    .0
+}
+
+pub fn parse_global_config(data: &[u8]) -> Result<PumpSwapGlobalConfig> {
+    let truncated = || anyhow!("PumpSwap global config truncated");
+    let lp_fee = read_u64(data, GLOBAL_CONFIG_LP_FEE_OFFSET).ok_or_else(truncated)?;
+    let protocol_fee = read_u64(data, GLOBAL_CONFIG_PROTOCOL_FEE_OFFSET).ok_or_else(truncated)?;
+    let creator_fee = read_u64(data, GLOBAL_CONFIG_CREATOR_FEE_OFFSET).unwrap_or(0);
+    Ok(PumpSwapGlobalConfig {
+        protocol_fee_recipient: read_pubkey(data, GLOBAL_CONFIG_FEE_RECIPIENTS_OFFSET)
+            .ok_or_else(truncated)?,
+        total_fee_basis_points: lp_fee + protocol_fee + creator_fee,
+    })
+}
+
+pub fn parse_pool(pool: Pubkey, data: &[u8]) -> Result<PumpSwapPoolAccounts> {
+    let truncated = || anyhow!("PumpSwap pool {} truncated", pool);
+    let base_mint = read_pubkey(data, POOL_BASE_MINT_OFFSET).ok_or_else(truncated)?;
+    let quote_mint = read_pubkey(data, POOL_QUOTE_MINT_OFFSET).ok_or_else(truncated)?;
+    if quote_mint != WSOL_MINT {
+        return Err(anyhow!(
+            "PumpSwap pool {} is not quoted in SOL (quote mint {})",
+            pool,
+            quote_mint
+        ));
+    }
+    Ok(PumpSwapPoolAccounts {
+        pool,
+        base_mint,
+        pool_base_token_account: read_pubkey(data, POOL_BASE_TOKEN_ACCOUNT_OFFSET)
+            .ok_or_else(truncated)?,
+        pool_quote_token_account: read_pubkey(data, POOL_QUOTE_TOKEN_ACCOUNT_OFFSET)
+            .ok_or_else(truncated)?,
+        coin_creator: read_pubkey(data, POOL_COIN_CREATOR_OFFSET).unwrap_or_default(),
+    })
+}
+
+pub fn quote_base_out(
+    base_reserve: u64,
+    quote_reserve: u64,
+    quote_in: u64,
+    total_fee_basis_points: u64,
+) -> u64 {
+    let net_quote = quote_in as u128 * 10_000 / (10_000 + total_fee_basis_points as u128);
+    let denominator = quote_reserve as u128 + net_quote;
+    if denominator == 0 {
+        return 0;
+    }
+    (base_reserve as u128 * net_quote / denominator) as u64
+}
+
+pub fn build_buy_instruction(
+    user: &Pubkey,
+    pool: &PumpSwapPoolAccounts,
+    base_token_program: &Pubkey,
+    config: &PumpSwapGlobalConfig,
+    base_amount_out: u64,
+    max_quote_amount_in: u64,
+) -> Instruction {
+    let quote_token_program = spl_token::id();
+    let user_base_token_account =
+        get_associated_token_address_with_program_id(user, &pool.base_mint, base_token_program);
+    let user_quote_token_account =
+        get_associated_token_address_with_program_id(user, &WSOL_MINT, &quote_token_program);
+    let protocol_fee_recipient_token_account = get_associated_token_address_with_program_id(
+        &config.protocol_fee_recipient,
+        &WSOL_MINT,
+        &quote_token_program,
+    );
+    let coin_creator_vault_authority = coin_creator_vault_authority_pda(&pool.coin_creator);
+    let coin_creator_vault_ata = get_associated_token_address_with_program_id(
+        &coin_creator_vault_authority,
+        &WSOL_MINT,
+        &quote_token_program,
+    );
+
+    let mut data = Vec::with_capacity(8 + 8 + 8 + 1);
+    data.extend_from_slice(&BUY_DISCRIMINATOR);
+    data.extend_from_slice(&base_amount_out.to_le_bytes());
+    data.extend_from_slice(&max_quote_amount_in.to_le_bytes());
+    data.push(0);
+
+    Instruction {
+        program_id: PUMP_AMM_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(pool.pool, false),
+            AccountMeta::new(*user, true),
+            AccountMeta::new_readonly(global_config_pda(), false),
+            AccountMeta::new_readonly(pool.base_mint, false),
+            AccountMeta::new_readonly(WSOL_MINT, false),
+            AccountMeta::new(user_base_token_account, false),
+            AccountMeta::new(user_quote_token_account, false),
+            AccountMeta::new(pool.pool_base_token_account, false),
+            AccountMeta::new(pool.pool_quote_token_account, false),
+            AccountMeta::new_readonly(config.protocol_fee_recipient, false),
+            AccountMeta::new(protocol_fee_recipient_token_account, false),
+            AccountMeta::new_readonly(*base_token_program, false),
+            AccountMeta::new_readonly(quote_token_program, false),
+            AccountMeta::new_readonly(solana_system_interface::program::ID, false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+            AccountMeta::new_readonly(event_authority_pda(), false),
+            AccountMeta::new_readonly(PUMP_AMM_PROGRAM_ID, false),
+            AccountMeta::new(coin_creator_vault_ata, false),
+            AccountMeta::new_readonly(coin_creator_vault_authority, false),
+            AccountMeta::new(global_volume_accumulator_pda(), false),
+            AccountMeta::new(user_volume_accumulator_pda(user), false),
+            AccountMeta::new_readonly(fee_config_pda(), false),
+            AccountMeta::new_readonly(PUMP_FEE_PROGRAM_ID, false),
+        ],
+        data,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::pubkey;
+
+    const MINT: Pubkey = pubkey!("9BB6NFEcjBCtnNLFko2FqVQBq8HHM13kCyYcdQbgpump");
+
+    fn pool_data(quote_mint: &Pubkey) -> (Vec<u8>, [Pubkey; 3]) {
+        let base_vault = Pubkey::new_unique();
+        let quote_vault = Pubkey::new_unique();
+        let coin_creator = Pubkey::new_unique();
+        let mut data = vec![0u8; POOL_COIN_CREATOR_OFFSET + 32];
+        let mut write = |offset: usize, key: &Pubkey| {
+            data[offset..offset + 32].copy_from_slice(key.as_ref());
+        };
+        write(POOL_BASE_MINT_OFFSET, &MINT);
+        write(POOL_QUOTE_MINT_OFFSET, quote_mint);
+        write(POOL_BASE_TOKEN_ACCOUNT_OFFSET, &base_vault);
+        write(POOL_QUOTE_TOKEN_ACCOUNT_OFFSET, &quote_vault);
+        write(POOL_COIN_CREATOR_OFFSET, &coin_creator);
+        (data, [base_vault, quote_vault, coin_creator])
+    }
+
+    #[test]
+    fn derives_known_program_addresses() {
+        assert_eq!(
+            global_config_pda(),
+            pubkey!("ADyA8hdefvWN2dbGGWFotbzWxrAvLW83WG6QCVXvJKqw")
+        );
+        assert_eq!(
+            pool_authority_pda(&MINT),
+            pubkey!("FUh7qWqBJCK5wAq2DaiT47eHyomMVr4mT2sQc9GKrf44")
+        );
+        assert_eq!(
+            canonical_pool_pda(&MINT),
+            pubkey!("EVtHHoEb2V35V1fpzUZcaASgFn6N4yGWoaZ1twNDVmjW")
+        );
+    }
+
+    #[test]
+    fn parses_global_config_fees() {
+        let recipient = Pubkey::new_unique();
+        let mut data = vec![0u8; GLOBAL_CONFIG_CREATOR_FEE_OFFSET + 8];
+        data[GLOBAL_CONFIG_LP_FEE_OFFSET..GLOBAL_CONFIG_LP_FEE_OFFSET + 8]
+            .copy_from_slice(&20u64.to_le_bytes());
+        data[GLOBAL_CONFIG_PROTOCOL_FEE_OFFSET..GLOBAL_CONFIG_PROTOCOL_FEE_OFFSET + 8]
+            .copy_from_slice(&5u64.to_le_bytes());
+        data[GLOBAL_CONFIG_FEE_RECIPIENTS_OFFSET..GLOBAL_CONFIG_FEE_RECIPIENTS_OFFSET + 32]
+            .copy_from_slice(recipient.as_ref());
+        data[GLOBAL_CONFIG_CREATOR_FEE_OFFSET..].copy_from_slice(&5u64.to_le_bytes());
+
+        let config = parse_global_config(&data).unwrap();
+        assert_eq!(config.protocol_fee_recipient, recipient);
+        assert_eq!(config.total_fee_basis_points, 30);
+
+        let legacy = parse_global_config(&data[..GLOBAL_CONFIG_CREATOR_FEE_OFFSET]).unwrap();
+        assert_eq!(legacy.total_fee_basis_points, 25);
+
+        assert!(parse_global_config(&data[..GLOBAL_CONFIG_PROTOCOL_FEE_OFFSET]).is_err());
+    }
+
+    #[test]
+    fn parses_sol_quoted_pool() {
+        let pool = Pubkey::new_unique();
+        let (data, [base_vault, quote_vault, coin_creator]) = pool_data(&WSOL_MINT);
+
+        let accounts = parse_pool(pool, &data).unwrap();
+        assert_eq!(accounts.pool, pool);
+        assert_eq!(accounts.base_mint, MINT);
+        assert_eq!(accounts.pool_base_token_account, base_vault);
+        assert_eq!(accounts.pool_quote_token_account, quote_vault);
+        assert_eq!(accounts.coin_creator, coin_creator);
+
+        let without_creator = parse_pool(pool, &data[..POOL_COIN_CREATOR_OFFSET]).unwrap();
+        assert_eq!(without_creator.coin_creator, Pubkey::default());
+    }
+
+    #[test]
+    fn rejects_pool_not_quoted_in_sol() {
+        let (data, _) = pool_data(&Pubkey::new_unique());
+        assert!(parse_pool(Pubkey::new_unique(), &data).is_err());
+    }
+
+    #[test]
+    fn quotes_base_net_of_fee() {
+        assert_eq!(
+            quote_base_out(200_000_000_000_000, 80_000_000_000, 1_000_000_000, 25),
+            2_463_054_186_182
+        );
+        assert_eq!(quote_base_out(0, 0, 0, 25), 0);
+    }
+
+    #[test]
+    fn builds_buy_instruction() {
+        let user = Pubkey::new_unique();
+        let token_program = spl_token_2022::id();
+        let (data, _) = pool_data(&WSOL_MINT);
+        let pool = parse_pool(canonical_pool_pda(&MINT), &data).unwrap();
+        let config = PumpSwapGlobalConfig {
+            protocol_fee_recipient: Pubkey::new_unique(),
+            total_fee_basis_points: 30,
+        };
+
+        let instruction = build_buy_instruction(&user, &pool, &token_program, &config, 11, 13);
+
+        assert_eq!(instruction.program_id, PUMP_AMM_PROGRAM_ID);
+        assert_eq!(instruction.data.len(), 25);
+        assert_eq!(instruction.data[..8], BUY_DISCRIMINATOR);
+        assert_eq!(instruction.data[8..16], 11u64.to_le_bytes());
+        assert_eq!(instruction.data[16..24], 13u64.to_le_bytes());
+        assert_eq!(instruction.data[24], 0);
+
+        let accounts = &instruction.accounts;
+        assert_eq!(accounts.len(), 23);
+        assert_eq!(accounts.iter().filter(|meta| meta.is_signer).count(), 1);
+        assert_eq!(accounts[0].pubkey, pool.pool);
+        assert_eq!(accounts[1].pubkey, user);
+        assert!(accounts[1].is_signer && accounts[1].is_writable);
+        assert_eq!(accounts[2].pubkey, global_config_pda());
+        assert_eq!(accounts[3].pubkey, MINT);
+        assert_eq!(accounts[4].pubkey, WSOL_MINT);
+        assert_eq!(
+            accounts[5].pubkey,
+            get_associated_token_address_with_program_id(&user, &MINT, &token_program)
+        );
+        assert_eq!(
+            accounts[6].pubkey,
+            get_associated_token_address_with_program_id(&user, &WSOL_MINT, &spl_token::id())
+        );
+        assert_eq!(accounts[7].pubkey, pool.pool_base_token_account);
+        assert_eq!(accounts[8].pubkey, pool.pool_quote_token_account);
+        assert_eq!(accounts[11].pubkey, token_program);
+        assert_eq!(accounts[12].pubkey, spl_token::id());
+        assert_eq!(
+            accounts[18].pubkey,
+            coin_creator_vault_authority_pda(&pool.coin_creator)
+        );
+        assert_eq!(accounts[20].pubkey, user_volume_accumulator_pda(&user));
+        assert_eq!(accounts[22].pubkey, PUMP_FEE_PROGRAM_ID);
+    }
+}
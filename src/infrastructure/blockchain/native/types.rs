@@ -0,0 +1,83 @@
+use solana_sdk::pubkey;
+use solana_sdk::pubkey::Pubkey;
+
+pub const PUMP_PROGRAM_ID: Pubkey = pubkey!("6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P");
+pub const PUMP_AMM_PROGRAM_ID: Pubkey = pubkey!("pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA");
+pub const PUMP_FEE_PROGRAM_ID: Pubkey = pubkey!("pfeeUxB6jkeY1Hxd7CsFCAjcbHA9rWtchMGdZ6VojVZ");
+pub const WSOL_MINT: Pubkey = pubkey!("So11111111111111111111111111111111111111112");
+
+pub const BUY_DISCRIMINATOR: [u8; 8] = [102, 6, 61, 18, 1, 218, 235, 234];
+
+pub const BONDING_CURVE_COMPUTE_UNITS: u32 = 200_000;
+pub const PUMP_SWAP_COMPUTE_UNITS: u32 = 300_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NativeRoute {
+    BondingCurve,
+    PumpSwap,
+}
+
+impl NativeRoute {
+    pub fn label(&self) -> &'static str {
+        match self {
+            NativeRoute::BondingCurve => "pump.fun bonding curve",
+            NativeRoute::PumpSwap => "PumpSwap AMM",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct NativeBuyResult {
+    pub route: NativeRoute,
+    pub signature: String,
+    pub tokens_out: u64,
+    pub token_decimals: u8,
+    pub max_sol_cost_lamports: u64,
+    pub confirmed: bool,
+}
+
+pub(super) fn read_u64(data: &[u8], offset: usize) -> Option<u64> {
+    data.get(offset..offset + 8)
+        .and_then(|bytes| bytes.try_into().ok())
+        .map(u64::from_le_bytes)
+}
+
+pub(super) fn read_pubkey(data: &[u8], offset: usize) -> Option<Pubkey> {
+    data.get(offset..offset + 32)
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .map(Pubkey::new_from_array)
+}
+
+pub(super) fn apply_slippage_up(amount: u64, slippage_percent: u32) -> u64 {
+    let scaled = amount as u128 * (100 + slippage_percent as u128) / 100;
+    scaled.min(u64::MAX as u128) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_u64_decodes_little_endian_and_rejects_short_slices() {
+        let mut data = vec![0u8; 4];
+        data.extend_from_slice(&42u64.to_le_bytes());
+        assert_eq!(read_u64(&data, 4), Some(42));
+        assert_eq!(read_u64(&data, 5), None);
+        assert_eq!(read_u64(&data, usize::MAX - 2), None);
+    }
+
+    #[test]
+    fn read_pubkey_decodes_and_rejects_short_slices() {
+        let mut data = vec![0u8; 3];
+        data.extend_from_slice(WSOL_MINT.as_ref());
+        assert_eq!(read_pubkey(&data, 3), Some(WSOL_MINT));
+        assert_eq!(read_pubkey(&data, 4), None);
+    }
+
+    #[test]
+    fn apply_slippage_up_scales_and_saturates() {
+        assert_eq!(apply_slippage_up(1_000_000_000, 0), 1_000_000_000);
+        assert_eq!(apply_slippage_up(1_000_000_000, 15), 1_150_000_000);
+        assert_eq!(apply_slippage_up(u64::MAX, 50), u64::MAX);
+    }
+}
@@ -1,4 +1,6 @@
-use crate::infrastructure::blockchain::config::HELIUS_RPC_URL;
+use crate::infrastructure::blockchain::config::{
+    HELIUS_RPC_URL, NODE1_RPC_URL, SHYFT_RPC_URL, ZEROSLOT_RPC_URL,
+};
 use crate::infrastructure::blockchain::types::RpcClients;
 use solana_rpc_client::nonblocking::rpc_client::RpcClient;
 use std::sync::Arc;
@@ -6,5 +8,8 @@ use std::sync::Arc;
 pub fn create_rpc_clients() -> RpcClients {
     RpcClients {
         helius_client: Arc::new(RpcClient::new(HELIUS_RPC_URL.to_string())),
+        zeroslot_client: Arc::new(RpcClient::new(ZEROSLOT_RPC_URL.to_string())),
+        node1_client: Arc::new(RpcClient::new(NODE1_RPC_URL.to_string())),
+        shyft_client: Arc::new(RpcClient::new(SHYFT_RPC_URL.to_string())),
     }
 }
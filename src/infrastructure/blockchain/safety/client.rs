@@ -0,0 +1,79 @@
+use super::types::{MintSafetyIssue, MintSafetyReport};
+use crate::infrastructure::blockchain::types::RpcClients;
+use anyhow::{Result, anyhow};
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use spl_token_2022::extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions};
+use spl_token_2022::state::Mint;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+pub async fn check_mint_safety(
+    rpc_clients: &RpcClients,
+    mint: &str,
+    budget: Duration,
+) -> Result<MintSafetyReport> {
+    let started = Instant::now();
+    let mint_pubkey =
+        Pubkey::from_str(mint).map_err(|e| anyhow!("Invalid mint address {}: {}", mint, e))?;
+
+    let response = tokio::time::timeout(
+        budget,
+        rpc_clients
+            .helius_client
+            .get_account_with_commitment(&mint_pubkey, CommitmentConfig::processed()),
+    )
+    .await
+    .map_err(|_| anyhow!("Mint lookup exceeded {} ms budget", budget.as_millis()))??;
+
+    let account = response
+        .value
+        .ok_or_else(|| anyhow!("Mint account {} not found", mint))?;
+
+    let is_token_2022 = if account.owner == spl_token_2022::id() {
+        true
+    } else if account.owner == spl_token::id() {
+        false
+    } else {
+        return Err(anyhow!(
+            "Account {} is not owned by a token program ({})",
+            mint,
+            account.owner
+        ));
+    };
+
+    let state = StateWithExtensions::<Mint>::unpack(&account.data)
+        .map_err(|e| anyhow!("Failed to unpack mint {}: {}", mint, e))?;
+
+    let mut issues = Vec::new();
+    if state.base.mint_authority.is_some() {
+        issues.push(MintSafetyIssue::MintAuthorityActive);
+    }
+    if state.base.freeze_authority.is_some() {
+        issues.push(MintSafetyIssue::FreezeAuthorityActive);
+    }
+
+    if is_token_2022 {
+        let extensions = state
+            .get_extension_types()
+            .map_err(|e| anyhow!("Failed to read mint extensions for {}: {}", mint, e))?;
+        for extension in extensions {
+            let issue = match extension {
+                ExtensionType::TransferFeeConfig => Some(MintSafetyIssue::TransferFee),
+                ExtensionType::TransferHook => Some(MintSafetyIssue::TransferHook),
+                ExtensionType::PermanentDelegate => Some(MintSafetyIssue::PermanentDelegate),
+                ExtensionType::NonTransferable => Some(MintSafetyIssue::NonTransferable),
+                _ => None,
+            };
+            if let Some(issue) = issue {
+                issues.push(issue);
+            }
+        }
+    }
+
+    Ok(MintSafetyReport {
+        is_token_2022,
+        issues,
+        elapsed_ms: started.elapsed().as_millis(),
+    })
+}
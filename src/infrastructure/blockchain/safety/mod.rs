@@ -0,0 +1,5 @@
+pub mod client;
+pub mod types;
+
+pub use client::check_mint_safety;
+pub use types::{MintSafetyIssue, MintSafetyReport, SAFETY_CHECK_BUDGET};
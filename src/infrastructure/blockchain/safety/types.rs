@@ -0,0 +1,47 @@
+use std::time::Duration;
+
+pub const SAFETY_CHECK_BUDGET: Duration = Duration::from_millis(250);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MintSafetyIssue {
+    MintAuthorityActive,
+    FreezeAuthorityActive,
+    TransferFee,
+    TransferHook,
+    PermanentDelegate,
+    NonTransferable,
+}
+
+impl MintSafetyIssue {
+    pub fn label(&self) -> &'static str {
+        match self {
+            MintSafetyIssue::MintAuthorityActive => "Mint authority active",
+            MintSafetyIssue::FreezeAuthorityActive => "Freeze authority active",
+            MintSafetyIssue::TransferFee => "Token-2022 transfer fee",
+            MintSafetyIssue::TransferHook => "Token-2022 transfer hook",
+            MintSafetyIssue::PermanentDelegate => "Token-2022 permanent delegate",
+            MintSafetyIssue::NonTransferable => "Token-2022 non-transferable",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct MintSafetyReport {
+    pub is_token_2022: bool,
+    pub issues: Vec<MintSafetyIssue>,
+    pub elapsed_ms: u128,
+}
+
+impl MintSafetyReport {
+    pub fn is_safe(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    pub fn issues_summary(&self) -> String {
+        self.issues
+            .iter()
+            .map(|issue| issue.label())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
@@ -1,7 +1,24 @@
+use super::broadcast::rank_senders;
 use solana_rpc_client::nonblocking::rpc_client::RpcClient;
 use std::sync::Arc;
 
 #[derive(Clone)]
 pub struct RpcClients {
     pub helius_client: Arc<RpcClient>,
+    pub zeroslot_client: Arc<RpcClient>,
+    pub node1_client: Arc<RpcClient>,
+    pub shyft_client: Arc<RpcClient>,
+}
+
+impl RpcClients {
+    pub fn senders(&self) -> Vec<(String, Arc<RpcClient>)> {
+        let mut senders = vec![
+            ("0slot".to_string(), Arc::clone(&self.zeroslot_client)),
+            ("node1".to_string(), Arc::clone(&self.node1_client)),
+            ("helius".to_string(), Arc::clone(&self.helius_client)),
+            ("shyft".to_string(), Arc::clone(&self.shyft_client)),
+        ];
+        rank_senders(&mut senders);
+        senders
+    }
 }
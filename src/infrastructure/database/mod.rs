@@ -1,3 +1,6 @@
 pub mod redis;
 
-pub use redis::{RedisResult, ensure_initialized, get_connection, hmget_strings, hset_multi};
+pub use redis::{
+    RedisResult, decr, del_keys, ensure_initialized, get_connection, hdel_fields, hgetall_strings,
+    hmget_strings, hset_multi, incr_ex, set_nx_ex,
+};
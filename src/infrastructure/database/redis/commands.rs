@@ -1,5 +1,6 @@
 use super::client::get_conn;
 use super::types::RedisResult;
+use std::collections::HashMap;
 
 pub async fn hset_multi(key: &str, fields: &[(String, String)]) -> RedisResult<()> {
     if fields.is_empty() {
@@ -35,3 +36,63 @@ pub async fn hmget_strings(key: &str, fields: &[String]) -> RedisResult<Vec<Opti
 
     redis::cmd("HMGET").arg(&args).query_async(&mut conn).await
 }
+
+pub async fn hgetall_strings(key: &str) -> RedisResult<HashMap<String, String>> {
+    let mut conn = get_conn().await?;
+    redis::cmd("HGETALL").arg(key).query_async(&mut conn).await
+}
+
+pub async fn set_nx_ex(key: &str, value: &str, ttl_secs: u64) -> RedisResult<bool> {
+    let mut conn = get_conn().await?;
+    let mut cmd = redis::cmd("SET");
+    cmd.arg(key).arg(value).arg("NX");
+    if ttl_secs > 0 {
+        cmd.arg("EX").arg(ttl_secs);
+    }
+    let reply: Option<String> = cmd.query_async(&mut conn).await?;
+    Ok(reply.is_some())
+}
+
+const INCR_EX_SCRIPT: &str = r"
+local value = redis.call('INCR', KEYS[1])
+if value == 1 and tonumber(ARGV[1]) > 0 then
+    redis.call('EXPIRE', KEYS[1], ARGV[1])
+end
+return value
+";
+
+pub async fn incr_ex(key: &str, ttl_secs: u64) -> RedisResult<i64> {
+    let mut conn = get_conn().await?;
+    redis::Script::new(INCR_EX_SCRIPT)
+        .key(key)
+        .arg(ttl_secs)
+        .invoke_async(&mut conn)
+        .await
+}
+
+pub async fn decr(key: &str) -> RedisResult<i64> {
+    let mut conn = get_conn().await?;
+    redis::cmd("DECR").arg(key).query_async(&mut conn).await
+}
+
+pub async fn del_keys(keys: &[String]) -> RedisResult<u64> {
+    if keys.is_empty() {
+        return Ok(0);
+    }
+
+    let mut conn = get_conn().await?;
+    redis::cmd("DEL").arg(keys).query_async(&mut conn).await
+}
+
+pub async fn hdel_fields(key: &str, fields: &[String]) -> RedisResult<u64> {
+    if fields.is_empty() {
+        return Ok(0);
+    }
+
+    let mut conn = get_conn().await?;
+    redis::cmd("HDEL")
+        .arg(key)
+        .arg(fields)
+        .query_async(&mut conn)
+        .await
+}
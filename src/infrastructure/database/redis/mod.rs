@@ -3,5 +3,7 @@ pub mod commands;
 pub mod types;
 
 pub use client::{ensure_initialized, get_connection};
-pub use commands::{hmget_strings, hset_multi};
+pub use commands::{
+    decr, del_keys, hdel_fields, hgetall_strings, hmget_strings, hset_multi, incr_ex, set_nx_ex,
+};
 pub use types::RedisResult;
@@ -0,0 +1,119 @@
+use crate::infrastructure::blockchain::BloomCredentials;
+use crate::infrastructure::blockchain::bloom::{
+    ensure_bloom_ws_listener, listener_account_keys, stop_bloom_ws_listener,
+};
+use crate::interfaces::bot::{escape_markdown, get_all_user_ids, get_user_data};
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use redis::Client as RedisClient;
+use std::collections::{HashMap, HashSet};
+use teloxide::prelude::*;
+use teloxide::types::{ChatId, ParseMode};
+use tokio::time::{Duration, MissedTickBehavior};
+
+const MONITOR_INTERVAL: Duration = Duration::from_secs(300);
+const EXPIRY_WARNING_MS: i64 = 24 * 60 * 60 * 1000;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum ExpiryStage {
+    Soon,
+    Expired,
+}
+
+static EXPIRY_WARNINGS: Lazy<Mutex<HashSet<(String, i64, ExpiryStage)>>> =
+    Lazy::new(|| Mutex::new(HashSet::new()));
+
+fn expiry_stage(credentials: &BloomCredentials) -> Option<ExpiryStage> {
+    let remaining = credentials.expires_in_ms()?;
+    if remaining <= 0 {
+        Some(ExpiryStage::Expired)
+    } else if remaining <= EXPIRY_WARNING_MS {
+        Some(ExpiryStage::Soon)
+    } else {
+        None
+    }
+}
+
+async fn notify(chat_id: i64, message: String) {
+    let bot = Bot::from_env();
+    if let Err(e) = bot
+        .send_message(ChatId(chat_id), message)
+        .parse_mode(ParseMode::MarkdownV2)
+        .await
+    {
+        log::warn!("bloom_accounts.notify_failed chat_id={} err={}", chat_id, e);
+    }
+}
+
+async fn sync_bloom_accounts(redis_client: &RedisClient, redis_url: &str) -> Result<()> {
+    let user_ids = get_all_user_ids(redis_url).await?;
+    let mut con = redis_client.get_multiplexed_async_connection().await?;
+    let mut desired: HashMap<String, BloomCredentials> = HashMap::new();
+    let mut warnings = Vec::new();
+
+    if let Some(credentials) = BloomCredentials::from_env() {
+        desired.insert(credentials.account_key.clone(), credentials);
+    }
+    for chat_id in user_ids {
+        let Some(user_data) = get_user_data(&mut con, chat_id).await? else {
+            continue;
+        };
+        for account in &user_data.bloom_accounts {
+            let credentials = account.credentials(chat_id);
+            if let (Some(stage), Some(expires_at)) =
+                (expiry_stage(&credentials), credentials.expires_at)
+            {
+                let key = (credentials.account_key.clone(), expires_at, stage);
+                if EXPIRY_WARNINGS.lock().insert(key) {
+                    warnings.push((chat_id, account.name.clone(), stage, account.expiry_label()));
+                }
+            }
+            desired.insert(credentials.account_key.clone(), credentials);
+        }
+    }
+
+    for account_key in listener_account_keys() {
+        if !desired.contains_key(&account_key) {
+            stop_bloom_ws_listener(&account_key);
+        }
+    }
+    for credentials in desired.into_values() {
+        ensure_bloom_ws_listener(credentials);
+    }
+
+    for (chat_id, name, stage, expiry_label) in warnings {
+        log::info!(
+            "bloom_accounts.expiry chat_id={} account={} expired={}",
+            chat_id,
+            name,
+            stage == ExpiryStage::Expired
+        );
+        let title = match stage {
+            ExpiryStage::Soon => "⏳ *Bloom Token Expiring Soon*",
+            ExpiryStage::Expired => "⛔ *Bloom Token Expired*",
+        };
+        let message = format!(
+            "{}\n\n*Account:* `{}`\n*Token:* {}\n\n{}",
+            title,
+            escape_markdown(&name),
+            escape_markdown(&expiry_label),
+            escape_markdown(
+                "Send a fresh token from Bloom Accounts; the WebSocket reconnects automatically."
+            )
+        );
+        notify(chat_id, message).await;
+    }
+    Ok(())
+}
+
+pub async fn run_bloom_account_monitor(redis_client: RedisClient, redis_url: String) {
+    let mut interval = tokio::time::interval(MONITOR_INTERVAL);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        if let Err(e) = sync_bloom_accounts(&redis_client, &redis_url).await {
+            log::warn!("bloom_accounts.sync_failed err=\"{}\"", e);
+        }
+    }
+}
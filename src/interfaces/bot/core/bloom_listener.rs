@@ -1,13 +1,14 @@
 use crate::{
-    ACTIVE_BLOOM_SWAPS, BLOOM_WS_CONNECTION, BloomBuyAck, BloomWsConnectionStatus,
-    PENDING_BLOOM_INFO, PENDING_BLOOM_RESPONSES,
-    interfaces::bot::{update_bus, user::client::UserClientHandle},
+    PENDING_BLOOM_INFO,
+    infrastructure::blockchain::bloom::{SwapEvent, apply_mint_event},
+    interfaces::bot::{update_bus::UpdateArc, user::client::UserClientHandle},
 };
 use grammers_client::Update;
 use grammers_tl_types::enums::MessageEntity;
 use lazy_static::lazy_static;
 use regex::Regex;
-use std::time::Instant;
+use tokio::sync::broadcast::Receiver;
+use tokio::sync::broadcast::error::RecvError;
 
 lazy_static! {
     static ref MINT_REGEX: Regex = Regex::new(r"([1-9A-HJ-NP-Za-km-z]{32,44})").unwrap();
@@ -50,56 +51,27 @@ fn reconstruct_text_with_markdown(text: &str, entities: Option<&Vec<MessageEntit
 }
 
 fn deliver_telegram_ack(mint: &str, token_name: Option<String>, signature: Option<String>) {
-    let ws_status = {
-        let state = BLOOM_WS_CONNECTION.lock();
-        state.status
-    };
-    if ws_status == BloomWsConnectionStatus::Connected {
-        return;
-    }
-
-    let sender = {
-        let mut responses = PENDING_BLOOM_RESPONSES.lock();
-        responses.remove(mint)
-    };
-
-    let Some(sender) = sender else {
-        return;
-    };
-
-    let started_at = {
-        let mut swaps = ACTIVE_BLOOM_SWAPS.lock();
-        let mut selected: Option<(String, Instant)> = None;
-        for (id, tracker) in swaps.iter() {
-            if tracker.mint == mint {
-                selected = Some((id.clone(), tracker.started_at));
-                break;
-            }
-        }
-        if let Some((id, started)) = selected {
-            swaps.remove(&id);
-            Some(started)
-        } else {
-            log::debug!("bloom_listener: telegram ack tracker missing mint={}", mint);
-            None
-        }
-    };
-
-    let ack = BloomBuyAck {
-        pending_time: started_at.unwrap_or_else(Instant::now),
-        success_time: Instant::now(),
-        token_name,
-        signature,
-    };
-
-    if sender.send(ack).is_err() {
-        log::warn!("bloom_listener: telegram ack channel closed mint={}", mint);
-    } else {
-        log::info!("bloom_listener: telegram ack delivered mint={}", mint);
+    match apply_mint_event(
+        mint,
+        SwapEvent::Confirmed {
+            token_name,
+            signature,
+        },
+        "telegram",
+    ) {
+        Some(id) => log::info!(
+            "bloom_listener: telegram ack delivered id={} mint={}",
+            id,
+            mint
+        ),
+        None => log::debug!(
+            "bloom_listener: telegram ack without open swap mint={}",
+            mint
+        ),
     }
 }
 
-pub async fn run_bloom_listener(client: UserClientHandle) {
+pub async fn run_bloom_listener(client: UserClientHandle, mut rx: Receiver<UpdateArc>) {
     log::info!("bloom_listener: starting");
     let bloom_bot = match client.resolve_username("BloomSolana_bot").await {
         Ok(Some(bot)) => bot,
@@ -110,7 +82,6 @@ pub async fn run_bloom_listener(client: UserClientHandle) {
     };
     let bloom_bot_id = bloom_bot.id();
     log::info!("bloom_listener: resolved id={}", bloom_bot_id);
-    let mut rx = update_bus::subscribe();
 
     loop {
         match rx.recv().await {
@@ -169,8 +140,11 @@ pub async fn run_bloom_listener(client: UserClientHandle) {
                     }
                 }
             }
-            Err(e) => {
-                log::error!("bloom_listener: bus error {}", e);
+            Err(RecvError::Lagged(skipped)) => {
+                log::warn!("bloom_listener: bus lagged skipped={}", skipped);
+            }
+            Err(RecvError::Closed) => {
+                log::error!("bloom_listener: bus closed");
                 break;
             }
         }
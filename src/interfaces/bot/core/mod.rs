@@ -1,5 +1,8 @@
+pub mod bloom_accounts;
 pub mod bloom_listener;
 pub mod update_bus;
+pub mod update_health;
+pub mod update_router;
 pub mod user_logger;
 
 pub use user_logger::*;
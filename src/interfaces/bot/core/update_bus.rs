@@ -1,7 +1,5 @@
-use once_cell::sync::OnceCell;
 use std::sync::Arc;
 use std::time::Instant;
-use tokio::sync::broadcast;
 
 pub struct TimedUpdate {
     pub ts: Instant,
@@ -9,16 +7,3 @@ pub struct TimedUpdate {
 }
 
 pub type UpdateArc = Arc<TimedUpdate>;
-
-static UPDATE_TX: OnceCell<broadcast::Sender<UpdateArc>> = OnceCell::new();
-
-pub fn init_with_sender(tx: broadcast::Sender<UpdateArc>) {
-    let _ = UPDATE_TX.set(tx);
-}
-
-pub fn subscribe() -> broadcast::Receiver<UpdateArc> {
-    UPDATE_TX
-        .get()
-        .expect("UPDATE_TX not initialized")
-        .subscribe()
-}
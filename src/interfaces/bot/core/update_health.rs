@@ -0,0 +1,188 @@
+use crate::interfaces::bot::escape_markdown;
+use once_cell::sync::{Lazy, OnceCell};
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use teloxide::prelude::*;
+use teloxide::types::{ChatId, ParseMode};
+
+const LAG_ALERT_COOLDOWN: Duration = Duration::from_secs(600);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HubStatus {
+    Connected,
+    Reconnecting,
+}
+
+#[derive(Clone, Debug)]
+pub struct HubHealth {
+    pub account_id: i64,
+    pub status: HubStatus,
+    pub last_error: Option<String>,
+    pub reconnects: u32,
+    pub failed_attempts: u32,
+    pub last_update: Option<Instant>,
+}
+
+impl HubHealth {
+    fn new(account_id: i64) -> Self {
+        Self {
+            account_id,
+            status: HubStatus::Connected,
+            last_error: None,
+            reconnects: 0,
+            failed_attempts: 0,
+            last_update: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct WorkerLag {
+    pub chat_id: i64,
+    pub task_name: String,
+    pub dropped: u64,
+    pub last_alert: Option<Instant>,
+}
+
+static ADMIN_CHAT_ID: OnceCell<i64> = OnceCell::new();
+
+static HUB_HEALTH: Lazy<Mutex<HashMap<i64, HubHealth>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+static WORKER_LAG: Lazy<Mutex<HashMap<(i64, String), WorkerLag>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+pub fn set_admin_chat_id(chat_id: i64) {
+    let _ = ADMIN_CHAT_ID.set(chat_id);
+}
+
+fn notify_admin(message: String) {
+    let Some(chat_id) = ADMIN_CHAT_ID.get().copied() else {
+        return;
+    };
+    tokio::spawn(async move {
+        let bot = Bot::from_env();
+        if let Err(e) = bot
+            .send_message(ChatId(chat_id), message)
+            .parse_mode(ParseMode::MarkdownV2)
+            .await
+        {
+            log::warn!("update_health.notify_failed chat_id={} err={}", chat_id, e);
+        }
+    });
+}
+
+pub fn record_hub_started(account_id: i64) {
+    HUB_HEALTH
+        .lock()
+        .entry(account_id)
+        .or_insert_with(|| HubHealth::new(account_id))
+        .status = HubStatus::Connected;
+}
+
+pub fn record_hub_update(account_id: i64) {
+    if let Some(health) = HUB_HEALTH.lock().get_mut(&account_id) {
+        health.last_update = Some(Instant::now());
+    }
+}
+
+pub fn record_hub_error(account_id: i64, error: &str) {
+    let first_failure = {
+        let mut hubs = HUB_HEALTH.lock();
+        let health = hubs
+            .entry(account_id)
+            .or_insert_with(|| HubHealth::new(account_id));
+        let first_failure = health.status == HubStatus::Connected;
+        health.status = HubStatus::Reconnecting;
+        health.last_error = Some(error.to_string());
+        health.failed_attempts += 1;
+        first_failure
+    };
+    if first_failure {
+        log::warn!(
+            "update_health: hub disconnected account={} err={}",
+            account_id,
+            error
+        );
+        notify_admin(format!(
+            "⚠️ *Update Hub Disconnected*\n\n*Account:* `{}`\n*Error:* `{}`\n\n{}",
+            account_id,
+            escape_markdown(error),
+            escape_markdown("Reconnecting with backoff; task monitors stay subscribed.")
+        ));
+    }
+}
+
+pub fn record_hub_reconnected(account_id: i64) {
+    let (attempts, downtime_error) = {
+        let mut hubs = HUB_HEALTH.lock();
+        let health = hubs
+            .entry(account_id)
+            .or_insert_with(|| HubHealth::new(account_id));
+        let attempts = health.failed_attempts;
+        health.status = HubStatus::Connected;
+        health.reconnects += 1;
+        health.failed_attempts = 0;
+        (attempts, health.last_error.clone())
+    };
+    log::info!(
+        "update_health: hub reconnected account={} attempts={}",
+        account_id,
+        attempts
+    );
+    notify_admin(format!(
+        "✅ *Update Hub Reconnected*\n\n*Account:* `{}`\n*Failed Attempts:* {}\n*Last Error:* `{}`",
+        account_id,
+        attempts,
+        escape_markdown(downtime_error.as_deref().unwrap_or("-"))
+    ));
+}
+
+pub fn record_worker_lag(chat_id: i64, task_name: &str, skipped: u64) -> u64 {
+    let (dropped, alert) = {
+        let mut workers = WORKER_LAG.lock();
+        let lag = workers
+            .entry((chat_id, task_name.to_string()))
+            .or_insert_with(|| WorkerLag {
+                chat_id,
+                task_name: task_name.to_string(),
+                dropped: 0,
+                last_alert: None,
+            });
+        lag.dropped += skipped;
+        let alert = lag
+            .last_alert
+            .is_none_or(|at| at.elapsed() >= LAG_ALERT_COOLDOWN);
+        if alert {
+            lag.last_alert = Some(Instant::now());
+        }
+        (lag.dropped, alert)
+    };
+    if alert {
+        notify_admin(format!(
+            "🐢 *Task Monitor Lagging*\n\n*Task:* `{}`\n*User:* `{}`\n*Skipped:* {}\n*Dropped Total:* {}",
+            escape_markdown(task_name),
+            chat_id,
+            skipped,
+            dropped
+        ));
+    }
+    dropped
+}
+
+pub fn hub_health_snapshot() -> Vec<HubHealth> {
+    let mut hubs: Vec<HubHealth> = HUB_HEALTH.lock().values().cloned().collect();
+    hubs.sort_by_key(|health| health.account_id);
+    hubs
+}
+
+pub fn worker_lag_snapshot() -> Vec<WorkerLag> {
+    let mut workers: Vec<WorkerLag> = WORKER_LAG
+        .lock()
+        .values()
+        .filter(|lag| lag.dropped > 0)
+        .cloned()
+        .collect();
+    workers.sort_by(|a, b| b.dropped.cmp(&a.dropped));
+    workers
+}
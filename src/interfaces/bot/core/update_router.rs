@@ -0,0 +1,227 @@
+use crate::interfaces::bot::Task;
+use crate::interfaces::bot::core::update_bus::UpdateArc;
+use crate::interfaces::bot::core::update_health::record_worker_lag;
+use grammers_client::Update;
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::TrySendError;
+
+const TASK_ROUTE_CAPACITY: usize = 256;
+const HUB_QUEUE_BUCKETS_US: [u64; 12] = [
+    50, 100, 250, 500, 1_000, 2_500, 5_000, 10_000, 25_000, 50_000, 100_000, 250_000,
+];
+
+type TaskKey = (i64, String);
+
+struct TaskRoute {
+    account_id: i64,
+    chats: HashSet<i64>,
+    sender: mpsc::Sender<UpdateArc>,
+}
+
+#[derive(Default)]
+struct RouterState {
+    routes: HashMap<TaskKey, TaskRoute>,
+    index: HashMap<(i64, i64), Vec<TaskKey>>,
+}
+
+impl RouterState {
+    fn unindex(&mut self, key: &TaskKey) {
+        let Some(route) = self.routes.get(key) else {
+            return;
+        };
+        for chat in &route.chats {
+            let index_key = (route.account_id, *chat);
+            if let Some(keys) = self.index.get_mut(&index_key) {
+                keys.retain(|existing| existing != key);
+                if keys.is_empty() {
+                    self.index.remove(&index_key);
+                }
+            }
+        }
+    }
+
+    fn index(&mut self, key: &TaskKey) {
+        let Some(route) = self.routes.get(key) else {
+            return;
+        };
+        for chat in &route.chats {
+            self.index
+                .entry((route.account_id, *chat))
+                .or_default()
+                .push(key.clone());
+        }
+    }
+
+    fn remove(&mut self, key: &TaskKey) {
+        self.unindex(key);
+        self.routes.remove(key);
+    }
+}
+
+static ROUTER: Lazy<RwLock<RouterState>> = Lazy::new(|| RwLock::new(RouterState::default()));
+
+static HUB_QUEUE_COUNTS: Lazy<Vec<AtomicU64>> = Lazy::new(|| {
+    (0..=HUB_QUEUE_BUCKETS_US.len())
+        .map(|_| AtomicU64::new(0))
+        .collect()
+});
+static HUB_QUEUE_TOTAL_US: AtomicU64 = AtomicU64::new(0);
+static HUB_QUEUE_MAX_US: AtomicU64 = AtomicU64::new(0);
+
+fn routed_chats(task: &Task) -> HashSet<i64> {
+    task.enabled_channels().map(|channel| channel.id).collect()
+}
+
+pub fn update_chat_id(update: &Update) -> Option<i64> {
+    match update {
+        Update::NewMessage(msg) | Update::MessageEdited(msg) => Some(msg.chat().id()),
+        _ => None,
+    }
+}
+
+pub fn subscribe_task(account_id: i64, chat_id: i64, task: &Task) -> mpsc::Receiver<UpdateArc> {
+    let (sender, receiver) = mpsc::channel(TASK_ROUTE_CAPACITY);
+    let key = (chat_id, task.name.clone());
+    let mut router = ROUTER.write();
+    router.remove(&key);
+    router.routes.insert(
+        key.clone(),
+        TaskRoute {
+            account_id,
+            chats: routed_chats(task),
+            sender,
+        },
+    );
+    router.index(&key);
+    receiver
+}
+
+pub fn refresh_task_route(chat_id: i64, task: &Task) {
+    let key = (chat_id, task.name.clone());
+    let mut router = ROUTER.write();
+    if !router.routes.contains_key(&key) {
+        return;
+    }
+    if !task.active {
+        router.remove(&key);
+        return;
+    }
+    let chats = routed_chats(task);
+    if router
+        .routes
+        .get(&key)
+        .is_some_and(|route| route.chats == chats)
+    {
+        return;
+    }
+    router.unindex(&key);
+    if let Some(route) = router.routes.get_mut(&key) {
+        route.chats = chats;
+    }
+    router.index(&key);
+}
+
+pub fn unsubscribe_task(chat_id: i64, task_name: &str) {
+    ROUTER.write().remove(&(chat_id, task_name.to_string()));
+}
+
+pub fn route_update(account_id: i64, update: &UpdateArc) -> usize {
+    let Some(chat) = update_chat_id(&update.update) else {
+        return 0;
+    };
+    let mut delivered = 0;
+    let mut closed = Vec::new();
+    {
+        let router = ROUTER.read();
+        let Some(keys) = router.index.get(&(account_id, chat)) else {
+            return 0;
+        };
+        for key in keys {
+            let Some(route) = router.routes.get(key) else {
+                continue;
+            };
+            match route.sender.try_send(update.clone()) {
+                Ok(()) => delivered += 1,
+                Err(TrySendError::Full(_)) => {
+                    let dropped = record_worker_lag(key.0, &key.1, 1);
+                    log::warn!(
+                        "router: task queue full task={} user_chat={} dropped_total={}",
+                        key.1,
+                        key.0,
+                        dropped
+                    );
+                }
+                Err(TrySendError::Closed(_)) => closed.push(key.clone()),
+            }
+        }
+    }
+    if !closed.is_empty() {
+        let mut router = ROUTER.write();
+        for key in &closed {
+            router.remove(key);
+        }
+    }
+    delivered
+}
+
+pub fn active_route_count() -> usize {
+    ROUTER.read().routes.len()
+}
+
+pub fn record_hub_queue_us(hub_queue_us: u64) {
+    let bucket = HUB_QUEUE_BUCKETS_US
+        .iter()
+        .position(|bound| hub_queue_us <= *bound)
+        .unwrap_or(HUB_QUEUE_BUCKETS_US.len());
+    HUB_QUEUE_COUNTS[bucket].fetch_add(1, Ordering::Relaxed);
+    HUB_QUEUE_TOTAL_US.fetch_add(hub_queue_us, Ordering::Relaxed);
+    HUB_QUEUE_MAX_US.fetch_max(hub_queue_us, Ordering::Relaxed);
+}
+
+#[derive(Clone, Debug)]
+pub struct HubQueueStats {
+    pub samples: u64,
+    pub mean_us: u64,
+    pub max_us: u64,
+    pub p50_us: Option<u64>,
+    pub p95_us: Option<u64>,
+    pub p99_us: Option<u64>,
+}
+
+pub fn hub_queue_stats() -> HubQueueStats {
+    let counts: Vec<u64> = HUB_QUEUE_COUNTS
+        .iter()
+        .map(|count| count.load(Ordering::Relaxed))
+        .collect();
+    let samples: u64 = counts.iter().sum();
+    let max_us = HUB_QUEUE_MAX_US.load(Ordering::Relaxed);
+    let percentile = |quantile: f64| -> Option<u64> {
+        if samples == 0 {
+            return None;
+        }
+        let target = ((samples as f64) * quantile).ceil().max(1.0) as u64;
+        let mut seen = 0;
+        for (bucket, count) in counts.iter().enumerate() {
+            seen += count;
+            if seen >= target {
+                return Some(HUB_QUEUE_BUCKETS_US.get(bucket).copied().unwrap_or(max_us));
+            }
+        }
+        Some(max_us)
+    };
+    HubQueueStats {
+        samples,
+        mean_us: HUB_QUEUE_TOTAL_US
+            .load(Ordering::Relaxed)
+            .checked_div(samples)
+            .unwrap_or(0),
+        max_us,
+        p50_us: percentile(0.50),
+        p95_us: percentile(0.95),
+        p99_us: percentile(0.99),
+    }
+}
@@ -0,0 +1,304 @@
+use crate::infrastructure::database::{RedisResult, hdel_fields, hgetall_strings, hset_multi};
+use crate::interfaces::bot::data::types::Task;
+use anyhow::{Result, anyhow};
+use chrono::Utc;
+use once_cell::sync::{Lazy, OnceCell};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const ACCESS_USERS_KEY: &str = "access:users";
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    Viewer,
+    Trader,
+    Owner,
+}
+
+impl Role {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Role::Viewer => "Viewer",
+            Role::Trader => "Trader",
+            Role::Owner => "Owner",
+        }
+    }
+
+    pub fn parse(text: &str) -> Option<Role> {
+        match text.trim().to_lowercase().as_str() {
+            "viewer" | "view" => Some(Role::Viewer),
+            "trader" | "trade" => Some(Role::Trader),
+            "owner" => Some(Role::Owner),
+            _ => None,
+        }
+    }
+
+    pub fn allows(&self, required: Role) -> bool {
+        *self >= required
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AuthorizedUser {
+    pub user_id: u64,
+    pub role: Role,
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub added_by: Option<u64>,
+    pub added_at: i64,
+}
+
+static BOOTSTRAP_OWNER: OnceCell<u64> = OnceCell::new();
+
+static ROLE_CACHE: Lazy<RwLock<HashMap<u64, AuthorizedUser>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+pub fn set_bootstrap_owner(user_id: u64) {
+    let _ = BOOTSTRAP_OWNER.set(user_id);
+}
+
+pub fn bootstrap_owner() -> Option<u64> {
+    BOOTSTRAP_OWNER.get().copied()
+}
+
+pub fn role_for(user_id: Option<u64>) -> Option<Role> {
+    let user_id = user_id?;
+    if bootstrap_owner() == Some(user_id) {
+        return Some(Role::Owner);
+    }
+    ROLE_CACHE.read().get(&user_id).map(|user| user.role)
+}
+
+pub fn data_scope(user_id: Option<u64>, chat_id: i64) -> i64 {
+    let Some(user_id) = user_id else {
+        return chat_id;
+    };
+    if matches!(role_for(Some(user_id)), None | Some(Role::Owner)) {
+        return chat_id;
+    }
+    let added_by = ROLE_CACHE
+        .read()
+        .get(&user_id)
+        .and_then(|user| user.added_by);
+    let owners = owner_ids();
+    added_by
+        .filter(|owner| owners.contains(owner))
+        .or_else(|| owners.first().copied())
+        .map(|owner| owner as i64)
+        .unwrap_or(chat_id)
+}
+
+pub fn can_modify_task(role: Role, user_id: Option<u64>, task: &Task) -> bool {
+    match role {
+        Role::Owner => true,
+        Role::Trader => user_id.is_some() && task.created_by == user_id,
+        Role::Viewer => false,
+    }
+}
+
+pub fn callback_task_name<'a>(
+    data: &str,
+    names: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    names
+        .into_iter()
+        .filter(|name| !name.is_empty())
+        .filter(|name| {
+            data.match_indices(*name).any(|(index, _)| {
+                let before = data[..index].chars().next_back();
+                let after = data[index + name.len()..].chars().next();
+                matches!(before, Some('_' | ':')) && matches!(after, None | Some('_' | ':'))
+            })
+        })
+        .max_by_key(|name| name.len())
+}
+
+pub fn owner_ids() -> Vec<u64> {
+    let mut owners: Vec<u64> = ROLE_CACHE
+        .read()
+        .values()
+        .filter(|user| user.role == Role::Owner)
+        .map(|user| user.user_id)
+        .collect();
+    if let Some(owner) = bootstrap_owner() {
+        owners.retain(|user_id| *user_id != owner);
+        owners.insert(0, owner);
+    }
+    owners
+}
+
+pub async fn list_authorized_users() -> RedisResult<Vec<AuthorizedUser>> {
+    let entries = hgetall_strings(ACCESS_USERS_KEY).await?;
+    let mut users: Vec<AuthorizedUser> = entries
+        .values()
+        .filter_map(|raw| serde_json::from_str(raw).ok())
+        .collect();
+    users.sort_by(|a, b| b.role.cmp(&a.role).then(a.added_at.cmp(&b.added_at)));
+    Ok(users)
+}
+
+pub async fn load_access_control() -> Result<usize> {
+    if let Some(owner) = bootstrap_owner() {
+        let stored = list_authorized_users().await?;
+        if !stored.iter().any(|user| user.user_id == owner) {
+            save_authorized_user(&AuthorizedUser {
+                user_id: owner,
+                role: Role::Owner,
+                label: Some("Bootstrap owner".to_string()),
+                added_by: None,
+                added_at: Utc::now().timestamp(),
+            })
+            .await?;
+        }
+    }
+    let users = list_authorized_users().await?;
+    let mut cache = ROLE_CACHE.write();
+    cache.clear();
+    cache.extend(users.into_iter().map(|user| (user.user_id, user)));
+    Ok(cache.len())
+}
+
+async fn save_authorized_user(user: &AuthorizedUser) -> Result<()> {
+    hset_multi(
+        ACCESS_USERS_KEY,
+        &[(user.user_id.to_string(), serde_json::to_string(user)?)],
+    )
+    .await?;
+    ROLE_CACHE.write().insert(user.user_id, user.clone());
+    Ok(())
+}
+
+fn would_remove_last_owner(user_id: u64) -> bool {
+    role_for(Some(user_id)) == Some(Role::Owner) && owner_ids() == [user_id]
+}
+
+pub async fn grant_role(
+    user_id: u64,
+    role: Role,
+    label: Option<String>,
+    added_by: u64,
+) -> Result<AuthorizedUser> {
+    if bootstrap_owner() == Some(user_id) && role != Role::Owner {
+        return Err(anyhow!(
+            "User {} is the owner configured via ADMIN_TG_ID and cannot be demoted",
+            user_id
+        ));
+    }
+    if role != Role::Owner && would_remove_last_owner(user_id) {
+        return Err(anyhow!("Cannot demote the last owner"));
+    }
+    let existing = list_authorized_users()
+        .await?
+        .into_iter()
+        .find(|user| user.user_id == user_id);
+    let user = AuthorizedUser {
+        user_id,
+        role,
+        label: label.or_else(|| existing.as_ref().and_then(|user| user.label.clone())),
+        added_by: Some(added_by),
+        added_at: existing
+            .map(|user| user.added_at)
+            .unwrap_or_else(|| Utc::now().timestamp()),
+    };
+    save_authorized_user(&user).await?;
+    Ok(user)
+}
+
+pub async fn revoke_access(user_id: u64) -> Result<bool> {
+    if bootstrap_owner() == Some(user_id) {
+        return Err(anyhow!(
+            "User {} is the owner configured via ADMIN_TG_ID and cannot be removed",
+            user_id
+        ));
+    }
+    if would_remove_last_owner(user_id) {
+        return Err(anyhow!("Cannot remove the last owner"));
+    }
+    let removed = hdel_fields(ACCESS_USERS_KEY, &[user_id.to_string()]).await?;
+    ROLE_CACHE.write().remove(&user_id);
+    Ok(removed > 0)
+}
+
+pub fn required_role_for_callback(data: &str) -> Role {
+    const VIEW_ONLY_EXACT: [&str; 4] = ["rm", "r", "main_menu", "view_tasks"];
+    const VIEW_ONLY_PREFIXES: [&str; 5] = [
+        "task_detail_",
+        "task_settings_",
+        "task_paper_pnl_",
+        "task_autojoin_log_",
+        "bloom_accounts",
+    ];
+    const OWNER_ONLY_PREFIXES: [&str; 4] = [
+        "bloom_account_add",
+        "bloom_account_remove_",
+        "task_settings_wallets_",
+        "task_wallet_",
+    ];
+    if OWNER_ONLY_PREFIXES
+        .iter()
+        .any(|prefix| data.starts_with(prefix))
+    {
+        return Role::Owner;
+    }
+    if VIEW_ONLY_EXACT.contains(&data)
+        || VIEW_ONLY_PREFIXES
+            .iter()
+            .any(|prefix| data.starts_with(prefix))
+    {
+        Role::Viewer
+    } else {
+        Role::Trader
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wallet_and_bloom_account_management_is_owner_only() {
+        for data in [
+            "bloom_account_add",
+            "bloom_account_remove_main",
+            "task_settings_wallets_alpha",
+            "task_wallet_select:alpha:0",
+            "task_wallet_clear:alpha",
+        ] {
+            assert_eq!(required_role_for_callback(data), Role::Owner, "{}", data);
+        }
+        assert_eq!(
+            required_role_for_callback("task_settings_alpha"),
+            Role::Viewer
+        );
+        assert_eq!(
+            required_role_for_callback("task_toggle_alpha"),
+            Role::Trader
+        );
+    }
+
+    #[test]
+    fn resolves_the_task_named_in_callback_data() {
+        let names = ["alpha", "alpha_2", "beta"];
+        assert_eq!(
+            callback_task_name("task_toggle_alpha", names),
+            Some("alpha")
+        );
+        assert_eq!(
+            callback_task_name("task_toggle_alpha_2", names),
+            Some("alpha_2")
+        );
+        assert_eq!(
+            callback_task_name("task_chan_select_beta_-1001234", names),
+            Some("beta")
+        );
+        assert_eq!(
+            callback_task_name("task_safety_toggle:beta", names),
+            Some("beta")
+        );
+        assert_eq!(callback_task_name("task_toggle_gamma", names), None);
+        assert_eq!(callback_task_name("task_toggle_alphabet", names), None);
+    }
+}
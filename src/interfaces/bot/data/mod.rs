@@ -1,3 +1,4 @@
+pub mod access;
 pub mod logs;
 pub mod storage;
 pub mod types;
@@ -13,7 +13,12 @@ pub async fn get_user_data(
 ) -> RedisResult<Option<UserData>> {
     let key = get_user_key(chat_id);
     let data: Option<String> = con.get(key).await?;
-    let parsed = data.and_then(|s| serde_json::from_str(&s).ok());
+    let parsed = data
+        .and_then(|s| serde_json::from_str::<UserData>(&s).ok())
+        .map(|mut user_data| {
+            user_data.migrate_legacy_fields();
+            user_data
+        });
     if let Some(ref user_data) = parsed {
         state::sync_user_data(chat_id, user_data).await;
     }
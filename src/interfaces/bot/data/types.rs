@@ -1,3 +1,7 @@
+use crate::infrastructure::blockchain::bloom::account_key;
+use crate::infrastructure::blockchain::{BloomCredentials, BloomSwapOptions};
+use chrono::{DateTime, Datelike, TimeZone, Timelike, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 use solana_sdk::signer::{Signer, keypair::Keypair};
 
@@ -13,6 +17,325 @@ impl Default for Platform {
     }
 }
 
+pub const DEFAULT_DEDUP_TTL_SECS: u64 = 86_400;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DedupScope {
+    Task,
+    User,
+    Global,
+}
+
+impl Default for DedupScope {
+    fn default() -> Self {
+        DedupScope::Task
+    }
+}
+
+impl DedupScope {
+    pub fn label(&self) -> &'static str {
+        match self {
+            DedupScope::Task => "Task",
+            DedupScope::User => "User",
+            DedupScope::Global => "Global",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            DedupScope::Task => DedupScope::User,
+            DedupScope::User => DedupScope::Global,
+            DedupScope::Global => DedupScope::Task,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExecutionMode {
+    Bloom,
+    BloomWithNativeFallback,
+    Native,
+}
+
+impl Default for ExecutionMode {
+    fn default() -> Self {
+        ExecutionMode::Bloom
+    }
+}
+
+impl ExecutionMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExecutionMode::Bloom => "Bloom",
+            ExecutionMode::BloomWithNativeFallback => "Bloom + Native Fallback",
+            ExecutionMode::Native => "Native",
+        }
+    }
+
+    pub fn uses_native(&self) -> bool {
+        !matches!(self, ExecutionMode::Bloom)
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            ExecutionMode::Bloom => ExecutionMode::BloomWithNativeFallback,
+            ExecutionMode::BloomWithNativeFallback => ExecutionMode::Native,
+            ExecutionMode::Native => ExecutionMode::Bloom,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub delay_ms: u64,
+    pub fee_multiplier: f64,
+    pub slippage_step_percent: u32,
+    pub cutoff_secs: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 1,
+            delay_ms: 500,
+            fee_multiplier: 1.5,
+            slippage_step_percent: 5,
+            cutoff_secs: 30,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn is_enabled(&self) -> bool {
+        self.max_attempts > 1
+    }
+
+    pub fn summary(&self) -> String {
+        if !self.is_enabled() {
+            return "Off".to_string();
+        }
+        let cutoff = if self.cutoff_secs == 0 {
+            "no cutoff".to_string()
+        } else {
+            format!("cutoff {}s", self.cutoff_secs)
+        };
+        format!(
+            "{} attempts, {} ms delay, fee x{}, slippage +{}%, {}",
+            self.max_attempts,
+            self.delay_ms,
+            self.fee_multiplier,
+            self.slippage_step_percent,
+            cutoff
+        )
+    }
+}
+
+pub const WEEKDAY_LABELS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ScheduleWindow {
+    pub days: Vec<u8>,
+    pub start_minute: u16,
+    pub end_minute: u16,
+}
+
+impl ScheduleWindow {
+    pub fn contains(&self, weekday: u8, minute: u16) -> bool {
+        let yesterday = (weekday + 6) % 7;
+        if self.start_minute == self.end_minute {
+            self.days.contains(&weekday)
+        } else if self.start_minute < self.end_minute {
+            self.days.contains(&weekday) && minute >= self.start_minute && minute < self.end_minute
+        } else {
+            (self.days.contains(&weekday) && minute >= self.start_minute)
+                || (self.days.contains(&yesterday) && minute < self.end_minute)
+        }
+    }
+
+    pub fn summary(&self) -> String {
+        let days = if self.days.len() == 7 {
+            "Daily".to_string()
+        } else {
+            self.days
+                .iter()
+                .filter_map(|day| WEEKDAY_LABELS.get(*day as usize))
+                .copied()
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        format!(
+            "{} {:02}:{:02}-{:02}:{:02}",
+            days,
+            self.start_minute / 60,
+            self.start_minute % 60,
+            self.end_minute / 60,
+            self.end_minute % 60
+        )
+    }
+}
+
+fn default_schedule_timezone() -> String {
+    "UTC".to_string()
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TaskSchedule {
+    #[serde(default = "default_schedule_timezone")]
+    pub timezone: String,
+    #[serde(default)]
+    pub windows: Vec<ScheduleWindow>,
+    #[serde(default)]
+    pub active_until: Option<i64>,
+}
+
+impl Default for TaskSchedule {
+    fn default() -> Self {
+        TaskSchedule {
+            timezone: default_schedule_timezone(),
+            windows: Vec::new(),
+            active_until: None,
+        }
+    }
+}
+
+impl TaskSchedule {
+    pub fn tz(&self) -> Tz {
+        self.timezone.parse().unwrap_or(Tz::UTC)
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.active_until
+            .map(|until| now.timestamp() >= until)
+            .unwrap_or(false)
+    }
+
+    pub fn window_open(&self, now: DateTime<Utc>) -> Option<bool> {
+        if self.windows.is_empty() {
+            return None;
+        }
+        let local = now.with_timezone(&self.tz());
+        let weekday = local.weekday().num_days_from_monday() as u8;
+        let minute = (local.hour() * 60 + local.minute()) as u16;
+        Some(
+            self.windows
+                .iter()
+                .any(|window| window.contains(weekday, minute)),
+        )
+    }
+
+    pub fn summary(&self) -> String {
+        let mut parts = vec![self.timezone.clone()];
+        if !self.windows.is_empty() {
+            parts.push(
+                self.windows
+                    .iter()
+                    .map(ScheduleWindow::summary)
+                    .collect::<Vec<_>>()
+                    .join("; "),
+            );
+        }
+        if let Some(until) = self.active_until {
+            if let Some(local) = self.tz().timestamp_opt(until, 0).single() {
+                parts.push(format!("until {}", local.format("%Y-%m-%d %H:%M")));
+            }
+        }
+        parts.join(" | ")
+    }
+}
+
+fn default_dedup_ttl_secs() -> u64 {
+    DEFAULT_DEDUP_TTL_SECS
+}
+
+pub const DEFAULT_AUTO_JOIN_DAILY_LIMIT: u32 = 5;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AutoJoinMode {
+    Off,
+    ListenedUsers,
+    Always,
+}
+
+impl Default for AutoJoinMode {
+    fn default() -> Self {
+        AutoJoinMode::Off
+    }
+}
+
+impl AutoJoinMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            AutoJoinMode::Off => "Off",
+            AutoJoinMode::ListenedUsers => "Listened Users",
+            AutoJoinMode::Always => "Always",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            AutoJoinMode::Off => AutoJoinMode::ListenedUsers,
+            AutoJoinMode::ListenedUsers => AutoJoinMode::Always,
+            AutoJoinMode::Always => AutoJoinMode::Off,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AutoJoinSettings {
+    #[serde(default)]
+    pub mode: AutoJoinMode,
+    #[serde(default = "default_auto_join_daily_limit")]
+    pub daily_limit: u32,
+    #[serde(default)]
+    pub monitor_joined: bool,
+    #[serde(default)]
+    pub leave_after_secs: u64,
+}
+
+fn default_auto_join_daily_limit() -> u32 {
+    DEFAULT_AUTO_JOIN_DAILY_LIMIT
+}
+
+impl Default for AutoJoinSettings {
+    fn default() -> Self {
+        AutoJoinSettings {
+            mode: AutoJoinMode::Off,
+            daily_limit: DEFAULT_AUTO_JOIN_DAILY_LIMIT,
+            monitor_joined: false,
+            leave_after_secs: 0,
+        }
+    }
+}
+
+impl AutoJoinSettings {
+    pub fn summary(&self) -> String {
+        if self.mode == AutoJoinMode::Off {
+            return "Off".to_string();
+        }
+        let leave = if self.leave_after_secs == 0 {
+            "no auto-leave".to_string()
+        } else if self.leave_after_secs % 86_400 == 0 {
+            format!("leave after {}d", self.leave_after_secs / 86_400)
+        } else if self.leave_after_secs % 3_600 == 0 {
+            format!("leave after {}h", self.leave_after_secs / 3_600)
+        } else {
+            format!("leave after {}m", self.leave_after_secs.div_ceil(60))
+        };
+        format!(
+            "{}, {}/day, {}, {}",
+            self.mode.label(),
+            self.daily_limit,
+            if self.monitor_joined {
+                "monitor joined"
+            } else {
+                "join only"
+            },
+            leave
+        )
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Wallet {
     pub name: String,
@@ -27,6 +350,155 @@ pub struct BloomWalletInfo {
     pub label: Option<String>,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BloomAccount {
+    pub name: String,
+    pub auth_token: String,
+    #[serde(default)]
+    pub expires_at: Option<i64>,
+}
+
+impl BloomAccount {
+    pub fn credentials(&self, chat_id: i64) -> BloomCredentials {
+        BloomCredentials::new(
+            account_key(chat_id, &self.name),
+            &self.auth_token,
+            self.expires_at,
+        )
+    }
+
+    pub fn expiry_label(&self) -> String {
+        let Some(expires_at) = self.expires_at else {
+            return "no expiry set".to_string();
+        };
+        let remaining_secs = (expires_at - Utc::now().timestamp_millis()) / 1000;
+        let date = Utc
+            .timestamp_millis_opt(expires_at)
+            .single()
+            .map(|time| time.format("%Y-%m-%d %H:%M UTC").to_string())
+            .unwrap_or_else(|| expires_at.to_string());
+        if remaining_secs <= 0 {
+            return format!("expired {}", date);
+        }
+        let days = remaining_secs / 86_400;
+        let hours = (remaining_secs % 86_400) / 3600;
+        let minutes = (remaining_secs % 3600) / 60;
+        let remaining = if days > 0 {
+            format!("{}d {}h", days, hours)
+        } else if hours > 0 {
+            format!("{}h {}m", hours, minutes)
+        } else {
+            format!("{}m", minutes.max(1))
+        };
+        format!("expires in {} ({})", remaining, date)
+    }
+}
+
+fn default_channel_enabled() -> bool {
+    true
+}
+
+pub const GENERAL_FORUM_TOPIC_ID: i32 = 1;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ForumTopic {
+    pub id: i32,
+    #[serde(default)]
+    pub title: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TelegramChannel {
+    pub id: i64,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub users: Vec<i64>,
+    #[serde(default)]
+    pub usernames: Vec<String>,
+    #[serde(default)]
+    pub is_broadcast: bool,
+    #[serde(default = "default_channel_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub topics: Vec<ForumTopic>,
+}
+
+impl TelegramChannel {
+    pub fn new(id: i64, name: Option<String>) -> Self {
+        TelegramChannel {
+            id,
+            name,
+            users: Vec::new(),
+            usernames: Vec::new(),
+            is_broadcast: false,
+            enabled: true,
+            topics: Vec::new(),
+        }
+    }
+
+    pub fn display_name(&self) -> String {
+        self.name
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(|value| value.to_string())
+            .unwrap_or_else(|| self.id.to_string())
+    }
+
+    pub fn has_monitoring_target(&self) -> bool {
+        !self.users.is_empty() || !self.usernames.is_empty() || self.is_broadcast
+    }
+
+    pub fn accepts_sender(&self, sender_id: i64) -> bool {
+        self.users.is_empty() || self.users.contains(&sender_id)
+    }
+
+    pub fn accepts_topic(&self, topic_id: Option<i32>) -> bool {
+        let topic_id = topic_id.unwrap_or(GENERAL_FORUM_TOPIC_ID);
+        self.topics.is_empty() || self.topics.iter().any(|topic| topic.id == topic_id)
+    }
+
+    pub fn topics_label(&self) -> String {
+        if self.topics.is_empty() {
+            return "All topics".to_string();
+        }
+        self.topics
+            .iter()
+            .map(|topic| {
+                if topic.title.trim().is_empty() {
+                    format!("#{}", topic.id)
+                } else {
+                    topic.title.trim().to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    pub fn monitoring_label(&self) -> String {
+        if !self.usernames.is_empty() {
+            format!(
+                "{} users: {}",
+                self.usernames.len(),
+                self.usernames.join(", ")
+            )
+        } else if !self.users.is_empty() {
+            let users = self
+                .users
+                .iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("{} users: {}", self.users.len(), users)
+        } else if self.is_broadcast {
+            "Channel posts (no specific users)".to_string()
+        } else {
+            "Not Set".to_string()
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct UserConfig {
     pub slippage_percent: u32,
@@ -39,17 +511,24 @@ pub struct Task {
     pub name: String,
     #[serde(default)]
     pub platform: Platform,
+    #[serde(default)]
+    pub channels: Vec<TelegramChannel>,
+    #[serde(default, skip_serializing)]
     pub listen_channels: Vec<i64>,
+    #[serde(default, skip_serializing)]
     pub listen_channel_name: Option<String>,
+    #[serde(default, skip_serializing)]
     pub listen_users: Vec<i64>,
-    #[serde(default)]
+    #[serde(default, skip_serializing)]
     pub listen_usernames: Vec<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing)]
     pub telegram_channel_is_broadcast: bool,
     #[serde(default)]
     pub grammers_session_data: Option<String>,
     #[serde(default)]
     pub telegram_username: Option<String>,
+    #[serde(default)]
+    pub telegram_user_id: Option<i64>,
     pub discord_token: Option<String>,
     pub discord_channel_id: Option<String>,
     pub discord_username: Option<String>,
@@ -62,6 +541,52 @@ pub struct Task {
     pub inform_only: bool,
     #[serde(default)]
     pub bloom_wallet: Option<BloomWalletInfo>,
+    #[serde(default)]
+    pub safety_checks_enabled: bool,
+    #[serde(default)]
+    pub min_liquidity_sol: Option<f64>,
+    #[serde(default)]
+    pub max_liquidity_sol: Option<f64>,
+    #[serde(default)]
+    pub min_market_cap_sol: Option<f64>,
+    #[serde(default)]
+    pub max_market_cap_sol: Option<f64>,
+    #[serde(default)]
+    pub bloom_anti_mev: bool,
+    #[serde(default)]
+    pub bloom_auto_tip: bool,
+    #[serde(default)]
+    pub bloom_skip_if_bought: bool,
+    #[serde(default)]
+    pub bloom_hedged: bool,
+    #[serde(default)]
+    pub bloom_dev_sell: Option<String>,
+    #[serde(default)]
+    pub bloom_processor_tip_sol: Option<f64>,
+    #[serde(default)]
+    pub dedup_scope: DedupScope,
+    #[serde(default = "default_dedup_ttl_secs")]
+    pub dedup_ttl_secs: u64,
+    #[serde(default)]
+    pub execution_mode: ExecutionMode,
+    #[serde(default)]
+    pub dry_run: bool,
+    #[serde(default)]
+    pub retry_policy: RetryPolicy,
+    #[serde(default)]
+    pub schedule: Option<TaskSchedule>,
+    #[serde(default)]
+    pub bloom_account: Option<String>,
+    #[serde(default)]
+    pub process_edits: bool,
+    #[serde(default)]
+    pub include_reply_context: bool,
+    #[serde(default)]
+    pub match_forward_author: bool,
+    #[serde(default)]
+    pub auto_join: AutoJoinSettings,
+    #[serde(default)]
+    pub created_by: Option<u64>,
 }
 
 impl Task {
@@ -72,6 +597,91 @@ impl Task {
             .unwrap_or(false)
     }
 
+    pub fn migrate_legacy_channels(&mut self) {
+        let legacy_ids = std::mem::take(&mut self.listen_channels);
+        let mut first = TelegramChannel {
+            users: std::mem::take(&mut self.listen_users),
+            usernames: std::mem::take(&mut self.listen_usernames),
+            is_broadcast: std::mem::take(&mut self.telegram_channel_is_broadcast),
+            ..TelegramChannel::new(0, self.listen_channel_name.take())
+        };
+        for (index, id) in legacy_ids.into_iter().enumerate() {
+            if self.channels.iter().any(|channel| channel.id == id) {
+                continue;
+            }
+            let channel = if index == 0 {
+                first.id = id;
+                first.clone()
+            } else {
+                TelegramChannel::new(id, None)
+            };
+            self.channels.push(channel);
+        }
+    }
+
+    pub fn channel(&self, channel_id: i64) -> Option<&TelegramChannel> {
+        self.channels
+            .iter()
+            .find(|channel| channel.id == channel_id)
+    }
+
+    pub fn channel_mut(&mut self, channel_id: i64) -> Option<&mut TelegramChannel> {
+        self.channels
+            .iter_mut()
+            .find(|channel| channel.id == channel_id)
+    }
+
+    pub fn enabled_channels(&self) -> impl Iterator<Item = &TelegramChannel> {
+        self.channels.iter().filter(|channel| channel.enabled)
+    }
+
+    pub fn monitored_channel(&self, channel_id: i64) -> Option<&TelegramChannel> {
+        self.channel(channel_id).filter(|channel| channel.enabled)
+    }
+
+    pub fn has_market_filters(&self) -> bool {
+        self.min_liquidity_sol.is_some()
+            || self.max_liquidity_sol.is_some()
+            || self.min_market_cap_sol.is_some()
+            || self.max_market_cap_sol.is_some()
+    }
+
+    pub fn processor_tip_sol(&self) -> f64 {
+        self.bloom_processor_tip_sol
+            .unwrap_or(self.buy_priority_fee_sol)
+    }
+
+    pub fn bloom_swap_options(&self) -> BloomSwapOptions {
+        BloomSwapOptions {
+            processor_tip: self.processor_tip_sol(),
+            skip_if_bought: self.bloom_skip_if_bought,
+            anti_mev: self.bloom_anti_mev,
+            auto_tip: self.bloom_auto_tip,
+            dev_sell: self
+                .bloom_dev_sell
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(|value| value.to_string()),
+            hedged: self.bloom_hedged,
+        }
+    }
+
+    pub fn dedup_ttl_display(&self) -> String {
+        let secs = self.dedup_ttl_secs;
+        if secs == 0 {
+            "No expiry".to_string()
+        } else if secs % 86_400 == 0 {
+            format!("{}d", secs / 86_400)
+        } else if secs % 3_600 == 0 {
+            format!("{}h", secs / 3_600)
+        } else if secs % 60 == 0 {
+            format!("{}m", secs / 60)
+        } else {
+            format!("{}s", secs)
+        }
+    }
+
     pub fn telegram_username_display(&self) -> Option<&str> {
         self.telegram_username
             .as_deref()
@@ -86,12 +696,26 @@ pub struct UserData {
     pub default_wallet_index: usize,
     pub config: UserConfig,
     pub tasks: Vec<Task>,
+    #[serde(default)]
+    pub bloom_accounts: Vec<BloomAccount>,
 }
 
 impl UserData {
     pub fn get_default_wallet(&self) -> Option<&Wallet> {
         self.wallets.get(self.default_wallet_index)
     }
+
+    pub fn migrate_legacy_fields(&mut self) {
+        for task in self.tasks.iter_mut() {
+            task.migrate_legacy_channels();
+        }
+    }
+
+    pub fn bloom_account(&self, name: &str) -> Option<&BloomAccount> {
+        self.bloom_accounts
+            .iter()
+            .find(|account| account.name.eq_ignore_ascii_case(name))
+    }
 }
 
 pub fn create_new_wallet(name: String) -> Wallet {
@@ -0,0 +1,142 @@
+use chrono::{NaiveDateTime, Utc};
+use redis::Client as RedisClient;
+use teloxide::prelude::*;
+use teloxide::types::MessageId;
+
+use crate::infrastructure::blockchain::bloom::{account_key, stop_bloom_ws_listener};
+use crate::interfaces::bot::data::access::data_scope;
+use crate::interfaces::bot::{
+    BloomAccount, State, UserData, bloom_accounts_keyboard, generate_bloom_accounts_text,
+    get_user_data, save_user_data,
+};
+
+type MyDialogue = Dialogue<State, teloxide::dispatching::dialogue::InMemStorage<State>>;
+type HandlerResult = Result<(), Box<dyn std::error::Error + Send + Sync>>;
+
+pub fn parse_bloom_account(text: &str) -> Result<BloomAccount, String> {
+    let mut parts = text.split_whitespace();
+    let name = parts
+        .next()
+        .ok_or_else(|| "Send the account name followed by the auth token.".to_string())?;
+    let auth_token = parts
+        .next()
+        .ok_or_else(|| format!("Missing auth token for account '{}'.", name))?;
+    let expiry_text = parts.collect::<Vec<_>>().join(" ");
+    let expires_at = if expiry_text.is_empty() {
+        None
+    } else if let Ok(millis) = expiry_text.parse::<i64>() {
+        Some(millis)
+    } else {
+        let naive =
+            NaiveDateTime::parse_from_str(&expiry_text, "%Y-%m-%d %H:%M").map_err(|_| {
+                format!(
+                    "Invalid expiry '{}', expected YYYY-MM-DD HH:MM (UTC)",
+                    expiry_text
+                )
+            })?;
+        Some(naive.and_utc().timestamp_millis())
+    };
+    if expires_at.is_some_and(|expires_at| expires_at <= Utc::now().timestamp_millis()) {
+        return Err("The expiry time is already in the past.".to_string());
+    }
+    Ok(BloomAccount {
+        name: name.to_string(),
+        auth_token: auth_token.to_string(),
+        expires_at,
+    })
+}
+
+pub fn upsert_bloom_account(user_data: &mut UserData, account: BloomAccount) {
+    match user_data
+        .bloom_accounts
+        .iter_mut()
+        .find(|existing| existing.name.eq_ignore_ascii_case(&account.name))
+    {
+        Some(existing) => *existing = account,
+        None => user_data.bloom_accounts.push(account),
+    }
+}
+
+pub async fn render_bloom_accounts_view(
+    bot: &Bot,
+    chat_id: ChatId,
+    data_chat_id: i64,
+    message_id: MessageId,
+    accounts: &[BloomAccount],
+) -> HandlerResult {
+    bot.edit_message_text(
+        chat_id,
+        message_id,
+        generate_bloom_accounts_text(data_chat_id, accounts),
+    )
+    .parse_mode(teloxide::types::ParseMode::MarkdownV2)
+    .reply_markup(bloom_accounts_keyboard(accounts))
+    .await?;
+    Ok(())
+}
+
+pub async fn handle_bloom_callbacks(
+    q: CallbackQuery,
+    bot: Bot,
+    redis_client: RedisClient,
+    dialogue: MyDialogue,
+) -> HandlerResult {
+    let Some(message) = q.message.clone() else {
+        return Ok(());
+    };
+    let chat_id = message.chat.id;
+    let data_chat_id = data_scope(Some(q.from.id.0), chat_id.0);
+    let data = q.data.clone().unwrap_or_default();
+    let mut con = redis_client.get_multiplexed_async_connection().await?;
+    let Some(mut user_data) = get_user_data(&mut con, data_chat_id).await? else {
+        return Ok(());
+    };
+
+    if data == "bloom_accounts" {
+        render_bloom_accounts_view(
+            &bot,
+            chat_id,
+            data_chat_id,
+            message.id,
+            &user_data.bloom_accounts,
+        )
+        .await?;
+        dialogue.update(State::TasksMenu).await?;
+    } else if data == "bloom_account_add" {
+        let prompt = bot
+            .send_message(
+                chat_id,
+                "Send the Bloom account as: name auth_token [expiry]\nExpiry is optional, in UTC as YYYY-MM-DD HH:MM or epoch milliseconds (e.g., main eyJhbGci... 2026-12-31 23:59). Sending an existing name replaces its token and reconnects its WebSocket.",
+            )
+            .await?;
+        dialogue
+            .update(State::BloomReceiveAccount {
+                menu_message_id: message.id,
+                prompt_message_id: prompt.id,
+            })
+            .await?;
+    } else if let Some(name) = data.strip_prefix("bloom_account_remove_") {
+        let before = user_data.bloom_accounts.len();
+        user_data
+            .bloom_accounts
+            .retain(|account| account.name != name);
+        if user_data.bloom_accounts.len() != before {
+            for task in user_data.tasks.iter_mut() {
+                if task.bloom_account.as_deref() == Some(name) {
+                    task.bloom_account = None;
+                }
+            }
+            save_user_data(&mut con, data_chat_id, &user_data).await?;
+            stop_bloom_ws_listener(&account_key(data_chat_id, name));
+        }
+        render_bloom_accounts_view(
+            &bot,
+            chat_id,
+            data_chat_id,
+            message.id,
+            &user_data.bloom_accounts,
+        )
+        .await?;
+    }
+    Ok(())
+}
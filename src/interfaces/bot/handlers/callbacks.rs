@@ -1,17 +1,18 @@
-use grammers_client::Client as TelegramClient;
 use parking_lot::Mutex;
 use redis::Client as RedisClient;
 use std::sync::Arc;
 use teloxide::prelude::*;
-use tokio::sync::mpsc::Sender;
 
 use crate::application::pricing::SolPriceState;
 use crate::infrastructure::blockchain::RpcClients;
+use crate::interfaces::bot::data::access::{
+    Role, callback_task_name, can_modify_task, data_scope, required_role_for_callback,
+};
 use crate::interfaces::bot::handlers::tasks::get_tasks;
 use crate::interfaces::bot::user::client::UserClientHandle;
 use crate::interfaces::bot::{State, generate_tasks_text, tasks_menu_keyboard};
 
-use super::{tasks, trade};
+use super::{bloom, tasks, trade};
 
 type MyDialogue = Dialogue<State, teloxide::dispatching::dialogue::InMemStorage<State>>;
 type HandlerResult = Result<(), Box<dyn std::error::Error + Send + Sync>>;
@@ -24,7 +25,7 @@ pub async fn callback_handler(
     sol_price_state: SolPriceState,
     user_client_handle: Arc<Mutex<Option<UserClientHandle>>>,
     rpc_clients: RpcClients,
-    client_sender: Sender<TelegramClient>,
+    role: Role,
 ) -> HandlerResult {
     if let Some(message) = q.message.clone() {
         let chat_id = message.chat.id;
@@ -32,6 +33,50 @@ pub async fn callback_handler(
 
         log::info!("[CALLBACK] Data: '{}' from ChatID: {}", data, chat_id);
 
+        let required = required_role_for_callback(&data);
+        if !role.allows(required) {
+            log::warn!(
+                "[CALLBACK] Denied '{}' for user {} with role {}",
+                data,
+                q.from.id.0,
+                role.label()
+            );
+            bot.answer_callback_query(q.id)
+                .text(format!(
+                    "🚫 Requires the {} role; you are a {}.",
+                    required.label(),
+                    role.label()
+                ))
+                .show_alert(true)
+                .await?;
+            return Ok(());
+        }
+
+        if role == Role::Trader && required == Role::Trader && data.starts_with("task_") {
+            let tasks = get_tasks(
+                redis_client.clone(),
+                data_scope(Some(q.from.id.0), chat_id.0),
+            )
+            .await;
+            let denied = callback_task_name(&data, tasks.iter().map(|task| task.name.as_str()))
+                .and_then(|name| tasks.iter().find(|task| task.name == name))
+                .filter(|task| !can_modify_task(role, Some(q.from.id.0), task));
+            if let Some(task) = denied {
+                log::warn!(
+                    "[CALLBACK] Denied '{}' for trader {}: task '{}' belongs to {:?}",
+                    data,
+                    q.from.id.0,
+                    task.name,
+                    task.created_by
+                );
+                bot.answer_callback_query(q.id)
+                    .text("🚫 Traders can only change tasks they created.")
+                    .show_alert(true)
+                    .await?;
+                return Ok(());
+            }
+        }
+
         if data == "rm" {
             match bot.delete_message(chat_id, message.id).await {
                 Ok(_) => {}
@@ -52,9 +97,10 @@ pub async fn callback_handler(
                 sol_price_state.clone(),
                 user_client_handle.clone(),
                 rpc_clients.clone(),
-                client_sender.clone(),
             )
             .await?;
+        } else if data.starts_with("bloom_") {
+            bloom::handle_bloom_callbacks(q.clone(), bot.clone(), redis_client, dialogue).await?;
         } else if trade_actions.iter().any(|&action| data.starts_with(action)) {
             trade::handle_trade_callback(
                 q.clone(),
@@ -64,12 +110,12 @@ pub async fn callback_handler(
                 sol_price_state,
                 user_client_handle,
                 rpc_clients,
-                client_sender,
             )
             .await?;
         } else if data == "main_menu" {
-            let tasks_text = generate_tasks_text(redis_client.clone(), chat_id.0).await;
-            let tasks = get_tasks(redis_client.clone(), chat_id.0).await;
+            let data_chat_id = data_scope(Some(q.from.id.0), chat_id.0);
+            let tasks_text = generate_tasks_text(redis_client.clone(), data_chat_id).await;
+            let tasks = get_tasks(redis_client.clone(), data_chat_id).await;
             bot.edit_message_text(chat_id, message.id, tasks_text)
                 .parse_mode(teloxide::types::ParseMode::MarkdownV2)
                 .reply_markup(tasks_menu_keyboard(&tasks))
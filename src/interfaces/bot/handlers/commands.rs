@@ -0,0 +1,824 @@
+use crate::application::health::worker::{WarmerState, WarmupStatus};
+use crate::application::indexer::indexer_mint_log_counters;
+use crate::infrastructure::blockchain::{SwapOutcome, bloom_buy, subscribe_swap};
+use crate::infrastructure::database::{hdel_fields, hgetall_strings, hset_multi};
+use crate::interfaces::bot::core::update_health::{HubStatus, hub_health_snapshot};
+use crate::interfaces::bot::data::access::{
+    Role, can_modify_task, data_scope, grant_role, list_authorized_users, revoke_access,
+};
+use crate::interfaces::bot::handlers::start::Command;
+use crate::interfaces::bot::handlers::tasks::activation_requirement_error;
+use crate::interfaces::bot::handlers::text::parse_mint_from_text_robust;
+use crate::interfaces::bot::tasks::native::{
+    build_native_buy_failed_message, build_native_buy_message, run_native_buy,
+};
+use crate::interfaces::bot::tasks::paper::{
+    build_paper_fill_failed_message, build_paper_fill_message, build_paper_pnl_report,
+    load_positions, record_paper_fill,
+};
+use crate::interfaces::bot::tasks::resume::start_monitor;
+use crate::interfaces::bot::tasks::retry::build_bloom_failure_message;
+use crate::interfaces::bot::tasks::{
+    append_task_log, resolve_bloom_credentials, resolve_task_wallet, subscribe_task_logs,
+};
+use crate::interfaces::bot::{
+    ExecutionMode, Task, UserData, escape_markdown, get_user_data, save_user_data,
+};
+use crate::{BLOOM_WS_CONNECTION, BloomWsConnectionStatus};
+use anyhow::{Result, anyhow};
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use redis::Client as RedisClient;
+use solana_account_decoder::UiAccountData;
+use solana_rpc_client_api::request::TokenAccountsFilter;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::VecDeque;
+use std::str::FromStr;
+use std::time::Instant;
+use teloxide::prelude::*;
+use teloxide::types::ParseMode;
+use teloxide::utils::command::BotCommands;
+use tokio::time::{Duration, MissedTickBehavior};
+
+type HandlerResult = Result<(), Box<dyn std::error::Error + Send + Sync>>;
+
+const DEFAULT_LOG_LINES: usize = 20;
+const MAX_LOG_LINES: usize = 50;
+const LOG_REPLY_CHAR_BUDGET: usize = 3_500;
+const MANUAL_BUY_ACK_TIMEOUT: Duration = Duration::from_secs(20);
+const INDEXER_SAMPLE_INTERVAL: Duration = Duration::from_secs(15);
+const INDEXER_RATE_WINDOW: Duration = Duration::from_secs(5 * 60);
+const MAX_LIVE_HOLDINGS: usize = 15;
+
+static INDEXER_SAMPLES: Lazy<Mutex<VecDeque<(Instant, u64)>>> =
+    Lazy::new(|| Mutex::new(VecDeque::new()));
+
+fn paused_key(chat_id: i64) -> String {
+    format!("cmd:paused:{}", chat_id)
+}
+
+fn find_task<'a>(tasks: &'a [Task], name: &str) -> Option<&'a Task> {
+    tasks.iter().find(|task| task.name == name).or_else(|| {
+        tasks
+            .iter()
+            .find(|task| task.name.eq_ignore_ascii_case(name))
+    })
+}
+
+fn find_task_name(tasks: &[Task], name: &str) -> Option<String> {
+    find_task(tasks, name).map(|task| task.name.clone())
+}
+
+fn unknown_task(name: &str) -> String {
+    format!(
+        "❌ {}",
+        escape_markdown(&format!("No task named '{}'.", name))
+    )
+}
+
+fn not_your_task(name: &str) -> String {
+    format!(
+        "🚫 {}",
+        escape_markdown(&format!(
+            "Task '{}' was created by someone else; traders can only change their own tasks.",
+            name
+        ))
+    )
+}
+
+fn escape_code(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('`', "\\`")
+}
+
+pub async fn register_bot_commands(bot: &Bot) {
+    if let Err(e) = bot.set_my_commands(Command::bot_commands()).await {
+        log::warn!("commands.register_failed err={}", e);
+    }
+}
+
+pub fn required_role_for_command(cmd: &Command) -> Role {
+    match cmd {
+        Command::Start
+        | Command::Help
+        | Command::Status
+        | Command::Positions(_)
+        | Command::Logs(_) => Role::Viewer,
+        Command::Pause(_) | Command::Resume(_) | Command::Buy(_) => Role::Trader,
+        Command::Users | Command::AddUser(_) | Command::RemoveUser(_) => Role::Owner,
+    }
+}
+
+pub async fn handle_command(
+    bot: Bot,
+    msg: Message,
+    cmd: Command,
+    role: Role,
+    redis_client: RedisClient,
+    warmer_state: WarmerState,
+) -> HandlerResult {
+    let user_id = msg.from().map(|user| user.id.0);
+    let chat_id = data_scope(user_id, msg.chat.id.0);
+    let reply = match cmd {
+        Command::Start => return Ok(()),
+        Command::Help => escape_markdown(&Command::descriptions().to_string()),
+        Command::Status => build_status(&redis_client, chat_id, &warmer_state).await?,
+        Command::Pause(target) => {
+            pause_tasks(&redis_client, chat_id, target.trim(), role, user_id).await?
+        }
+        Command::Resume(target) => {
+            resume_tasks(&redis_client, chat_id, target.trim(), role, user_id).await?
+        }
+        Command::Buy(args) => {
+            bot.send_message(msg.chat.id, "⏳ Submitting manual buy...")
+                .await?;
+            manual_buy(&redis_client, chat_id, args.trim(), role, user_id).await?
+        }
+        Command::Positions(target) => {
+            build_positions(&redis_client, chat_id, target.trim()).await?
+        }
+        Command::Logs(args) => build_logs(&redis_client, chat_id, args.trim()).await?,
+        Command::Users => build_users(role).await?,
+        Command::AddUser(args) => {
+            let owner_id = msg.from().map(|user| user.id.0).unwrap_or_default();
+            add_user(owner_id, args.trim()).await
+        }
+        Command::RemoveUser(args) => remove_user(args.trim()).await,
+    };
+    bot.send_message(msg.chat.id, reply)
+        .parse_mode(ParseMode::MarkdownV2)
+        .await?;
+    Ok(())
+}
+
+async fn load_user_data(redis_client: &RedisClient, chat_id: i64) -> Result<Option<UserData>> {
+    let mut con = redis_client.get_multiplexed_async_connection().await?;
+    Ok(get_user_data(&mut con, chat_id).await?)
+}
+
+pub async fn run_indexer_rate_sampler() {
+    let mut interval = tokio::time::interval(INDEXER_SAMPLE_INTERVAL);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        let now = Instant::now();
+        let total = indexer_mint_log_counters().total;
+        let mut samples = INDEXER_SAMPLES.lock();
+        samples.push_back((now, total));
+        while samples
+            .front()
+            .is_some_and(|(at, _)| now.duration_since(*at) > INDEXER_RATE_WINDOW)
+        {
+            samples.pop_front();
+        }
+    }
+}
+
+fn indexer_rate_line() -> String {
+    let total = indexer_mint_log_counters().total;
+    let samples = INDEXER_SAMPLES.lock();
+    match (samples.front(), samples.back()) {
+        (Some((first_at, first_total)), Some((last_at, last_total)))
+            if last_at.duration_since(*first_at) >= INDEXER_SAMPLE_INTERVAL * 4 =>
+        {
+            let minutes = last_at.duration_since(*first_at).as_secs_f64() / 60.0;
+            let rate = last_total.saturating_sub(*first_total) as f64 / minutes;
+            format!(
+                "{} mints total, {:.1}/min over the last {:.0} min",
+                total, rate, minutes
+            )
+        }
+        _ => format!("{} mints total (rate available after warm-up)", total),
+    }
+}
+
+async fn build_status(
+    redis_client: &RedisClient,
+    chat_id: i64,
+    warmer_state: &WarmerState,
+) -> Result<String> {
+    let (bloom_status, bloom_message) = {
+        let state = BLOOM_WS_CONNECTION.lock();
+        (state.status, state.message.trim().to_string())
+    };
+    let bloom_label = match bloom_status {
+        BloomWsConnectionStatus::Connected => "🟢 Connected",
+        BloomWsConnectionStatus::Connecting => "🟡 Connecting",
+        BloomWsConnectionStatus::Disconnected => "🔴 Disconnected",
+        BloomWsConnectionStatus::Unavailable => "⚪ Unavailable",
+    };
+    let mut bloom_line = format!("🌸 *Bloom WS:* {}", escape_markdown(bloom_label));
+    if !bloom_message.is_empty() && bloom_status != BloomWsConnectionStatus::Connected {
+        bloom_line.push_str(&format!(" \\- {}", escape_markdown(&bloom_message)));
+    }
+
+    let (warm_ok, warm_failed, warm_pending, best_latency) = {
+        let results = warmer_state.lock();
+        let mut ok = 0;
+        let mut failed = 0;
+        let mut pending = 0;
+        let mut best: Option<f64> = None;
+        for result in results.iter() {
+            match result.status {
+                WarmupStatus::Success => {
+                    ok += 1;
+                    if let Some(latency) = result.latency_ms {
+                        best = Some(best.map_or(latency, |current| current.min(latency)));
+                    }
+                }
+                WarmupStatus::Failed => failed += 1,
+                WarmupStatus::Pending => pending += 1,
+            }
+        }
+        (ok, failed, pending, best)
+    };
+    let warmer_line = format!(
+        "🔥 *Warmer:* {}",
+        escape_markdown(&format!(
+            "{} ok, {} failed, {} pending{}",
+            warm_ok,
+            warm_failed,
+            warm_pending,
+            best_latency
+                .map(|latency| format!(", best {:.0} ms", latency))
+                .unwrap_or_default()
+        ))
+    );
+
+    let indexer_line = format!("📈 *Indexer:* {}", escape_markdown(&indexer_rate_line()));
+
+    let hubs = hub_health_snapshot();
+    let hub_line = if hubs.is_empty() {
+        format!(
+            "👤 *Telegram Sessions:* {}",
+            escape_markdown("none running")
+        )
+    } else {
+        let reconnecting = hubs
+            .iter()
+            .filter(|hub| hub.status == HubStatus::Reconnecting)
+            .count();
+        format!(
+            "👤 *Telegram Sessions:* {}",
+            escape_markdown(&format!(
+                "{} running, {} reconnecting",
+                hubs.len(),
+                reconnecting
+            ))
+        )
+    };
+
+    let tasks = load_user_data(redis_client, chat_id)
+        .await?
+        .map(|user_data| user_data.tasks)
+        .unwrap_or_default();
+    let active: Vec<&Task> = tasks.iter().filter(|task| task.active).collect();
+    let mut task_line = format!(
+        "📋 *Tasks:* {}",
+        escape_markdown(&format!("{} active of {}", active.len(), tasks.len()))
+    );
+    for task in active {
+        task_line.push_str(&format!("\n• `{}`", escape_markdown(&task.name)));
+    }
+
+    Ok(format!(
+        "📊 *Status*\n\n{}\n{}\n{}\n{}\n\n{}",
+        bloom_line, warmer_line, indexer_line, hub_line, task_line
+    ))
+}
+
+async fn pause_tasks(
+    redis_client: &RedisClient,
+    chat_id: i64,
+    target: &str,
+    role: Role,
+    user_id: Option<u64>,
+) -> Result<String> {
+    let mut con = redis_client.get_multiplexed_async_connection().await?;
+    let Some(mut user_data) = get_user_data(&mut con, chat_id).await? else {
+        return Ok(escape_markdown("No tasks yet. Use /start to create one."));
+    };
+    let names: Vec<String> = if target.is_empty() {
+        user_data
+            .tasks
+            .iter()
+            .filter(|task| task.active && can_modify_task(role, user_id, task))
+            .map(|task| task.name.clone())
+            .collect()
+    } else {
+        match find_task(&user_data.tasks, target) {
+            Some(task) if !can_modify_task(role, user_id, task) => {
+                return Ok(not_your_task(&task.name));
+            }
+            Some(task) if task.active => vec![task.name.clone()],
+            Some(task) => {
+                return Ok(format!(
+                    "⏸️ `{}` {}",
+                    escape_markdown(&task.name),
+                    escape_markdown("is not running.")
+                ));
+            }
+            None => return Ok(unknown_task(target)),
+        }
+    };
+    if names.is_empty() {
+        return Ok(escape_markdown("No active tasks to pause."));
+    }
+
+    for task in user_data
+        .tasks
+        .iter_mut()
+        .filter(|task| names.contains(&task.name))
+    {
+        task.active = false;
+    }
+    save_user_data(&mut con, chat_id, &user_data).await?;
+    let paused_at = Utc::now().timestamp().to_string();
+    hset_multi(
+        &paused_key(chat_id),
+        &names
+            .iter()
+            .map(|name| (name.clone(), paused_at.clone()))
+            .collect::<Vec<_>>(),
+    )
+    .await?;
+
+    let mut lines = vec![format!("⏸️ *Paused {} task\\(s\\)*", names.len())];
+    for name in &names {
+        append_task_log(chat_id, name, "Task paused via /pause");
+        lines.push(format!("• `{}`", escape_markdown(name)));
+    }
+    Ok(lines.join("\n"))
+}
+
+async fn resume_tasks(
+    redis_client: &RedisClient,
+    chat_id: i64,
+    target: &str,
+    role: Role,
+    user_id: Option<u64>,
+) -> Result<String> {
+    let mut con = redis_client.get_multiplexed_async_connection().await?;
+    let Some(mut user_data) = get_user_data(&mut con, chat_id).await? else {
+        return Ok(escape_markdown("No tasks yet. Use /start to create one."));
+    };
+    let names: Vec<String> = if target.is_empty() {
+        let paused = hgetall_strings(&paused_key(chat_id)).await?;
+        user_data
+            .tasks
+            .iter()
+            .filter(|task| {
+                !task.active
+                    && paused.contains_key(&task.name)
+                    && can_modify_task(role, user_id, task)
+            })
+            .map(|task| task.name.clone())
+            .collect()
+    } else {
+        match find_task(&user_data.tasks, target) {
+            Some(task) if !can_modify_task(role, user_id, task) => {
+                return Ok(not_your_task(&task.name));
+            }
+            Some(task) if !task.active => vec![task.name.clone()],
+            Some(task) => {
+                return Ok(format!(
+                    "▶️ `{}` {}",
+                    escape_markdown(&task.name),
+                    escape_markdown("is already running.")
+                ));
+            }
+            None => return Ok(unknown_task(target)),
+        }
+    };
+    if names.is_empty() {
+        return Ok(escape_markdown(
+            "No tasks paused via /pause. Use /resume <task> to start a specific task.",
+        ));
+    }
+
+    let mut resumed = Vec::new();
+    let mut blocked = Vec::new();
+    for task in user_data
+        .tasks
+        .iter_mut()
+        .filter(|task| names.contains(&task.name))
+    {
+        match activation_requirement_error(task) {
+            Some(error) => blocked.push((
+                task.name.clone(),
+                error.trim_start_matches("❌ ").to_string(),
+            )),
+            None => {
+                task.active = true;
+                resumed.push(task.clone());
+            }
+        }
+    }
+    if !resumed.is_empty() {
+        save_user_data(&mut con, chat_id, &user_data).await?;
+    }
+    hdel_fields(&paused_key(chat_id), &names).await?;
+
+    let mut lines = vec![format!("▶️ *Resumed {} task\\(s\\)*", resumed.len())];
+    for task in &resumed {
+        append_task_log(chat_id, &task.name, "Task resumed via /resume");
+        lines.push(format!("• `{}`", escape_markdown(&task.name)));
+    }
+    for (name, reason) in &blocked {
+        lines.push(format!(
+            "❌ `{}` \\- {}",
+            escape_markdown(name),
+            escape_markdown(reason)
+        ));
+    }
+    for task in resumed {
+        start_monitor(task, chat_id).await;
+    }
+    Ok(lines.join("\n"))
+}
+
+async fn manual_buy(
+    redis_client: &RedisClient,
+    chat_id: i64,
+    args: &str,
+    role: Role,
+    user_id: Option<u64>,
+) -> Result<String> {
+    let mut parts = args.split_whitespace();
+    let Some(mint) = parts.next().and_then(parse_mint_from_text_robust) else {
+        return Ok(escape_markdown(
+            "Usage: /buy <mint> [amount_sol] [task]. Uses the task's wallet and buy settings.",
+        ));
+    };
+    let mut amount = None;
+    let mut task_name = Vec::new();
+    for part in parts {
+        match part.parse::<f64>() {
+            Ok(value) if amount.is_none() && task_name.is_empty() => amount = Some(value),
+            _ => task_name.push(part),
+        }
+    }
+    if amount.is_some_and(|value| !value.is_finite() || value <= 0.0) {
+        return Ok(escape_markdown(
+            "The buy amount must be greater than 0 SOL.",
+        ));
+    }
+    let task_name = task_name.join(" ");
+
+    let Some(user_data) = load_user_data(redis_client, chat_id).await? else {
+        return Ok(escape_markdown("No tasks yet. Use /start to create one."));
+    };
+    let task = if task_name.is_empty() {
+        let mut permitted = user_data
+            .tasks
+            .iter()
+            .filter(|task| can_modify_task(role, user_id, task));
+        let first = permitted.clone().next();
+        permitted.find(|task| task.active).or(first)
+    } else {
+        match find_task(&user_data.tasks, &task_name) {
+            Some(task) if !can_modify_task(role, user_id, task) => {
+                return Ok(not_your_task(&task.name));
+            }
+            task => task,
+        }
+    };
+    let Some(mut task) = task.cloned() else {
+        return Ok(if task_name.is_empty() {
+            escape_markdown(
+                "Create a task first; manual buys use the wallet and settings of a task you can change.",
+            )
+        } else {
+            unknown_task(&task_name)
+        });
+    };
+    if let Some(amount) = amount {
+        task.buy_amount_sol = amount;
+    }
+
+    append_task_log(
+        chat_id,
+        &task.name,
+        format!(
+            "Manual buy requested via /buy for mint {} ({} SOL)",
+            mint, task.buy_amount_sol
+        ),
+    );
+    let started = Instant::now();
+    if task.dry_run {
+        return Ok(match record_paper_fill(chat_id, &task, &mint).await {
+            Ok(position) => build_paper_fill_message(&position, started.elapsed().as_millis()),
+            Err(e) => build_paper_fill_failed_message(&mint, &e.to_string()),
+        });
+    }
+    let bloom_error = match task.execution_mode {
+        ExecutionMode::Native => None,
+        ExecutionMode::Bloom | ExecutionMode::BloomWithNativeFallback => {
+            match manual_bloom_buy(chat_id, &task, &user_data, &mint).await {
+                Ok(reply) => return Ok(reply),
+                Err(e) if task.execution_mode == ExecutionMode::Bloom => {
+                    return Ok(format!(
+                        "❌ *Manual Buy Failed*\n\n*Token:* `{}`\n*Error:* `{}`",
+                        escape_markdown(&mint),
+                        escape_markdown(&e.to_string())
+                    ));
+                }
+                Err(e) => Some(e.to_string()),
+            }
+        }
+    };
+
+    let native_start = Instant::now();
+    let mut reply = match run_native_buy(&task, &user_data, &mint).await {
+        Ok(result) => build_native_buy_message(
+            &mint,
+            task.buy_amount_sol,
+            task.buy_priority_fee_sol,
+            native_start.elapsed().as_millis(),
+            &result,
+        ),
+        Err(e) => build_native_buy_failed_message(&mint, &e.to_string()),
+    };
+    if let Some(error) = bloom_error {
+        reply.push_str(&format!(
+            "\n\n{}",
+            escape_markdown(&format!("Bloom failed first: {}", error))
+        ));
+    }
+    Ok(reply)
+}
+
+async fn manual_bloom_buy(
+    chat_id: i64,
+    task: &Task,
+    user_data: &UserData,
+    mint: &str,
+) -> Result<String> {
+    let (wallet_address, wallet_label) = resolve_task_wallet(task, user_data)
+        .ok_or_else(|| anyhow!("Assign a Bloom wallet to task '{}' first", task.name))?;
+    let credentials = resolve_bloom_credentials(chat_id, task, user_data)
+        .ok_or_else(|| anyhow!("No Bloom account configured; add one from Bloom Accounts"))?;
+    let swap_id = bloom_buy(
+        &credentials,
+        mint,
+        task.buy_amount_sol,
+        task.buy_slippage_percent,
+        task.buy_priority_fee_sol,
+        &wallet_address,
+        &wallet_label,
+        &task.bloom_swap_options(),
+    )
+    .await?;
+    let rx = subscribe_swap(&swap_id).ok_or_else(|| anyhow!("Bloom swap was not registered"))?;
+    match tokio::time::timeout(MANUAL_BUY_ACK_TIMEOUT, rx).await {
+        Ok(Ok(SwapOutcome::Confirmed(ack))) => {
+            append_task_log(
+                chat_id,
+                &task.name,
+                format!("Manual Bloom buy confirmed for mint {}", mint),
+            );
+            Ok(format!(
+                "✅ *Manual Buy Confirmed*\n\n*Token:* `{}`{}\n*Amount:* {} SOL\n*Wallet:* {}\n*Signature:* `{}`",
+                escape_markdown(mint),
+                ack.token_name
+                    .map(|name| format!(" \\({}\\)", escape_markdown(&name)))
+                    .unwrap_or_default(),
+                escape_markdown(&task.buy_amount_sol.to_string()),
+                escape_markdown(&wallet_label),
+                escape_markdown(ack.signature.as_deref().unwrap_or("-"))
+            ))
+        }
+        Ok(Ok(SwapOutcome::Failed(failure))) => {
+            append_task_log(
+                chat_id,
+                &task.name,
+                format!(
+                    "Manual Bloom buy failed for mint {}: {}",
+                    mint,
+                    failure.describe()
+                ),
+            );
+            Ok(build_bloom_failure_message(mint, &failure, ""))
+        }
+        Ok(Ok(SwapOutcome::Expired)) | Err(_) => Ok(format!(
+            "⌛ *Manual Buy Pending*\n\n*Token:* `{}`\n{}",
+            escape_markdown(mint),
+            escape_markdown("Bloom accepted the order but did not confirm it within 20 seconds.")
+        )),
+        Ok(Err(_)) => Err(anyhow!("Bloom confirmation channel closed")),
+    }
+}
+
+async fn wallet_holdings(owner: &str) -> Result<Vec<(String, f64)>> {
+    let owner = Pubkey::from_str(owner).map_err(|e| anyhow!("Invalid wallet {}: {}", owner, e))?;
+    let mut holdings = Vec::new();
+    for program in [spl_token::id(), spl_token_2022::id()] {
+        let accounts = crate::RPC_CLIENTS
+            .helius_client
+            .get_token_accounts_by_owner(&owner, TokenAccountsFilter::ProgramId(program))
+            .await?;
+        for keyed in accounts {
+            let UiAccountData::Json(parsed) = keyed.account.data else {
+                continue;
+            };
+            let info = &parsed.parsed["info"];
+            let Some(mint) = info["mint"].as_str() else {
+                continue;
+            };
+            let amount = info["tokenAmount"]["uiAmount"].as_f64().unwrap_or(0.0);
+            if amount > 0.0 {
+                holdings.push((mint.to_string(), amount));
+            }
+        }
+    }
+    holdings.sort_by(|a, b| b.1.total_cmp(&a.1));
+    Ok(holdings)
+}
+
+async fn build_live_holdings(tasks: &[&Task], user_data: &UserData) -> Vec<String> {
+    let mut wallets: Vec<(String, String)> = Vec::new();
+    for task in tasks.iter().filter(|task| !task.dry_run) {
+        if let Some(wallet) = resolve_task_wallet(task, user_data) {
+            if !wallets.iter().any(|(address, _)| *address == wallet.0) {
+                wallets.push(wallet);
+            }
+        }
+    }
+
+    let mut sections = Vec::new();
+    for (address, label) in wallets {
+        let body = match wallet_holdings(&address).await {
+            Ok(holdings) if holdings.is_empty() => escape_markdown("No token balances."),
+            Ok(holdings) => {
+                let mut lines: Vec<String> = holdings
+                    .iter()
+                    .take(MAX_LIVE_HOLDINGS)
+                    .map(|(mint, amount)| {
+                        format!(
+                            "• `{}` {}",
+                            escape_markdown(mint),
+                            escape_markdown(&format!("{:.2}", amount))
+                        )
+                    })
+                    .collect();
+                if holdings.len() > MAX_LIVE_HOLDINGS {
+                    lines.push(escape_markdown(&format!(
+                        "…and {} more",
+                        holdings.len() - MAX_LIVE_HOLDINGS
+                    )));
+                }
+                lines.join("\n")
+            }
+            Err(e) => escape_markdown(&format!("Holdings unavailable: {}", e)),
+        };
+        sections.push(format!(
+            "💼 *Live Holdings \\| {}*\n`{}`\n{}",
+            escape_markdown(&label),
+            escape_markdown(&address),
+            body
+        ));
+    }
+    sections
+}
+
+async fn build_positions(redis_client: &RedisClient, chat_id: i64, target: &str) -> Result<String> {
+    let Some(user_data) = load_user_data(redis_client, chat_id).await? else {
+        return Ok(escape_markdown("No tasks configured."));
+    };
+    let selected: Vec<&Task> = if target.is_empty() {
+        user_data.tasks.iter().collect()
+    } else {
+        match find_task(&user_data.tasks, target) {
+            Some(task) => vec![task],
+            None => return Ok(unknown_task(target)),
+        }
+    };
+    let names: Vec<String> = selected.iter().map(|task| task.name.clone()).collect();
+
+    let mut sections = Vec::new();
+    for name in &names {
+        if target.is_empty() && load_positions(chat_id, name).await?.is_empty() {
+            continue;
+        }
+        sections.push(build_paper_pnl_report(chat_id, name).await);
+    }
+    if sections.is_empty() {
+        sections.push(format!(
+            "📊 *Positions*\n\n{}",
+            escape_markdown("No open dry run positions.")
+        ));
+    }
+    sections.extend(build_live_holdings(&selected, &user_data).await);
+    Ok(sections.join("\n\n"))
+}
+
+async fn build_logs(redis_client: &RedisClient, chat_id: i64, args: &str) -> Result<String> {
+    let (name, count) = match args.rsplit_once(' ') {
+        Some((name, count)) if count.parse::<usize>().is_ok() => (
+            name.trim(),
+            count.parse::<usize>().unwrap_or(DEFAULT_LOG_LINES),
+        ),
+        _ => (args, DEFAULT_LOG_LINES),
+    };
+    if name.is_empty() {
+        return Ok(escape_markdown("Usage: /logs <task> [lines]"));
+    }
+    let tasks = load_user_data(redis_client, chat_id)
+        .await?
+        .map(|user_data| user_data.tasks)
+        .unwrap_or_default();
+    let Some(task_name) = find_task_name(&tasks, name) else {
+        return Ok(unknown_task(name));
+    };
+
+    let (entries, _) = subscribe_task_logs(chat_id, &task_name);
+    let heading = format!(
+        "📜 *{}*",
+        escape_markdown(&format!("Task Logs - {}", task_name))
+    );
+    if entries.is_empty() {
+        return Ok(format!(
+            "{}\n\n{}",
+            heading,
+            escape_markdown("No log entries since the server started.")
+        ));
+    }
+    let mut lines = Vec::new();
+    let mut used = 0;
+    for entry in entries.iter().rev().take(count.clamp(1, MAX_LOG_LINES)) {
+        used += entry.len() + 1;
+        if used > LOG_REPLY_CHAR_BUDGET {
+            break;
+        }
+        lines.push(escape_code(entry));
+    }
+    lines.reverse();
+    Ok(format!("{}\n\n```\n{}\n```", heading, lines.join("\n")))
+}
+
+async fn build_users(role: Role) -> Result<String> {
+    let users = list_authorized_users().await?;
+    let mut lines = vec![format!("👥 *Authorised Users:* {}", users.len())];
+    for user in &users {
+        let mut line = format!("• `{}` \\- {}", user.user_id, user.role.label());
+        if let Some(label) = user.label.as_deref().filter(|label| !label.is_empty()) {
+            line.push_str(&format!(" \\({}\\)", escape_markdown(label)));
+        }
+        lines.push(line);
+    }
+    lines.push(String::new());
+    lines.push(escape_markdown(&format!(
+        "Your role: {}. Viewers browse tasks and logs, traders edit tasks they created and buy, owners also manage users, Bloom accounts and wallets.",
+        role.label()
+    )));
+    Ok(lines.join("\n"))
+}
+
+async fn add_user(owner_id: u64, args: &str) -> String {
+    let mut parts = args.split_whitespace();
+    let user_id = parts.next().and_then(|value| value.parse::<u64>().ok());
+    let role = parts.next().and_then(Role::parse);
+    let (Some(user_id), Some(role)) = (user_id, role) else {
+        return escape_markdown("Usage: /adduser <user_id> <owner|trader|viewer> [label]");
+    };
+    let label = parts.collect::<Vec<_>>().join(" ");
+    let label = (!label.is_empty()).then_some(label);
+    match grant_role(user_id, role, label, owner_id).await {
+        Ok(user) => {
+            log::info!(
+                "access.granted user_id={} role={} by={}",
+                user.user_id,
+                user.role.label(),
+                owner_id
+            );
+            format!(
+                "✅ `{}` {}",
+                user.user_id,
+                escape_markdown(&format!("is now a {}.", user.role.label()))
+            )
+        }
+        Err(e) => format!("❌ {}", escape_markdown(&e.to_string())),
+    }
+}
+
+async fn remove_user(args: &str) -> String {
+    let Some(user_id) = args.parse::<u64>().ok() else {
+        return escape_markdown("Usage: /removeuser <user_id>");
+    };
+    match revoke_access(user_id).await {
+        Ok(true) => {
+            log::info!("access.revoked user_id={}", user_id);
+            format!(
+                "🗑️ `{}` {}",
+                user_id,
+                escape_markdown("no longer has access.")
+            )
+        }
+        Ok(false) => format!(
+            "ℹ️ `{}` {}",
+            user_id,
+            escape_markdown("was not authorised.")
+        ),
+        Err(e) => format!("❌ {}", escape_markdown(&e.to_string())),
+    }
+}
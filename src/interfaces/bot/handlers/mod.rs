@@ -1,4 +1,6 @@
+pub mod bloom;
 pub mod callbacks;
+pub mod commands;
 pub mod start;
 pub mod tasks;
 pub mod text;
@@ -3,6 +3,7 @@ use teloxide::{prelude::*, types::ChatId, utils::command::BotCommands};
 
 use crate::application::pricing::SolPriceState;
 use crate::infrastructure::blockchain::RpcClients;
+use crate::interfaces::bot::data::access::data_scope;
 use crate::interfaces::bot::handlers::tasks::get_tasks;
 use crate::interfaces::bot::{
     State, UserConfig, UserData, create_new_wallet, generate_tasks_text, get_user_data,
@@ -17,6 +18,28 @@ type MyDialogue = Dialogue<State, teloxide::dispatching::dialogue::InMemStorage<
 pub enum Command {
     #[command(description = "Start the bot and review your tasks.")]
     Start,
+    #[command(description = "List the available commands.")]
+    Help,
+    #[command(description = "Show Bloom WS, warmer, indexer and task status.")]
+    Status,
+    #[command(description = "Pause all running tasks, or one: /pause [task].")]
+    Pause(String),
+    #[command(description = "Resume tasks paused via /pause, or one: /resume [task].")]
+    Resume(String),
+    #[command(description = "Buy a mint with a task's settings: /buy <mint> [amount_sol] [task].")]
+    Buy(String),
+    #[command(description = "List dry run positions and live wallet holdings: /positions [task].")]
+    Positions(String),
+    #[command(description = "Show a task's recent log lines: /logs <task> [lines].")]
+    Logs(String),
+    #[command(description = "Owner only: list authorised users and their roles.")]
+    Users,
+    #[command(
+        description = "Owner only: authorise a user: /adduser <user_id> <owner|trader|viewer> [label]."
+    )]
+    AddUser(String),
+    #[command(description = "Owner only: revoke a user's access: /removeuser <user_id>.")]
+    RemoveUser(String),
 }
 
 pub async fn start(
@@ -28,8 +51,9 @@ pub async fn start(
     _rpc_clients: RpcClients,
 ) -> HandlerResult {
     let chat_id = msg.chat.id.0;
+    let data_chat_id = data_scope(msg.from().map(|user| user.id.0), chat_id);
     let mut con = redis_client.get_multiplexed_async_connection().await?;
-    let has_user_data = get_user_data(&mut con, chat_id).await?.is_some();
+    let has_user_data = get_user_data(&mut con, data_chat_id).await?.is_some();
 
     if !has_user_data {
         bot.send_message(
@@ -47,12 +71,13 @@ pub async fn start(
                 sell_priority_fee_sol: 0.001,
             },
             tasks: vec![],
+            bloom_accounts: vec![],
         };
-        save_user_data(&mut con, chat_id, &new_user_data).await?;
+        save_user_data(&mut con, data_chat_id, &new_user_data).await?;
     }
 
-    let tasks_text = generate_tasks_text(redis_client.clone(), chat_id).await;
-    let tasks = get_tasks(redis_client.clone(), chat_id).await;
+    let tasks_text = generate_tasks_text(redis_client.clone(), data_chat_id).await;
+    let tasks = get_tasks(redis_client.clone(), data_chat_id).await;
     bot.send_message(ChatId(chat_id), tasks_text)
         .parse_mode(teloxide::types::ParseMode::MarkdownV2)
         .reply_markup(tasks_menu_keyboard(&tasks))
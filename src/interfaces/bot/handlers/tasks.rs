@@ -1,22 +1,29 @@
-use crate::USER_CLIENT_HANDLE;
 use crate::application::pricing::SolPriceState;
-use crate::infrastructure::blockchain::RpcClients;
+use crate::infrastructure::blockchain::{BloomCredentials, RpcClients};
 use crate::interfaces::bot::WalletDisplayInfo;
 use crate::interfaces::bot::data::BloomWalletInfo;
+use crate::interfaces::bot::data::access::data_scope;
 use crate::interfaces::bot::data::types::Platform;
+use crate::interfaces::bot::tasks::autojoin::build_join_log_report;
+use crate::interfaces::bot::tasks::native::resolve_native_signer;
+use crate::interfaces::bot::tasks::paper::{build_paper_pnl_report, reset_paper_positions};
+use crate::interfaces::bot::tasks::resolve_bloom_credentials;
 use crate::interfaces::bot::user::client::{
-    UserClientHandle, authenticate_task_user_via_qr, get_chat_admins, is_channel_member,
-    take_active_qr_message, take_pending_session,
+    UserClientHandle, authenticate_task_user_via_qr, get_chat_admins, get_forum_topics,
+    is_channel_member, take_active_qr_message, take_pending_session,
 };
+use crate::interfaces::bot::user::sessions::{register_session, task_client};
 use crate::interfaces::bot::utils::fetch_bloom_wallets;
 use crate::interfaces::bot::{
-    ITEMS_PER_PAGE, State, Task, channel_selection_keyboard, generate_task_detail_text,
+    DEFAULT_DEDUP_TTL_SECS, ForumTopic, ITEMS_PER_PAGE, State, Task, TelegramChannel,
+    channel_selection_keyboard, generate_task_channels_text, generate_task_detail_text,
     generate_task_settings_text, generate_task_wallets_text, generate_tasks_text, get_user_data,
-    save_user_data, send_cleanup_msg, task_delete_confirmation_keyboard, task_detail_keyboard,
+    save_user_data, send_cleanup_msg, task_channels_keyboard, task_delete_confirmation_keyboard,
+    task_detail_keyboard, task_join_log_keyboard, task_paper_report_keyboard,
     task_settings_keyboard, task_telegram_linking_keyboard, task_wallets_keyboard,
-    tasks_menu_keyboard, telegram_linking_intro_text, user_selection_keyboard,
+    tasks_menu_keyboard, telegram_linking_intro_text, topic_selection_keyboard,
+    user_selection_keyboard,
 };
-use grammers_client::Client as TelegramClient;
 use parking_lot::Mutex;
 use rand::Rng;
 use redis::Client as RedisClient;
@@ -25,7 +32,6 @@ use std::str::FromStr;
 use std::sync::Arc;
 use teloxide::prelude::*;
 use teloxide::types::MessageId;
-use tokio::sync::mpsc::Sender;
 
 type MyDialogue = Dialogue<State, teloxide::dispatching::dialogue::InMemStorage<State>>;
 type HandlerResult = Result<(), Box<dyn std::error::Error + Send + Sync>>;
@@ -47,12 +53,12 @@ pub async fn handle_task_callbacks(
     redis_client: RedisClient,
     dialogue: MyDialogue,
     sol_price_state: SolPriceState,
-    user_client_handle: Arc<Mutex<Option<UserClientHandle>>>,
+    _user_client_handle: Arc<Mutex<Option<UserClientHandle>>>,
     rpc_clients: RpcClients,
-    client_sender: Sender<TelegramClient>,
 ) -> HandlerResult {
     if let Some(message) = q.message.clone() {
         let chat_id = message.chat.id;
+        let data_chat_id = data_scope(Some(q.from.id.0), chat_id.0);
         let data = q.data.clone().unwrap_or_default();
 
         log::info!(
@@ -62,8 +68,8 @@ pub async fn handle_task_callbacks(
         );
 
         if data == "view_tasks" {
-            let tasks_text = generate_tasks_text(redis_client.clone(), chat_id.0).await;
-            let tasks = get_tasks(redis_client.clone(), chat_id.0).await;
+            let tasks_text = generate_tasks_text(redis_client.clone(), data_chat_id).await;
+            let tasks = get_tasks(redis_client.clone(), data_chat_id).await;
             bot.edit_message_text(chat_id, message.id, tasks_text)
                 .parse_mode(teloxide::types::ParseMode::MarkdownV2)
                 .reply_markup(tasks_menu_keyboard(&tasks))
@@ -71,11 +77,12 @@ pub async fn handle_task_callbacks(
             dialogue.update(State::TasksMenu).await?;
         } else if data == "create_task" {
             let mut con = redis_client.get_multiplexed_async_connection().await?;
-            if let Some(mut user_data) = get_user_data(&mut con, chat_id.0).await? {
+            if let Some(mut user_data) = get_user_data(&mut con, data_chat_id).await? {
                 let task_name = generate_random_task_name();
                 let new_task = Task {
                     name: task_name.clone(),
                     platform: crate::interfaces::bot::data::types::Platform::Telegram,
+                    channels: vec![],
                     listen_channels: vec![],
                     listen_channel_name: None,
                     listen_users: vec![],
@@ -83,6 +90,7 @@ pub async fn handle_task_callbacks(
                     telegram_channel_is_broadcast: false,
                     grammers_session_data: None,
                     telegram_username: None,
+                    telegram_user_id: None,
                     discord_token: None,
                     discord_channel_id: None,
                     discord_username: None,
@@ -94,15 +102,38 @@ pub async fn handle_task_callbacks(
                     blacklist_words: vec![],
                     inform_only: false,
                     bloom_wallet: None,
+                    safety_checks_enabled: false,
+                    min_liquidity_sol: None,
+                    max_liquidity_sol: None,
+                    min_market_cap_sol: None,
+                    max_market_cap_sol: None,
+                    bloom_anti_mev: false,
+                    bloom_auto_tip: false,
+                    bloom_skip_if_bought: false,
+                    bloom_hedged: false,
+                    bloom_dev_sell: None,
+                    bloom_processor_tip_sol: None,
+                    dedup_scope: Default::default(),
+                    dedup_ttl_secs: DEFAULT_DEDUP_TTL_SECS,
+                    execution_mode: Default::default(),
+                    dry_run: false,
+                    retry_policy: Default::default(),
+                    schedule: None,
+                    bloom_account: None,
+                    process_edits: false,
+                    include_reply_context: false,
+                    match_forward_author: false,
+                    auto_join: Default::default(),
+                    created_by: Some(q.from.id.0),
                 };
                 user_data.tasks.push(new_task);
-                save_user_data(&mut con, chat_id.0, &user_data).await?;
+                save_user_data(&mut con, data_chat_id, &user_data).await?;
                 bot.answer_callback_query(q.id)
                     .text(&format!("✅ New task created: {}", task_name))
                     .await?;
             }
-            let tasks_text = generate_tasks_text(redis_client.clone(), chat_id.0).await;
-            let tasks = get_tasks(redis_client.clone(), chat_id.0).await;
+            let tasks_text = generate_tasks_text(redis_client.clone(), data_chat_id).await;
+            let tasks = get_tasks(redis_client.clone(), data_chat_id).await;
             bot.edit_message_text(chat_id, message.id, tasks_text)
                 .parse_mode(teloxide::types::ParseMode::MarkdownV2)
                 .reply_markup(tasks_menu_keyboard(&tasks))
@@ -131,7 +162,7 @@ pub async fn handle_task_callbacks(
                             if let Some(wallet) = wallets.get(index) {
                                 if persist_task_wallet_selection(
                                     &redis_client,
-                                    chat_id.0,
+                                    data_chat_id,
                                     &task_name,
                                     Some(wallet),
                                 )
@@ -148,6 +179,7 @@ pub async fn handle_task_callbacks(
                             &bot,
                             redis_client.clone(),
                             chat_id,
+                            data_chat_id,
                             menu_message_id,
                             &task_name,
                             &wallets,
@@ -187,6 +219,7 @@ pub async fn handle_task_callbacks(
                                 &bot,
                                 redis_client.clone(),
                                 chat_id,
+                                data_chat_id,
                                 menu_message_id,
                                 &task_name,
                                 &wallets,
@@ -219,7 +252,7 @@ pub async fn handle_task_callbacks(
                     if task_name_payload == task_name {
                         let cleared = persist_task_wallet_selection(
                             &redis_client,
-                            chat_id.0,
+                            data_chat_id,
                             &task_name,
                             None,
                         )
@@ -234,6 +267,7 @@ pub async fn handle_task_callbacks(
                             &bot,
                             redis_client.clone(),
                             chat_id,
+                            data_chat_id,
                             menu_message_id,
                             &task_name,
                             &wallets,
@@ -254,12 +288,20 @@ pub async fn handle_task_callbacks(
                 }
             }
         } else if let Some(task_name) = data.strip_prefix("task_settings_wallets_") {
-            match fetch_bloom_wallets(None).await {
+            let wallets_result =
+                match task_bloom_credentials(redis_client.clone(), data_chat_id, task_name).await {
+                    Some(credentials) => fetch_bloom_wallets(&credentials, None).await,
+                    None => Err(anyhow::anyhow!(
+                        "No Bloom account configured; add one from Bloom Accounts"
+                    )),
+                };
+            match wallets_result {
                 Ok(wallets) => {
                     render_task_wallets_view(
                         &bot,
                         redis_client.clone(),
                         chat_id,
+                        data_chat_id,
                         message.id,
                         task_name,
                         &wallets,
@@ -288,6 +330,7 @@ pub async fn handle_task_callbacks(
                 &bot,
                 redis_client.clone(),
                 chat_id,
+                data_chat_id,
                 message.id,
                 task_name,
                 sol_price_state.clone(),
@@ -301,10 +344,12 @@ pub async fn handle_task_callbacks(
                 })
                 .await?;
         } else if let Some(task_name) = data.strip_prefix("task_detail_") {
-            if let Some(task) = get_task_by_name(redis_client.clone(), chat_id.0, task_name).await {
+            if let Some(task) =
+                get_task_by_name(redis_client.clone(), data_chat_id, task_name).await
+            {
                 let task_text = build_task_detail_text(
                     redis_client.clone(),
-                    chat_id.0,
+                    data_chat_id,
                     &task,
                     sol_price_state.clone(),
                     rpc_clients.clone(),
@@ -319,11 +364,91 @@ pub async fn handle_task_callbacks(
                     .await?;
             }
         } else if let Some(task_name) = data.strip_prefix("task_toggle_inform_") {
-            toggle_task_inform_only(redis_client.clone(), chat_id.0, task_name).await?;
-            if let Some(task) = get_task_by_name(redis_client.clone(), chat_id.0, task_name).await {
+            toggle_task_inform_only(redis_client.clone(), data_chat_id, task_name).await?;
+            if let Some(task) =
+                get_task_by_name(redis_client.clone(), data_chat_id, task_name).await
+            {
+                let task_text = build_task_detail_text(
+                    redis_client.clone(),
+                    data_chat_id,
+                    &task,
+                    sol_price_state.clone(),
+                    rpc_clients.clone(),
+                )
+                .await;
+                bot.edit_message_text(chat_id, message.id, task_text)
+                    .parse_mode(teloxide::types::ParseMode::MarkdownV2)
+                    .reply_markup(task_detail_keyboard(&task))
+                    .await?;
+            }
+        } else if let Some(task_name) = data.strip_prefix("task_safety_toggle:") {
+            toggle_task_safety_checks(redis_client.clone(), data_chat_id, task_name).await?;
+            if let Some(task) =
+                get_task_by_name(redis_client.clone(), data_chat_id, task_name).await
+            {
+                let task_text = build_task_detail_text(
+                    redis_client.clone(),
+                    data_chat_id,
+                    &task,
+                    sol_price_state.clone(),
+                    rpc_clients.clone(),
+                )
+                .await;
+                bot.edit_message_text(chat_id, message.id, task_text)
+                    .parse_mode(teloxide::types::ParseMode::MarkdownV2)
+                    .reply_markup(task_detail_keyboard(&task))
+                    .await?;
+            }
+        } else if let Some(task_name) = data.strip_prefix("task_bloom_account_") {
+            cycle_task_bloom_account(redis_client.clone(), data_chat_id, task_name).await?;
+            if let Some(task) =
+                get_task_by_name(redis_client.clone(), data_chat_id, task_name).await
+            {
+                let task_text = build_task_detail_text(
+                    redis_client.clone(),
+                    data_chat_id,
+                    &task,
+                    sol_price_state.clone(),
+                    rpc_clients.clone(),
+                )
+                .await;
+                bot.edit_message_text(chat_id, message.id, task_text)
+                    .parse_mode(teloxide::types::ParseMode::MarkdownV2)
+                    .reply_markup(task_detail_keyboard(&task))
+                    .await?;
+            }
+        } else if let Some(task_name) = data.strip_prefix("task_toggle_dryrun_") {
+            update_task_field(redis_client.clone(), data_chat_id, task_name, |task| {
+                task.dry_run = !task.dry_run;
+            })
+            .await?;
+            if let Some(task) =
+                get_task_by_name(redis_client.clone(), data_chat_id, task_name).await
+            {
+                let task_text = build_task_detail_text(
+                    redis_client.clone(),
+                    data_chat_id,
+                    &task,
+                    sol_price_state.clone(),
+                    rpc_clients.clone(),
+                )
+                .await;
+                bot.edit_message_text(chat_id, message.id, task_text)
+                    .parse_mode(teloxide::types::ParseMode::MarkdownV2)
+                    .reply_markup(task_detail_keyboard(&task))
+                    .await?;
+            }
+        } else if let Some(task_name) = data.strip_prefix("task_toggle_edits_") {
+            update_task_field(redis_client.clone(), data_chat_id, task_name, |task| {
+                task.process_edits = !task.process_edits;
+            })
+            .await?;
+            if let Some(task) =
+                get_task_by_name(redis_client.clone(), data_chat_id, task_name).await
+            {
                 let task_text = build_task_detail_text(
                     redis_client.clone(),
-                    chat_id.0,
+                    data_chat_id,
                     &task,
                     sol_price_state.clone(),
                     rpc_clients.clone(),
@@ -334,15 +459,127 @@ pub async fn handle_task_callbacks(
                     .reply_markup(task_detail_keyboard(&task))
                     .await?;
             }
+        } else if let Some(task_name) = data.strip_prefix("task_toggle_replies_") {
+            update_task_field(redis_client.clone(), data_chat_id, task_name, |task| {
+                task.include_reply_context = !task.include_reply_context;
+            })
+            .await?;
+            if let Some(task) =
+                get_task_by_name(redis_client.clone(), data_chat_id, task_name).await
+            {
+                let task_text = build_task_detail_text(
+                    redis_client.clone(),
+                    data_chat_id,
+                    &task,
+                    sol_price_state.clone(),
+                    rpc_clients.clone(),
+                )
+                .await;
+                bot.edit_message_text(chat_id, message.id, task_text)
+                    .parse_mode(teloxide::types::ParseMode::MarkdownV2)
+                    .reply_markup(task_detail_keyboard(&task))
+                    .await?;
+            }
+        } else if let Some(task_name) = data.strip_prefix("task_toggle_forwards_") {
+            update_task_field(redis_client.clone(), data_chat_id, task_name, |task| {
+                task.match_forward_author = !task.match_forward_author;
+            })
+            .await?;
+            if let Some(task) =
+                get_task_by_name(redis_client.clone(), data_chat_id, task_name).await
+            {
+                let task_text = build_task_detail_text(
+                    redis_client.clone(),
+                    data_chat_id,
+                    &task,
+                    sol_price_state.clone(),
+                    rpc_clients.clone(),
+                )
+                .await;
+                bot.edit_message_text(chat_id, message.id, task_text)
+                    .parse_mode(teloxide::types::ParseMode::MarkdownV2)
+                    .reply_markup(task_detail_keyboard(&task))
+                    .await?;
+            }
+        } else if let Some(task_name) = data.strip_prefix("task_autojoin_mode_") {
+            update_task_field(redis_client.clone(), data_chat_id, task_name, |task| {
+                task.auto_join.mode = task.auto_join.mode.next();
+            })
+            .await?;
+            if let Some(task) =
+                get_task_by_name(redis_client.clone(), data_chat_id, task_name).await
+            {
+                let task_text = build_task_detail_text(
+                    redis_client.clone(),
+                    data_chat_id,
+                    &task,
+                    sol_price_state.clone(),
+                    rpc_clients.clone(),
+                )
+                .await;
+                bot.edit_message_text(chat_id, message.id, task_text)
+                    .parse_mode(teloxide::types::ParseMode::MarkdownV2)
+                    .reply_markup(task_detail_keyboard(&task))
+                    .await?;
+            }
+        } else if let Some(task_name) = data.strip_prefix("task_autojoin_settings_") {
+            let prompt = bot
+                .send_message(
+                    chat_id,
+                    "Enter the auto-join settings as: daily_limit leave_after monitor\nLimit is the number of invites the linked Telegram account joins per day (1-50); leave_after is how long to stay in joined chats (e.g., 24h, 7d, or 0 to stay); monitor is on/off to add joined chats to this task (e.g., 5 24h on).",
+                )
+                .await?;
+            dialogue
+                .update(State::TaskReceiveAutoJoin {
+                    task_name: task_name.to_string(),
+                    menu_message_id: message.id,
+                    prompt_message_id: prompt.id,
+                })
+                .await?;
+        } else if let Some(task_name) = data.strip_prefix("task_autojoin_log_") {
+            bot.answer_callback_query(q.id.clone()).await?;
+            let report = build_join_log_report(data_chat_id, task_name).await;
+            bot.edit_message_text(chat_id, message.id, report)
+                .parse_mode(teloxide::types::ParseMode::MarkdownV2)
+                .reply_markup(task_join_log_keyboard(task_name))
+                .await?;
+        } else if let Some(task_name) = data.strip_prefix("task_paper_pnl_") {
+            bot.answer_callback_query(q.id.clone()).await?;
+            let report = build_paper_pnl_report(data_chat_id, task_name).await;
+            bot.edit_message_text(chat_id, message.id, report)
+                .parse_mode(teloxide::types::ParseMode::MarkdownV2)
+                .reply_markup(task_paper_report_keyboard(task_name))
+                .await?;
+        } else if let Some(task_name) = data.strip_prefix("task_paper_reset_") {
+            match reset_paper_positions(data_chat_id, task_name).await {
+                Ok(_) => {
+                    bot.answer_callback_query(q.id.clone())
+                        .text("🧹 Paper positions cleared")
+                        .await?;
+                }
+                Err(e) => {
+                    log::error!("Failed to reset paper positions for {}: {}", task_name, e);
+                    bot.answer_callback_query(q.id.clone())
+                        .text("❌ Failed to clear paper positions")
+                        .await?;
+                }
+            }
+            let report = build_paper_pnl_report(data_chat_id, task_name).await;
+            bot.edit_message_text(chat_id, message.id, report)
+                .parse_mode(teloxide::types::ParseMode::MarkdownV2)
+                .reply_markup(task_paper_report_keyboard(task_name))
+                .await?;
         } else if let Some(task_name) = data.strip_prefix("task_toggle_") {
-            if let Some(task) = get_task_by_name(redis_client.clone(), chat_id.0, task_name).await {
+            if let Some(task) =
+                get_task_by_name(redis_client.clone(), data_chat_id, task_name).await
+            {
                 if !task.active {
                     if let Some(error_msg) = activation_requirement_error(&task) {
                         bot.answer_callback_query(q.id.clone()).await?;
                         let _ = send_cleanup_msg(&bot, chat_id, error_msg, 5).await;
                         let task_text = build_task_detail_text(
                             redis_client.clone(),
-                            chat_id.0,
+                            data_chat_id,
                             &task,
                             sol_price_state.clone(),
                             rpc_clients.clone(),
@@ -356,113 +593,108 @@ pub async fn handle_task_callbacks(
                     }
 
                     if matches!(task.platform, Platform::Telegram) {
-                        let Some(channel_id) = task.listen_channels.first().copied() else {
-                            bot.answer_callback_query(q.id.clone()).await?;
-                            let _ = send_cleanup_msg(
-                                &bot,
-                                chat_id,
-                                "⚠️ Channel information is missing. Please choose the channel again.",
-                                6,
-                            )
-                            .await;
-                            return Ok(());
-                        };
-
-                        let handle = user_client_handle.lock().clone();
-                        let Some(client) = handle else {
-                            bot.answer_callback_query(q.id.clone()).await?;
-                            let _ = send_cleanup_msg(
-                                &bot,
-                                chat_id,
-                                "⚠️ Telegram user client is disconnected. Please restart the session.",
-                                6,
-                            )
-                            .await;
-                            return Ok(());
-                        };
-
-                        match is_channel_member(&client, channel_id).await {
-                            Ok(true) => {}
-                            Ok(false) => {
+                        let client = match task_client(&task).await {
+                            Ok(client) => client,
+                            Err(err) => {
                                 bot.answer_callback_query(q.id.clone()).await?;
-                                log::warn!(
-                                    "Membership check failed chat_id={} task={} channel={}",
-                                    chat_id.0,
-                                    task_name,
-                                    channel_id
+                                let msg = format!(
+                                    "⚠️ Telegram user client is unavailable: {}. Please relink the Telegram user.",
+                                    err
                                 );
-                                let cleared_task_result =
-                                    clear_telegram_task_channel_configuration(
+                                let _ = send_cleanup_msg(&bot, chat_id, &msg, 6).await;
+                                return Ok(());
+                            }
+                        };
+
+                        for channel in task.enabled_channels() {
+                            let channel_id = channel.id;
+                            match is_channel_member(&client, channel_id).await {
+                                Ok(true) => {}
+                                Ok(false) => {
+                                    bot.answer_callback_query(q.id.clone()).await?;
+                                    log::warn!(
+                                        "Membership check failed chat_id={} task={} channel={}",
+                                        data_chat_id,
+                                        task_name,
+                                        channel_id
+                                    );
+                                    let cleared_task_result = remove_telegram_task_channel(
                                         redis_client.clone(),
-                                        chat_id.0,
+                                        data_chat_id,
                                         task_name,
+                                        channel_id,
                                     )
                                     .await;
-                                let task_for_view = match cleared_task_result {
-                                    Ok(Some(updated_task)) => updated_task,
-                                    Ok(None) => {
-                                        log::warn!(
-                                            "No task found during channel reset chat_id={} task={}",
-                                            chat_id.0,
-                                            task_name
-                                        );
-                                        task.clone()
-                                    }
-                                    Err(err) => {
-                                        log::error!(
-                                            "Failed to clear Telegram channel configuration chat_id={} task={} err={}",
-                                            chat_id.0,
-                                            task_name,
-                                            err
-                                        );
-                                        let msg =
-                                            format!("⚠️ Failed to reset channel settings: {}", err);
-                                        let _ = send_cleanup_msg(&bot, chat_id, &msg, 8).await;
-                                        return Ok(());
-                                    }
-                                };
+                                    let task_for_view = match cleared_task_result {
+                                        Ok(Some(updated_task)) => updated_task,
+                                        Ok(None) => {
+                                            log::warn!(
+                                                "No task found during channel removal chat_id={} task={}",
+                                                data_chat_id,
+                                                task_name
+                                            );
+                                            task.clone()
+                                        }
+                                        Err(err) => {
+                                            log::error!(
+                                                "Failed to remove Telegram channel chat_id={} task={} channel={} err={}",
+                                                data_chat_id,
+                                                task_name,
+                                                channel_id,
+                                                err
+                                            );
+                                            let msg =
+                                                format!("⚠️ Failed to remove the channel: {}", err);
+                                            let _ = send_cleanup_msg(&bot, chat_id, &msg, 8).await;
+                                            return Ok(());
+                                        }
+                                    };
 
-                                let task_text = build_task_detail_text(
-                                    redis_client.clone(),
-                                    chat_id.0,
-                                    &task_for_view,
-                                    sol_price_state.clone(),
-                                    rpc_clients.clone(),
-                                )
-                                .await;
-                                bot.edit_message_text(chat_id, message.id, task_text)
-                                    .parse_mode(teloxide::types::ParseMode::MarkdownV2)
-                                    .reply_markup(task_detail_keyboard(&task_for_view))
-                                    .await?;
+                                    let task_text = build_task_detail_text(
+                                        redis_client.clone(),
+                                        data_chat_id,
+                                        &task_for_view,
+                                        sol_price_state.clone(),
+                                        rpc_clients.clone(),
+                                    )
+                                    .await;
+                                    bot.edit_message_text(chat_id, message.id, task_text)
+                                        .parse_mode(teloxide::types::ParseMode::MarkdownV2)
+                                        .reply_markup(task_detail_keyboard(&task_for_view))
+                                        .await?;
 
-                                let warning_msg = "⚠️ The Telegram user client is no longer a member of this channel. Channel and monitoring settings were reset. Please select another channel or rejoin.";
-                                let _ = send_cleanup_msg(&bot, chat_id, warning_msg, 8).await;
-                                return Ok(());
-                            }
-                            Err(err) => {
-                                bot.answer_callback_query(q.id.clone()).await?;
-                                log::warn!(
-                                    "Failed to verify membership chat_id={} task={} err={}",
-                                    chat_id.0,
-                                    task_name,
-                                    err
-                                );
-                                let msg =
-                                    format!("⚠️ Unable to verify channel membership: {}", err);
-                                let _ = send_cleanup_msg(&bot, chat_id, &msg, 8).await;
-                                return Ok(());
+                                    let warning_msg = format!(
+                                        "⚠️ The Telegram user client is no longer a member of {}. The channel was removed from this task. Please rejoin and add it again.",
+                                        channel.display_name()
+                                    );
+                                    let _ = send_cleanup_msg(&bot, chat_id, &warning_msg, 8).await;
+                                    return Ok(());
+                                }
+                                Err(err) => {
+                                    bot.answer_callback_query(q.id.clone()).await?;
+                                    log::warn!(
+                                        "Failed to verify membership chat_id={} task={} err={}",
+                                        data_chat_id,
+                                        task_name,
+                                        err
+                                    );
+                                    let msg =
+                                        format!("⚠️ Unable to verify channel membership: {}", err);
+                                    let _ = send_cleanup_msg(&bot, chat_id, &msg, 8).await;
+                                    return Ok(());
+                                }
                             }
                         }
                     }
                 }
 
-                toggle_task_active(redis_client.clone(), chat_id.0, task_name).await?;
+                toggle_task_active(redis_client.clone(), data_chat_id, task_name).await?;
                 if let Some(updated_task) =
-                    get_task_by_name(redis_client.clone(), chat_id.0, task_name).await
+                    get_task_by_name(redis_client.clone(), data_chat_id, task_name).await
                 {
                     let task_text = build_task_detail_text(
                         redis_client.clone(),
-                        chat_id.0,
+                        data_chat_id,
                         &updated_task,
                         sol_price_state.clone(),
                         rpc_clients.clone(),
@@ -475,7 +707,9 @@ pub async fn handle_task_callbacks(
                 }
             }
         } else if let Some(task_name) = data.strip_prefix("task_delete_confirm_") {
-            if let Some(task) = get_task_by_name(redis_client.clone(), chat_id.0, task_name).await {
+            if let Some(task) =
+                get_task_by_name(redis_client.clone(), data_chat_id, task_name).await
+            {
                 if task.active {
                     let _ = send_cleanup_msg(
                         &bot,
@@ -485,12 +719,12 @@ pub async fn handle_task_callbacks(
                     )
                     .await;
                 } else {
-                    delete_task(redis_client.clone(), chat_id.0, task_name).await?;
+                    delete_task(redis_client.clone(), data_chat_id, task_name).await?;
                     bot.answer_callback_query(q.id)
                         .text("🗑️ Task deleted.")
                         .await?;
-                    let tasks_text = generate_tasks_text(redis_client.clone(), chat_id.0).await;
-                    let tasks = get_tasks(redis_client.clone(), chat_id.0).await;
+                    let tasks_text = generate_tasks_text(redis_client.clone(), data_chat_id).await;
+                    let tasks = get_tasks(redis_client.clone(), data_chat_id).await;
                     bot.edit_message_text(chat_id, message.id, tasks_text)
                         .parse_mode(teloxide::types::ParseMode::MarkdownV2)
                         .reply_markup(tasks_menu_keyboard(&tasks))
@@ -499,7 +733,9 @@ pub async fn handle_task_callbacks(
                 }
             }
         } else if let Some(task_name) = data.strip_prefix("task_delete_") {
-            if let Some(task) = get_task_by_name(redis_client.clone(), chat_id.0, task_name).await {
+            if let Some(task) =
+                get_task_by_name(redis_client.clone(), data_chat_id, task_name).await
+            {
                 if task.active {
                     let _ = send_cleanup_msg(
                         &bot,
@@ -569,6 +805,229 @@ pub async fn handle_task_callbacks(
                     prompt_message_id: prompt.id,
                 })
                 .await?;
+        } else if let Some(task_name) = data.strip_prefix("task_bloom_antimev_") {
+            update_task_field(redis_client.clone(), data_chat_id, task_name, |task| {
+                task.bloom_anti_mev = !task.bloom_anti_mev;
+            })
+            .await?;
+            render_task_settings_view(
+                &bot,
+                redis_client.clone(),
+                chat_id,
+                data_chat_id,
+                message.id,
+                task_name,
+                sol_price_state.clone(),
+                rpc_clients.clone(),
+            )
+            .await?;
+        } else if let Some(task_name) = data.strip_prefix("task_bloom_hedge_") {
+            update_task_field(redis_client.clone(), data_chat_id, task_name, |task| {
+                task.bloom_hedged = !task.bloom_hedged;
+            })
+            .await?;
+            render_task_settings_view(
+                &bot,
+                redis_client.clone(),
+                chat_id,
+                data_chat_id,
+                message.id,
+                task_name,
+                sol_price_state.clone(),
+                rpc_clients.clone(),
+            )
+            .await?;
+        } else if let Some(task_name) = data.strip_prefix("task_bloom_autotip_") {
+            update_task_field(redis_client.clone(), data_chat_id, task_name, |task| {
+                task.bloom_auto_tip = !task.bloom_auto_tip;
+            })
+            .await?;
+            render_task_settings_view(
+                &bot,
+                redis_client.clone(),
+                chat_id,
+                data_chat_id,
+                message.id,
+                task_name,
+                sol_price_state.clone(),
+                rpc_clients.clone(),
+            )
+            .await?;
+        } else if let Some(task_name) = data.strip_prefix("task_bloom_skipbought_") {
+            update_task_field(redis_client.clone(), data_chat_id, task_name, |task| {
+                task.bloom_skip_if_bought = !task.bloom_skip_if_bought;
+            })
+            .await?;
+            render_task_settings_view(
+                &bot,
+                redis_client.clone(),
+                chat_id,
+                data_chat_id,
+                message.id,
+                task_name,
+                sol_price_state.clone(),
+                rpc_clients.clone(),
+            )
+            .await?;
+        } else if let Some(task_name) = data.strip_prefix("task_bloom_tip_") {
+            let prompt = bot
+                .send_message(
+                    chat_id,
+                    "Please enter the Bloom processor tip in SOL (e.g., 0.001). Send 'default' to use the buy fee:",
+                )
+                .await?;
+            dialogue
+                .update(State::TaskReceiveProcessorTip {
+                    task_name: task_name.to_string(),
+                    menu_message_id: message.id,
+                    prompt_message_id: prompt.id,
+                })
+                .await?;
+        } else if let Some(task_name) = data.strip_prefix("task_bloom_devsell_") {
+            let prompt = bot
+                .send_message(
+                    chat_id,
+                    "Please enter the Bloom dev sell value (sent as-is to Bloom), or 'off' to disable:",
+                )
+                .await?;
+            dialogue
+                .update(State::TaskReceiveDevSell {
+                    task_name: task_name.to_string(),
+                    menu_message_id: message.id,
+                    prompt_message_id: prompt.id,
+                })
+                .await?;
+        } else if let Some(task_name) = data.strip_prefix("task_exec_mode_") {
+            let mut con = redis_client.get_multiplexed_async_connection().await?;
+            let user_data = get_user_data(&mut con, data_chat_id).await?;
+            let signer_error = user_data.as_ref().and_then(|user_data| {
+                let task = user_data.tasks.iter().find(|t| t.name == *task_name)?;
+                if !task.execution_mode.next().uses_native() {
+                    return None;
+                }
+                resolve_native_signer(task, user_data).err()
+            });
+            if let Some(e) = signer_error {
+                bot.answer_callback_query(q.id.clone())
+                    .text(format!(
+                        "❌ Native execution needs a local signing wallet: {}",
+                        e
+                    ))
+                    .show_alert(true)
+                    .await?;
+                return Ok(());
+            }
+            update_task_field(redis_client.clone(), data_chat_id, task_name, |task| {
+                task.execution_mode = task.execution_mode.next();
+            })
+            .await?;
+            if let Some(task) =
+                get_task_by_name(redis_client.clone(), data_chat_id, task_name).await
+            {
+                let task_text = build_task_detail_text(
+                    redis_client.clone(),
+                    data_chat_id,
+                    &task,
+                    sol_price_state.clone(),
+                    rpc_clients.clone(),
+                )
+                .await;
+                bot.edit_message_text(chat_id, message.id, task_text)
+                    .parse_mode(teloxide::types::ParseMode::MarkdownV2)
+                    .reply_markup(task_detail_keyboard(&task))
+                    .await?;
+            }
+        } else if let Some(task_name) = data.strip_prefix("task_dedup_scope_") {
+            update_task_field(redis_client.clone(), data_chat_id, task_name, |task| {
+                task.dedup_scope = task.dedup_scope.next();
+            })
+            .await?;
+            if let Some(task) =
+                get_task_by_name(redis_client.clone(), data_chat_id, task_name).await
+            {
+                let task_text = build_task_detail_text(
+                    redis_client.clone(),
+                    data_chat_id,
+                    &task,
+                    sol_price_state.clone(),
+                    rpc_clients.clone(),
+                )
+                .await;
+                bot.edit_message_text(chat_id, message.id, task_text)
+                    .parse_mode(teloxide::types::ParseMode::MarkdownV2)
+                    .reply_markup(task_detail_keyboard(&task))
+                    .await?;
+            }
+        } else if let Some(task_name) = data.strip_prefix("task_schedule_") {
+            let prompt = bot
+                .send_message(
+                    chat_id,
+                    "Enter the schedule, one rule per line:\ntz Europe/Istanbul\nmon-fri 14:00-22:00\nsat,sun 16:00-20:00\nuntil 2026-12-31 23:59\nWindows may cross midnight (e.g., daily 22:00-02:00). The 'until' line is optional and stops the task at that time. Send 'off' for manual control.",
+                )
+                .await?;
+            dialogue
+                .update(State::TaskReceiveSchedule {
+                    task_name: task_name.to_string(),
+                    menu_message_id: message.id,
+                    prompt_message_id: prompt.id,
+                })
+                .await?;
+        } else if let Some(task_name) = data.strip_prefix("task_retry_") {
+            let prompt = bot
+                .send_message(
+                    chat_id,
+                    "Enter the retry policy as: attempts delay_ms fee_multiplier slippage_step cutoff\nAttempts include the first try; cutoff is measured from detection (e.g., 3 500 1.5 5 30s). Send 'off' to disable retries.",
+                )
+                .await?;
+            dialogue
+                .update(State::TaskReceiveRetryPolicy {
+                    task_name: task_name.to_string(),
+                    menu_message_id: message.id,
+                    prompt_message_id: prompt.id,
+                })
+                .await?;
+        } else if let Some(task_name) = data.strip_prefix("task_dedup_ttl_") {
+            let prompt = bot
+                .send_message(
+                    chat_id,
+                    "Enter how long a bought mint stays blocked (e.g., 30m, 24h, 7d). Send 0 for no expiry:",
+                )
+                .await?;
+            dialogue
+                .update(State::TaskReceiveDedupTtl {
+                    task_name: task_name.to_string(),
+                    menu_message_id: message.id,
+                    prompt_message_id: prompt.id,
+                })
+                .await?;
+        } else if let Some(task_name) = data.strip_prefix("task_dedup_clear_") {
+            let prompt = bot
+                .send_message(
+                    chat_id,
+                    "Enter the mint address to clear so it can be bought again:",
+                )
+                .await?;
+            dialogue
+                .update(State::TaskReceiveDedupClear {
+                    task_name: task_name.to_string(),
+                    menu_message_id: message.id,
+                    prompt_message_id: prompt.id,
+                })
+                .await?;
+        } else if let Some(task_name) = data.strip_prefix("task_market_filters_") {
+            let prompt = bot
+                .send_message(
+                    chat_id,
+                    "Enter liquidity and market cap limits in SOL as: min_liquidity max_liquidity min_mcap max_mcap\nUse - for no limit (e.g., 10 - 50 2000). Send 'clear' to remove all filters.",
+                )
+                .await?;
+            dialogue
+                .update(State::TaskReceiveMarketFilters {
+                    task_name: task_name.to_string(),
+                    menu_message_id: message.id,
+                    prompt_message_id: prompt.id,
+                })
+                .await?;
         } else if let Some(task_name) = data.strip_prefix("task_blacklist_") {
             let prompt = bot
                 .send_message(
@@ -585,7 +1044,7 @@ pub async fn handle_task_callbacks(
                 .await?;
         } else if let Some(task_name) = data.strip_prefix("task_platform_telegram_") {
             let mut con = redis_client.get_multiplexed_async_connection().await?;
-            if let Some(mut user_data) = get_user_data(&mut con, chat_id.0).await? {
+            if let Some(mut user_data) = get_user_data(&mut con, data_chat_id).await? {
                 if let Some(task) = user_data.tasks.iter_mut().find(|t| t.name == *task_name) {
                     if task.active {
                         bot.answer_callback_query(q.id)
@@ -594,13 +1053,13 @@ pub async fn handle_task_callbacks(
                         return Ok(());
                     }
                     task.platform = crate::interfaces::bot::data::types::Platform::Telegram;
-                    save_user_data(&mut con, chat_id.0, &user_data).await?;
+                    save_user_data(&mut con, data_chat_id, &user_data).await?;
                     if let Some(updated_task) =
                         user_data.tasks.iter().find(|t| t.name == *task_name)
                     {
                         let task_text = build_task_detail_text(
                             redis_client.clone(),
-                            chat_id.0,
+                            data_chat_id,
                             updated_task,
                             sol_price_state.clone(),
                             rpc_clients.clone(),
@@ -618,7 +1077,7 @@ pub async fn handle_task_callbacks(
             }
         } else if let Some(task_name) = data.strip_prefix("task_platform_discord_") {
             let mut con = redis_client.get_multiplexed_async_connection().await?;
-            if let Some(mut user_data) = get_user_data(&mut con, chat_id.0).await? {
+            if let Some(mut user_data) = get_user_data(&mut con, data_chat_id).await? {
                 if let Some(task) = user_data.tasks.iter_mut().find(|t| t.name == *task_name) {
                     if task.active {
                         bot.answer_callback_query(q.id)
@@ -627,13 +1086,13 @@ pub async fn handle_task_callbacks(
                         return Ok(());
                     }
                     task.platform = crate::interfaces::bot::data::types::Platform::Discord;
-                    save_user_data(&mut con, chat_id.0, &user_data).await?;
+                    save_user_data(&mut con, data_chat_id, &user_data).await?;
                     if let Some(updated_task) =
                         user_data.tasks.iter().find(|t| t.name == *task_name)
                     {
                         let task_text = build_task_detail_text(
                             redis_client.clone(),
-                            chat_id.0,
+                            data_chat_id,
                             updated_task,
                             sol_price_state.clone(),
                             rpc_clients.clone(),
@@ -687,6 +1146,7 @@ pub async fn handle_task_callbacks(
                 &bot,
                 redis_client.clone(),
                 chat_id,
+                data_chat_id,
                 message.id,
                 &task_name,
                 sol_price_state.clone(),
@@ -748,6 +1208,7 @@ pub async fn handle_task_callbacks(
                                 bot_clone.clone(),
                                 redis_clone,
                                 chat_id_value,
+                                data_chat_id,
                                 task_name.clone(),
                                 menu_message_id_value,
                                 dialogue_clone.clone(),
@@ -810,29 +1271,41 @@ pub async fn handle_task_callbacks(
                             take_active_qr_message(chat_id.0, &task_name);
 
                             let mut con = redis_client.get_multiplexed_async_connection().await?;
-                            if let Some(mut user_data) = get_user_data(&mut con, chat_id.0).await? {
+                            if let Some(mut user_data) =
+                                get_user_data(&mut con, data_chat_id).await?
+                            {
                                 if let Some(task) =
                                     user_data.tasks.iter_mut().find(|t| t.name == task_name)
                                 {
                                     task.grammers_session_data =
                                         Some(pending.encoded_session.clone());
                                     task.telegram_username = pending.display_name.clone();
-                                    save_user_data(&mut con, chat_id.0, &user_data).await?;
+                                    task.telegram_user_id = Some(pending.user_id);
+                                    let relinked_task = task.clone();
+                                    save_user_data(&mut con, data_chat_id, &user_data).await?;
+                                    register_session(
+                                        pending.user_id,
+                                        pending.display_name.clone(),
+                                        pending.client.clone(),
+                                        Some(pending.encoded_session.clone()),
+                                    );
+                                    if relinked_task.active
+                                        && relinked_task.platform == Platform::Telegram
+                                    {
+                                        crate::interfaces::bot::tasks::tg::start_task_monitor(
+                                            relinked_task,
+                                            data_chat_id,
+                                        )
+                                        .await;
+                                    }
                                 }
                             }
 
-                            *USER_CLIENT_HANDLE.lock() = Some(pending.client.clone());
-                            if let Err(err) = client_sender.send(pending.client.clone()).await {
-                                log::warn!(
-                                    "Unable to hand over Telegram client to background loop: {}",
-                                    err
-                                );
-                            }
-
                             render_task_settings_view(
                                 &bot,
                                 redis_client.clone(),
                                 chat_id,
+                                data_chat_id,
                                 menu_message_id,
                                 &task_name,
                                 sol_price_state.clone(),
@@ -926,7 +1399,8 @@ pub async fn handle_task_callbacks(
                 .await?;
         } else if let Some(task_name) = data.strip_prefix("task_discord_channel_") {
             let task_name = task_name.to_string();
-            if let Some(task) = get_task_by_name(redis_client.clone(), chat_id.0, &task_name).await
+            if let Some(task) =
+                get_task_by_name(redis_client.clone(), data_chat_id, &task_name).await
             {
                 if task.active {
                     bot.answer_callback_query(q.id.clone()).await?;
@@ -959,16 +1433,63 @@ pub async fn handle_task_callbacks(
                     prompt_message_id: prompt.id,
                 })
                 .await?;
-        } else if data.starts_with("task_channels_") {
-            let task_name = data.strip_prefix("task_channels_").unwrap().to_string();
-            if let Some(task) = get_task_by_name(redis_client.clone(), chat_id.0, &task_name).await
+        } else if let Some(task_name) = data.strip_prefix("task_channels_") {
+            if let Some(task) =
+                get_task_by_name(redis_client.clone(), data_chat_id, task_name).await
             {
                 if !task.has_telegram_user_session() {
                     bot.answer_callback_query(q.id.clone()).await?;
                     let _ = send_cleanup_msg(
                         &bot,
                         chat_id,
-                        "⚠️ Telegram user client is not logged in. Please link a Telegram user before changing the channel.",
+                        "⚠️ Telegram user client is not logged in. Please link a Telegram user before managing channels.",
+                        5,
+                    )
+                    .await;
+                    return Ok(());
+                }
+                render_task_channels_view(&bot, chat_id, message.id, &task).await?;
+                dialogue.update(State::TasksMenu).await?;
+            }
+        } else if let Some(task_name) = data.strip_prefix("task_chan_toggle_") {
+            let Some((task_name, channel_id)) = parse_task_channel_payload(task_name) else {
+                return Ok(());
+            };
+            update_task_field(redis_client.clone(), data_chat_id, task_name, |task| {
+                if let Some(channel) = task.channel_mut(channel_id) {
+                    channel.enabled = !channel.enabled;
+                }
+            })
+            .await?;
+            if let Some(task) =
+                get_task_by_name(redis_client.clone(), data_chat_id, task_name).await
+            {
+                render_task_channels_view(&bot, chat_id, message.id, &task).await?;
+            }
+        } else if let Some(task_name) = data.strip_prefix("task_chan_remove_") {
+            let Some((task_name, channel_id)) = parse_task_channel_payload(task_name) else {
+                return Ok(());
+            };
+            update_task_field(redis_client.clone(), data_chat_id, task_name, |task| {
+                task.channels.retain(|channel| channel.id != channel_id);
+            })
+            .await?;
+            if let Some(task) =
+                get_task_by_name(redis_client.clone(), data_chat_id, task_name).await
+            {
+                render_task_channels_view(&bot, chat_id, message.id, &task).await?;
+            }
+        } else if let Some(task_name) = data.strip_prefix("task_chan_add_") {
+            let task_name = task_name.to_string();
+            if let Some(task) =
+                get_task_by_name(redis_client.clone(), data_chat_id, &task_name).await
+            {
+                if !task.has_telegram_user_session() {
+                    bot.answer_callback_query(q.id.clone()).await?;
+                    let _ = send_cleanup_msg(
+                        &bot,
+                        chat_id,
+                        "⚠️ Telegram user client is not logged in. Please link a Telegram user before adding a channel.",
                         5,
                     )
                     .await;
@@ -979,7 +1500,7 @@ pub async fn handle_task_callbacks(
                     let _ = send_cleanup_msg(
                         &bot,
                         chat_id,
-                        "⚠️ Task is active. Please stop it before changing the channel.",
+                        "⚠️ Task is active. Please stop it before adding a channel.",
                         5,
                     )
                     .await;
@@ -996,10 +1517,13 @@ pub async fn handle_task_callbacks(
                     prompt_message_id: prompt_message.id,
                 })
                 .await?;
-        } else if data.starts_with("task_users_") {
-            let task_name = data.strip_prefix("task_users_").unwrap().to_string();
+        } else if let Some(payload) = data.strip_prefix("task_chan_users_") {
+            let Some((task_name, channel_id)) = parse_task_channel_payload(payload) else {
+                return Ok(());
+            };
+            let task_name = task_name.to_string();
             let mut con = redis_client.get_multiplexed_async_connection().await?;
-            if let Some(user_data) = get_user_data(&mut con, chat_id.0).await? {
+            if let Some(user_data) = get_user_data(&mut con, data_chat_id).await? {
                 if let Some(task) = user_data.tasks.iter().find(|t| t.name == task_name) {
                     if !task.has_telegram_user_session() {
                         bot.answer_callback_query(q.id.clone()).await?;
@@ -1012,17 +1536,15 @@ pub async fn handle_task_callbacks(
                         .await;
                         return Ok(());
                     }
-                    if task.listen_channels.is_empty() {
-                        let _ =
-                            send_cleanup_msg(&bot, chat_id, "Please set a channel first.", 5).await;
+                    let Some(channel) = task.channel(channel_id) else {
+                        let _ = send_cleanup_msg(&bot, chat_id, "Please add the channel first.", 5)
+                            .await;
                         return Ok(());
-                    }
-                    let channel_id = task.listen_channels[0];
-                    let handle = user_client_handle.lock().clone();
-                    if let Some(client) = handle {
+                    };
+                    if let Ok(client) = task_client(task).await {
                         match get_chat_admins(&client, channel_id).await {
                             Ok(admins) => {
-                                let selected_users = task.listen_users.clone();
+                                let selected_users = channel.users.clone();
                                 dialogue
                                     .update(State::TaskSelectUsersFromList {
                                         task_name,
@@ -1049,37 +1571,33 @@ pub async fn handle_task_callbacks(
                                 if is_chat_admin_required_error(&e) {
                                     if let Err(mark_err) = mark_channel_without_users(
                                         redis_client.clone(),
-                                        chat_id.0,
+                                        data_chat_id,
                                         &task_name,
+                                        channel_id,
                                     )
                                     .await
                                     {
                                         log::warn!(
                                             "Failed to mark channel without admins chat_id={} task={} err={}",
-                                            chat_id.0,
+                                            data_chat_id,
                                             task_name,
                                             mark_err
                                         );
                                     }
                                     if let Some(updated_task) = get_task_by_name(
                                         redis_client.clone(),
-                                        chat_id.0,
+                                        data_chat_id,
                                         &task_name,
                                     )
                                     .await
                                     {
-                                        let task_text = build_task_detail_text(
-                                            redis_client.clone(),
-                                            chat_id.0,
+                                        render_task_channels_view(
+                                            &bot,
+                                            chat_id,
+                                            message.id,
                                             &updated_task,
-                                            sol_price_state.clone(),
-                                            rpc_clients.clone(),
                                         )
-                                        .await;
-                                        bot.edit_message_text(chat_id, message.id, task_text)
-                                            .parse_mode(teloxide::types::ParseMode::MarkdownV2)
-                                            .reply_markup(task_detail_keyboard(&updated_task))
-                                            .await?;
+                                        .await?;
                                     }
                                     let _ = send_cleanup_msg(
                                         &bot,
@@ -1105,10 +1623,128 @@ pub async fn handle_task_callbacks(
                     }
                 }
             }
-        } else if data.starts_with("task_chan_select_") {
-            let parts: Vec<&str> = data.split('_').collect();
-            let task_name = parts[3].to_string();
-            let selected_channel_id = parts[4].parse::<i64>()?;
+        } else if let Some(payload) = data.strip_prefix("task_chan_topics_") {
+            let Some((task_name, channel_id)) = parse_task_channel_payload(payload) else {
+                return Ok(());
+            };
+            let task_name = task_name.to_string();
+            let Some(task) = get_task_by_name(redis_client.clone(), data_chat_id, &task_name).await
+            else {
+                return Ok(());
+            };
+            let Some(channel) = task.channel(channel_id) else {
+                let _ = send_cleanup_msg(&bot, chat_id, "Please add the channel first.", 5).await;
+                return Ok(());
+            };
+            let client = match task_client(&task).await {
+                Ok(client) => client,
+                Err(e) => {
+                    let _ = send_cleanup_msg(
+                        &bot,
+                        chat_id,
+                        &format!("⚠️ Telegram user client unavailable: {}", e),
+                        5,
+                    )
+                    .await;
+                    return Ok(());
+                }
+            };
+            match get_forum_topics(&client, channel_id).await {
+                Ok(all_topics) => {
+                    let new_state = State::TaskSelectTopicsFromList {
+                        task_name,
+                        menu_message_id: message.id,
+                        channel_id,
+                        all_topics,
+                        selected_topics: channel.topics.iter().map(|topic| topic.id).collect(),
+                        page: 0,
+                    };
+                    dialogue.update(new_state.clone()).await?;
+                    let keyboard = topic_selection_keyboard(&new_state).await.unwrap();
+                    bot.edit_message_text(
+                        chat_id,
+                        message.id,
+                        "Select the forum topics to monitor (selection saves instantly):",
+                    )
+                    .reply_markup(keyboard)
+                    .await?;
+                }
+                Err(e) => {
+                    let _ = send_cleanup_msg(
+                        &bot,
+                        chat_id,
+                        &format!("Error fetching topics: {}", e),
+                        5,
+                    )
+                    .await;
+                }
+            }
+        } else if data.starts_with("task_topic_toggle_")
+            || data.starts_with("task_topic_clear_")
+            || data.starts_with("task_topic_page_")
+        {
+            if let Some(State::TaskSelectTopicsFromList {
+                task_name,
+                menu_message_id,
+                channel_id,
+                all_topics,
+                mut selected_topics,
+                mut page,
+            }) = dialogue.get().await?.clone()
+            {
+                if data.starts_with("task_topic_page_") {
+                    page = data.split('_').last().unwrap().parse::<usize>()?;
+                } else {
+                    if data.starts_with("task_topic_clear_") {
+                        selected_topics.clear();
+                    } else {
+                        let topic_id = data.split('_').last().unwrap().parse::<i32>()?;
+                        if let Some(pos) = selected_topics.iter().position(|&id| id == topic_id) {
+                            selected_topics.remove(pos);
+                        } else {
+                            selected_topics.push(topic_id);
+                        }
+                    }
+                    let topics: Vec<ForumTopic> = selected_topics
+                        .iter()
+                        .map(|id| ForumTopic {
+                            id: *id,
+                            title: all_topics
+                                .iter()
+                                .find(|(_, topic_id)| topic_id == id)
+                                .map(|(title, _)| title.clone())
+                                .unwrap_or_default(),
+                        })
+                        .collect();
+                    update_task_field(redis_client.clone(), data_chat_id, &task_name, |task| {
+                        if let Some(channel) = task.channel_mut(channel_id) {
+                            channel.topics = topics;
+                        }
+                    })
+                    .await?;
+                    bot.answer_callback_query(q.id)
+                        .text("✅ Selection saved.")
+                        .await?;
+                }
+                let new_state = State::TaskSelectTopicsFromList {
+                    task_name,
+                    menu_message_id,
+                    channel_id,
+                    all_topics,
+                    selected_topics,
+                    page,
+                };
+                dialogue.update(new_state.clone()).await?;
+                let keyboard = topic_selection_keyboard(&new_state).await.unwrap();
+                bot.edit_message_reply_markup(chat_id, message.id)
+                    .reply_markup(keyboard)
+                    .await?;
+            }
+        } else if let Some(payload) = data.strip_prefix("task_chan_select_") {
+            let Some((task_name, selected_channel_id)) = parse_task_channel_payload(payload) else {
+                return Ok(());
+            };
+            let task_name = task_name.to_string();
             let mut prompt_message_id_opt: Option<MessageId> = None;
             let mut configuration_message_id_opt: Option<MessageId> = None;
             let mut selected_channel_name = None;
@@ -1135,11 +1771,12 @@ pub async fn handle_task_callbacks(
                 return Ok(());
             }
             let mut con = redis_client.get_multiplexed_async_connection().await?;
-            if let Some(mut user_data) = get_user_data(&mut con, chat_id.0).await? {
-                if let Some(existing) = user_data.tasks.iter().find(|t| {
-                    t.listen_channels.first().copied() == Some(selected_channel_id)
-                        && t.name != task_name
-                }) {
+            if let Some(mut user_data) = get_user_data(&mut con, data_chat_id).await? {
+                if let Some(existing) = user_data
+                    .tasks
+                    .iter()
+                    .find(|t| t.channel(selected_channel_id).is_some() && t.name != task_name)
+                {
                     let _ = send_cleanup_msg(
                         &bot,
                         chat_id,
@@ -1153,29 +1790,26 @@ pub async fn handle_task_callbacks(
                     return Ok(());
                 }
                 if let Some(task) = user_data.tasks.iter_mut().find(|t| t.name == task_name) {
-                    task.listen_channels = vec![selected_channel_id];
-                    task.listen_channel_name = selected_channel_name;
-                    task.listen_users = vec![];
-                    task.listen_usernames = vec![];
-                    task.telegram_channel_is_broadcast = false;
-                }
-                save_user_data(&mut con, chat_id.0, &user_data).await?;
-                if let Some(task) = user_data.tasks.iter().find(|t| t.name == task_name) {
-                    let task_text = build_task_detail_text(
-                        redis_client.clone(),
-                        chat_id.0,
-                        task,
-                        sol_price_state.clone(),
-                        rpc_clients.clone(),
-                    )
-                    .await;
-                    if let Some(configuration_message_id) = configuration_message_id_opt {
-                        bot.edit_message_text(chat_id, configuration_message_id, task_text)
-                            .parse_mode(teloxide::types::ParseMode::MarkdownV2)
-                            .reply_markup(task_detail_keyboard(task))
-                            .await?;
+                    match task.channel_mut(selected_channel_id) {
+                        Some(channel) => {
+                            if selected_channel_name.is_some() {
+                                channel.name = selected_channel_name;
+                            }
+                        }
+                        None => task.channels.push(TelegramChannel::new(
+                            selected_channel_id,
+                            selected_channel_name,
+                        )),
                     }
                 }
+                save_user_data(&mut con, data_chat_id, &user_data).await?;
+                if let (Some(task), Some(configuration_message_id)) = (
+                    user_data.tasks.iter().find(|t| t.name == task_name),
+                    configuration_message_id_opt,
+                ) {
+                    render_task_channels_view(&bot, chat_id, configuration_message_id, task)
+                        .await?;
+                }
             }
             if let Some(prompt_message_id) = prompt_message_id_opt {
                 bot.delete_message(chat_id, prompt_message_id).await.ok();
@@ -1202,10 +1836,14 @@ pub async fn handle_task_callbacks(
                         selected_users.push(user_id_to_toggle);
                     }
                     let mut con = redis_client.get_multiplexed_async_connection().await?;
-                    if let Some(mut user_data) = get_user_data(&mut con, chat_id.0).await? {
-                        if let Some(task) = user_data.tasks.iter_mut().find(|t| t.name == task_name)
+                    if let Some(mut user_data) = get_user_data(&mut con, data_chat_id).await? {
+                        if let Some(channel) = user_data
+                            .tasks
+                            .iter_mut()
+                            .find(|t| t.name == task_name)
+                            .and_then(|task| task.channel_mut(channel_id))
                         {
-                            task.listen_users = selected_users.clone();
+                            channel.users = selected_users.clone();
                             let id_to_display: std::collections::HashMap<i64, String> =
                                 all_users.iter().map(|(n, i, _)| (*i, n.clone())).collect();
                             let mut selected_names: Vec<String> = selected_users
@@ -1213,10 +1851,10 @@ pub async fn handle_task_callbacks(
                                 .filter_map(|id| id_to_display.get(id).cloned())
                                 .collect();
                             selected_names.sort();
-                            task.listen_usernames = selected_names;
-                            task.telegram_channel_is_broadcast = false;
+                            channel.usernames = selected_names;
+                            channel.is_broadcast = false;
                         }
-                        save_user_data(&mut con, chat_id.0, &user_data).await?;
+                        save_user_data(&mut con, data_chat_id, &user_data).await?;
                     }
                     bot.answer_callback_query(q.id)
                         .text("✅ Selection saved.")
@@ -1390,15 +2028,16 @@ async fn render_task_settings_view(
     bot: &Bot,
     redis_client: RedisClient,
     chat_id: ChatId,
+    data_chat_id: i64,
     message_id: MessageId,
     task_name: &str,
     sol_price_state: SolPriceState,
     rpc_clients: RpcClients,
 ) -> HandlerResult {
-    if let Some(task) = get_task_by_name(redis_client.clone(), chat_id.0, task_name).await {
+    if let Some(task) = get_task_by_name(redis_client.clone(), data_chat_id, task_name).await {
         let text = build_task_settings_text(
             redis_client.clone(),
-            chat_id.0,
+            data_chat_id,
             &task,
             sol_price_state.clone(),
             rpc_clients.clone(),
@@ -1416,6 +2055,7 @@ async fn render_task_wallets_view(
     bot: &Bot,
     redis_client: RedisClient,
     chat_id: ChatId,
+    data_chat_id: i64,
     message_id: MessageId,
     task_name: &str,
     wallets: &[BloomWalletInfo],
@@ -1423,7 +2063,7 @@ async fn render_task_wallets_view(
     sol_price_state: SolPriceState,
     rpc_clients: RpcClients,
 ) -> HandlerResult {
-    if let Some(task) = get_task_by_name(redis_client.clone(), chat_id.0, task_name).await {
+    if let Some(task) = get_task_by_name(redis_client.clone(), data_chat_id, task_name).await {
         let selected_address = task
             .bloom_wallet
             .as_ref()
@@ -1471,19 +2111,16 @@ async fn persist_task_wallet_selection(
     Ok(None)
 }
 
-async fn clear_telegram_task_channel_configuration(
+async fn remove_telegram_task_channel(
     redis_client: RedisClient,
     chat_id: i64,
     task_name: &str,
+    channel_id: i64,
 ) -> Result<Option<Task>, Box<dyn std::error::Error + Send + Sync>> {
     let mut con = redis_client.get_multiplexed_async_connection().await?;
     if let Some(mut user_data) = get_user_data(&mut con, chat_id).await? {
         if let Some(task) = user_data.tasks.iter_mut().find(|t| t.name == task_name) {
-            task.listen_channels.clear();
-            task.listen_channel_name = None;
-            task.listen_users.clear();
-            task.listen_usernames.clear();
-            task.telegram_channel_is_broadcast = false;
+            task.channels.retain(|channel| channel.id != channel_id);
             task.active = false;
             let updated_task = task.clone();
             save_user_data(&mut con, chat_id, &user_data).await?;
@@ -1525,8 +2162,8 @@ async fn toggle_task_active(
     Ok(())
 }
 
-fn activation_requirement_error(task: &Task) -> Option<&'static str> {
-    if task.bloom_wallet.is_none() {
+pub fn activation_requirement_error(task: &Task) -> Option<&'static str> {
+    if task.bloom_wallet.is_none() && !task.dry_run {
         return Some("❌ Please assign a Bloom wallet before activating this task.");
     }
 
@@ -1542,15 +2179,17 @@ fn activation_requirement_error(task: &Task) -> Option<&'static str> {
                     "❌ Telegram user session is not configured. Link a Telegram user via the QR login flow before activating this task.",
                 );
             }
-            if task.listen_channels.is_empty() {
-                return Some("❌ Please set a Telegram channel ID before activating this task.");
+            if task.enabled_channels().next().is_none() {
+                return Some(
+                    "❌ Please add and enable a Telegram channel before activating this task.",
+                );
             }
-            if task.listen_users.is_empty()
-                && task.listen_usernames.is_empty()
-                && !task.telegram_channel_is_broadcast
+            if task
+                .enabled_channels()
+                .any(|channel| !channel.has_monitoring_target())
             {
                 return Some(
-                    "❌ Please choose at least one Telegram user to monitor before activating this task.",
+                    "❌ Please choose at least one Telegram user to monitor in every enabled channel before activating this task.",
                 );
             }
         }
@@ -1593,17 +2232,41 @@ async fn mark_channel_without_users(
     redis_client: RedisClient,
     chat_id: i64,
     task_name: &str,
+    channel_id: i64,
 ) -> redis::RedisResult<()> {
     let mut con = redis_client.get_multiplexed_async_connection().await?;
     if let Some(mut user_data) = get_user_data(&mut con, chat_id).await? {
-        if let Some(task) = user_data.tasks.iter_mut().find(|t| t.name == task_name) {
-            task.telegram_channel_is_broadcast = true;
+        if let Some(channel) = user_data
+            .tasks
+            .iter_mut()
+            .find(|t| t.name == task_name)
+            .and_then(|task| task.channel_mut(channel_id))
+        {
+            channel.is_broadcast = true;
             save_user_data(&mut con, chat_id, &user_data).await?;
         }
     }
     Ok(())
 }
 
+fn parse_task_channel_payload(payload: &str) -> Option<(&str, i64)> {
+    let (task_name, channel_id) = payload.rsplit_once('_')?;
+    Some((task_name, channel_id.parse().ok()?))
+}
+
+async fn render_task_channels_view(
+    bot: &Bot,
+    chat_id: ChatId,
+    message_id: MessageId,
+    task: &Task,
+) -> HandlerResult {
+    bot.edit_message_text(chat_id, message_id, generate_task_channels_text(task))
+        .parse_mode(teloxide::types::ParseMode::MarkdownV2)
+        .reply_markup(task_channels_keyboard(task))
+        .await?;
+    Ok(())
+}
+
 async fn toggle_task_inform_only(
     redis_client: RedisClient,
     chat_id: i64,
@@ -1662,6 +2325,78 @@ async fn toggle_task_inform_only(
     Ok(())
 }
 
+async fn toggle_task_safety_checks(
+    redis_client: RedisClient,
+    chat_id: i64,
+    task_name: &str,
+) -> HandlerResult {
+    let mut con = redis_client.get_multiplexed_async_connection().await?;
+    if let Some(mut user_data) = get_user_data(&mut con, chat_id).await? {
+        if let Some(task) = user_data.tasks.iter_mut().find(|t| t.name == *task_name) {
+            task.safety_checks_enabled = !task.safety_checks_enabled;
+            save_user_data(&mut con, chat_id, &user_data).await?;
+        }
+    }
+    Ok(())
+}
+
+async fn update_task_field<F>(
+    redis_client: RedisClient,
+    chat_id: i64,
+    task_name: &str,
+    update: F,
+) -> HandlerResult
+where
+    F: FnOnce(&mut Task),
+{
+    let mut con = redis_client.get_multiplexed_async_connection().await?;
+    if let Some(mut user_data) = get_user_data(&mut con, chat_id).await? {
+        if let Some(task) = user_data.tasks.iter_mut().find(|t| t.name == *task_name) {
+            update(task);
+            save_user_data(&mut con, chat_id, &user_data).await?;
+        }
+    }
+    Ok(())
+}
+
+async fn cycle_task_bloom_account(
+    redis_client: RedisClient,
+    chat_id: i64,
+    task_name: &str,
+) -> HandlerResult {
+    let mut con = redis_client.get_multiplexed_async_connection().await?;
+    if let Some(mut user_data) = get_user_data(&mut con, chat_id).await? {
+        let names: Vec<String> = user_data
+            .bloom_accounts
+            .iter()
+            .map(|account| account.name.clone())
+            .collect();
+        if let Some(task) = user_data.tasks.iter_mut().find(|t| t.name == *task_name) {
+            let next_index = match task.bloom_account.as_deref() {
+                None => 0,
+                Some(current) => names
+                    .iter()
+                    .position(|name| name == current)
+                    .map_or(0, |index| index + 1),
+            };
+            task.bloom_account = names.get(next_index).cloned();
+            save_user_data(&mut con, chat_id, &user_data).await?;
+        }
+    }
+    Ok(())
+}
+
+async fn task_bloom_credentials(
+    redis_client: RedisClient,
+    chat_id: i64,
+    task_name: &str,
+) -> Option<BloomCredentials> {
+    let mut con = redis_client.get_multiplexed_async_connection().await.ok()?;
+    let user_data = get_user_data(&mut con, chat_id).await.ok()??;
+    let task = user_data.tasks.iter().find(|t| t.name == *task_name)?;
+    resolve_bloom_credentials(chat_id, task, &user_data)
+}
+
 async fn delete_task(redis_client: RedisClient, chat_id: i64, task_name: &str) -> HandlerResult {
     let mut con = redis_client.get_multiplexed_async_connection().await?;
     if let Some(mut user_data) = get_user_data(&mut con, chat_id).await? {
@@ -9,13 +9,21 @@ use teloxide::prelude::*;
 
 use crate::application::pricing::SolPriceState;
 use crate::infrastructure::blockchain::RpcClients;
+use crate::infrastructure::blockchain::bloom::ensure_bloom_ws_listener;
+use crate::interfaces::bot::data::access::data_scope;
+use crate::interfaces::bot::handlers::bloom::{
+    parse_bloom_account, render_bloom_accounts_view, upsert_bloom_account,
+};
+use crate::interfaces::bot::tasks::dedup::clear_mint;
+use crate::interfaces::bot::tasks::schedule::{parse_schedule, reset_schedule_state};
 use crate::interfaces::bot::user::client::{
     UserClientHandle, get_token_info_from_bloom, search_dialogs,
 };
+use crate::interfaces::bot::user::sessions::task_client;
 use crate::interfaces::bot::{
-    State, Task, WalletDisplayInfo, channel_selection_keyboard, escape_markdown,
-    generate_task_detail_text, get_user_data, save_user_data, task_detail_keyboard,
-    token_info_keyboard,
+    AutoJoinSettings, RetryPolicy, State, Task, WalletDisplayInfo, channel_selection_keyboard,
+    escape_markdown, generate_task_detail_text, get_user_data, save_user_data, send_cleanup_msg,
+    task_detail_keyboard, token_info_keyboard,
 };
 
 type MyDialogue = Dialogue<State, teloxide::dispatching::dialogue::InMemStorage<State>>;
@@ -239,6 +247,99 @@ pub fn parse_mint_from_text_robust(text: &str) -> Option<String> {
     MINT_REGEX_ROBUST.find(text).map(|m| m.as_str().to_string())
 }
 
+fn parse_duration_secs(text: &str) -> Option<u64> {
+    let trimmed = text.trim().to_lowercase();
+    if trimmed == "0" {
+        return Some(0);
+    }
+    let (value, multiplier) = match trimmed.chars().last()? {
+        's' => (&trimmed[..trimmed.len() - 1], 1),
+        'm' => (&trimmed[..trimmed.len() - 1], 60),
+        'h' => (&trimmed[..trimmed.len() - 1], 3_600),
+        'd' => (&trimmed[..trimmed.len() - 1], 86_400),
+        _ => (trimmed.as_str(), 1),
+    };
+    value
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .and_then(|amount| amount.checked_mul(multiplier))
+}
+
+fn parse_market_filter_limits(text: &str) -> Option<[Option<f64>; 4]> {
+    let trimmed = text.trim();
+    if trimmed.eq_ignore_ascii_case("clear") {
+        return Some([None; 4]);
+    }
+    let parts: Vec<&str> = trimmed.split_whitespace().collect();
+    if parts.len() != 4 {
+        return None;
+    }
+    let mut limits = [None; 4];
+    for (slot, part) in limits.iter_mut().zip(parts) {
+        if part != "-" {
+            let value = part.parse::<f64>().ok()?;
+            if !value.is_finite() || value < 0.0 {
+                return None;
+            }
+            *slot = Some(value);
+        }
+    }
+    Some(limits)
+}
+
+fn parse_auto_join_settings(text: &str, current: &AutoJoinSettings) -> Option<AutoJoinSettings> {
+    let parts: Vec<&str> = text.split_whitespace().collect();
+    if parts.len() != 3 {
+        return None;
+    }
+    let daily_limit = parts[0]
+        .parse::<u32>()
+        .ok()
+        .filter(|value| (1..=50).contains(value))?;
+    let leave_after_secs = parse_duration_secs(parts[1])?;
+    let monitor_joined = match parts[2].to_lowercase().as_str() {
+        "on" | "yes" | "monitor" => true,
+        "off" | "no" => false,
+        _ => return None,
+    };
+    Some(AutoJoinSettings {
+        mode: current.mode,
+        daily_limit,
+        monitor_joined,
+        leave_after_secs,
+    })
+}
+
+fn parse_retry_policy(text: &str) -> Option<RetryPolicy> {
+    let trimmed = text.trim();
+    if trimmed.eq_ignore_ascii_case("off") {
+        return Some(RetryPolicy::default());
+    }
+    let parts: Vec<&str> = trimmed.split_whitespace().collect();
+    if parts.len() != 5 {
+        return None;
+    }
+    let max_attempts = parts[0]
+        .parse::<u32>()
+        .ok()
+        .filter(|value| (1..=10).contains(value))?;
+    let delay_ms = parts[1].parse::<u64>().ok()?;
+    let fee_multiplier = parts[2]
+        .parse::<f64>()
+        .ok()
+        .filter(|value| value.is_finite() && *value >= 1.0 && *value <= 10.0)?;
+    let slippage_step_percent = parts[3].parse::<u32>().ok().filter(|value| *value <= 100)?;
+    let cutoff_secs = parse_duration_secs(parts[4])?;
+    Some(RetryPolicy {
+        max_attempts,
+        delay_ms,
+        fee_multiplier,
+        slippage_step_percent,
+        cutoff_secs,
+    })
+}
+
 pub async fn text_handler(
     bot: Bot,
     msg: Message,
@@ -249,6 +350,7 @@ pub async fn text_handler(
     rpc_clients: RpcClients,
 ) -> HandlerResult {
     let chat_id = msg.chat.id;
+    let data_chat_id = data_scope(msg.from().map(|user| user.id.0), chat_id.0);
     let text = match msg.text() {
         Some(text) => text,
         None => return Ok(()),
@@ -263,7 +365,7 @@ pub async fn text_handler(
                     let final_message = format_token_info_message(
                         mint,
                         &token_info,
-                        chat_id.0,
+                        data_chat_id,
                         redis_client.clone(),
                         sol_price_state.clone(),
                         rpc_clients.clone(),
@@ -295,17 +397,46 @@ pub async fn text_handler(
         }
 
         let mut con = redis_client.get_multiplexed_async_connection().await?;
-        let mut user_data = get_user_data(&mut con, chat_id.0).await?.unwrap();
+        let mut user_data = get_user_data(&mut con, data_chat_id).await?.unwrap();
 
         bot.delete_message(chat_id, msg.id).await.ok();
 
         match state {
+            State::BloomReceiveAccount {
+                menu_message_id,
+                prompt_message_id,
+            } => {
+                bot.delete_message(chat_id, prompt_message_id).await.ok();
+                match parse_bloom_account(text) {
+                    Ok(account) => {
+                        let credentials = account.credentials(data_chat_id);
+                        upsert_bloom_account(&mut user_data, account);
+                        save_user_data(&mut con, data_chat_id, &user_data).await?;
+                        ensure_bloom_ws_listener(credentials);
+                        render_bloom_accounts_view(
+                            &bot,
+                            chat_id,
+                            data_chat_id,
+                            menu_message_id,
+                            &user_data.bloom_accounts,
+                        )
+                        .await?;
+                    }
+                    Err(error) => {
+                        let _ = send_cleanup_msg(&bot, chat_id, &format!("⚠️ {}", error), 6).await;
+                    }
+                }
+                dialogue.update(State::TasksMenu).await?;
+            }
             State::TaskSelectChannelSearch {
                 task_name,
                 menu_message_id,
                 prompt_message_id,
             } => {
-                let handle = user_client_handle.lock().clone();
+                let handle = match user_data.tasks.iter().find(|t| t.name == task_name) {
+                    Some(task) => task_client(task).await.ok(),
+                    None => None,
+                };
                 if let Some(client) = handle {
                     match search_dialogs(&client, text).await {
                         Ok(channels) if !channels.is_empty() => {
@@ -353,12 +484,12 @@ pub async fn text_handler(
                 bot.delete_message(chat_id, prompt_message_id).await.ok();
                 if let Some(task_index) = user_data.tasks.iter().position(|t| t.name == task_name) {
                     user_data.tasks[task_index].name = text.to_string();
-                    save_user_data(&mut con, chat_id.0, &user_data).await?;
+                    save_user_data(&mut con, data_chat_id, &user_data).await?;
 
                     let task = &user_data.tasks[task_index];
                     let task_text = compose_task_detail_text(
                         redis_client.clone(),
-                        chat_id.0,
+                        data_chat_id,
                         task,
                         sol_price_state.clone(),
                         rpc_clients.clone(),
@@ -382,12 +513,12 @@ pub async fn text_handler(
                         user_data.tasks.iter().position(|t| t.name == task_name)
                     {
                         user_data.tasks[task_index].buy_amount_sol = amount;
-                        save_user_data(&mut con, chat_id.0, &user_data).await?;
+                        save_user_data(&mut con, data_chat_id, &user_data).await?;
 
                         let task = &user_data.tasks[task_index];
                         let task_text = compose_task_detail_text(
                             redis_client.clone(),
-                            chat_id.0,
+                            data_chat_id,
                             task,
                             sol_price_state.clone(),
                             rpc_clients.clone(),
@@ -412,12 +543,12 @@ pub async fn text_handler(
                         user_data.tasks.iter().position(|t| t.name == task_name)
                     {
                         user_data.tasks[task_index].buy_priority_fee_sol = fee;
-                        save_user_data(&mut con, chat_id.0, &user_data).await?;
+                        save_user_data(&mut con, data_chat_id, &user_data).await?;
 
                         let task = &user_data.tasks[task_index];
                         let task_text = compose_task_detail_text(
                             redis_client.clone(),
-                            chat_id.0,
+                            data_chat_id,
                             task,
                             sol_price_state.clone(),
                             rpc_clients.clone(),
@@ -442,12 +573,232 @@ pub async fn text_handler(
                         user_data.tasks.iter().position(|t| t.name == task_name)
                     {
                         user_data.tasks[task_index].buy_slippage_percent = slippage;
-                        save_user_data(&mut con, chat_id.0, &user_data).await?;
+                        save_user_data(&mut con, data_chat_id, &user_data).await?;
+
+                        let task = &user_data.tasks[task_index];
+                        let task_text = compose_task_detail_text(
+                            redis_client.clone(),
+                            data_chat_id,
+                            task,
+                            sol_price_state.clone(),
+                            rpc_clients.clone(),
+                        )
+                        .await;
+                        bot.edit_message_text(chat_id, menu_message_id, task_text)
+                            .parse_mode(teloxide::types::ParseMode::MarkdownV2)
+                            .reply_markup(task_detail_keyboard(task))
+                            .await?;
+                    }
+                }
+                dialogue.update(State::TasksMenu).await?;
+            }
+            State::TaskReceiveMarketFilters {
+                task_name,
+                menu_message_id,
+                prompt_message_id,
+            } => {
+                bot.delete_message(chat_id, prompt_message_id).await.ok();
+                if let Some(limits) = parse_market_filter_limits(text) {
+                    if let Some(task_index) =
+                        user_data.tasks.iter().position(|t| t.name == task_name)
+                    {
+                        let task = &mut user_data.tasks[task_index];
+                        task.min_liquidity_sol = limits[0];
+                        task.max_liquidity_sol = limits[1];
+                        task.min_market_cap_sol = limits[2];
+                        task.max_market_cap_sol = limits[3];
+                        save_user_data(&mut con, data_chat_id, &user_data).await?;
+
+                        let task = &user_data.tasks[task_index];
+                        let task_text = compose_task_detail_text(
+                            redis_client.clone(),
+                            data_chat_id,
+                            task,
+                            sol_price_state.clone(),
+                            rpc_clients.clone(),
+                        )
+                        .await;
+                        bot.edit_message_text(chat_id, menu_message_id, task_text)
+                            .parse_mode(teloxide::types::ParseMode::MarkdownV2)
+                            .reply_markup(task_detail_keyboard(task))
+                            .await?;
+                    }
+                } else {
+                    let _ = send_cleanup_msg(
+                        &bot,
+                        chat_id,
+                        "⚠️ Invalid format. Expected four values, e.g. 10 - 50 2000.",
+                        5,
+                    )
+                    .await;
+                }
+                dialogue.update(State::TasksMenu).await?;
+            }
+            State::TaskReceiveProcessorTip {
+                task_name,
+                menu_message_id,
+                prompt_message_id,
+            } => {
+                bot.delete_message(chat_id, prompt_message_id).await.ok();
+                let trimmed = text.trim();
+                let tip = if trimmed.eq_ignore_ascii_case("default") {
+                    Some(None)
+                } else {
+                    trimmed
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|value| value.is_finite() && *value >= 0.0)
+                        .map(Some)
+                };
+                if let Some(tip) = tip {
+                    if let Some(task_index) =
+                        user_data.tasks.iter().position(|t| t.name == task_name)
+                    {
+                        user_data.tasks[task_index].bloom_processor_tip_sol = tip;
+                        save_user_data(&mut con, data_chat_id, &user_data).await?;
+
+                        let task = &user_data.tasks[task_index];
+                        let task_text = compose_task_detail_text(
+                            redis_client.clone(),
+                            data_chat_id,
+                            task,
+                            sol_price_state.clone(),
+                            rpc_clients.clone(),
+                        )
+                        .await;
+                        bot.edit_message_text(chat_id, menu_message_id, task_text)
+                            .parse_mode(teloxide::types::ParseMode::MarkdownV2)
+                            .reply_markup(task_detail_keyboard(task))
+                            .await?;
+                    }
+                }
+                dialogue.update(State::TasksMenu).await?;
+            }
+            State::TaskReceiveDevSell {
+                task_name,
+                menu_message_id,
+                prompt_message_id,
+            } => {
+                bot.delete_message(chat_id, prompt_message_id).await.ok();
+                let trimmed = text.trim();
+                let dev_sell = if trimmed.is_empty() || trimmed.eq_ignore_ascii_case("off") {
+                    None
+                } else {
+                    Some(trimmed.to_string())
+                };
+                if let Some(task_index) = user_data.tasks.iter().position(|t| t.name == task_name) {
+                    user_data.tasks[task_index].bloom_dev_sell = dev_sell;
+                    save_user_data(&mut con, data_chat_id, &user_data).await?;
+
+                    let task = &user_data.tasks[task_index];
+                    let task_text = compose_task_detail_text(
+                        redis_client.clone(),
+                        data_chat_id,
+                        task,
+                        sol_price_state.clone(),
+                        rpc_clients.clone(),
+                    )
+                    .await;
+                    bot.edit_message_text(chat_id, menu_message_id, task_text)
+                        .parse_mode(teloxide::types::ParseMode::MarkdownV2)
+                        .reply_markup(task_detail_keyboard(task))
+                        .await?;
+                }
+                dialogue.update(State::TasksMenu).await?;
+            }
+            State::TaskReceiveSchedule {
+                task_name,
+                menu_message_id,
+                prompt_message_id,
+            } => {
+                bot.delete_message(chat_id, prompt_message_id).await.ok();
+                match parse_schedule(text) {
+                    Ok(schedule) => {
+                        if let Some(task_index) =
+                            user_data.tasks.iter().position(|t| t.name == task_name)
+                        {
+                            user_data.tasks[task_index].schedule = schedule;
+                            save_user_data(&mut con, data_chat_id, &user_data).await?;
+                            reset_schedule_state(data_chat_id, &task_name);
+
+                            let task = &user_data.tasks[task_index];
+                            let task_text = compose_task_detail_text(
+                                redis_client.clone(),
+                                data_chat_id,
+                                task,
+                                sol_price_state.clone(),
+                                rpc_clients.clone(),
+                            )
+                            .await;
+                            bot.edit_message_text(chat_id, menu_message_id, task_text)
+                                .parse_mode(teloxide::types::ParseMode::MarkdownV2)
+                                .reply_markup(task_detail_keyboard(task))
+                                .await?;
+                        }
+                    }
+                    Err(error) => {
+                        let _ = send_cleanup_msg(&bot, chat_id, &format!("⚠️ {}", error), 6).await;
+                    }
+                }
+                dialogue.update(State::TasksMenu).await?;
+            }
+            State::TaskReceiveRetryPolicy {
+                task_name,
+                menu_message_id,
+                prompt_message_id,
+            } => {
+                bot.delete_message(chat_id, prompt_message_id).await.ok();
+                if let Some(policy) = parse_retry_policy(text) {
+                    if let Some(task_index) =
+                        user_data.tasks.iter().position(|t| t.name == task_name)
+                    {
+                        user_data.tasks[task_index].retry_policy = policy;
+                        save_user_data(&mut con, data_chat_id, &user_data).await?;
+
+                        let task = &user_data.tasks[task_index];
+                        let task_text = compose_task_detail_text(
+                            redis_client.clone(),
+                            data_chat_id,
+                            task,
+                            sol_price_state.clone(),
+                            rpc_clients.clone(),
+                        )
+                        .await;
+                        bot.edit_message_text(chat_id, menu_message_id, task_text)
+                            .parse_mode(teloxide::types::ParseMode::MarkdownV2)
+                            .reply_markup(task_detail_keyboard(task))
+                            .await?;
+                    }
+                } else {
+                    let _ = send_cleanup_msg(
+                        &bot,
+                        chat_id,
+                        "⚠️ Invalid retry policy. Use: attempts delay_ms fee_multiplier slippage_step cutoff (e.g., 3 500 1.5 5 30s), or 'off'.",
+                        5,
+                    )
+                    .await;
+                }
+                dialogue.update(State::TasksMenu).await?;
+            }
+            State::TaskReceiveAutoJoin {
+                task_name,
+                menu_message_id,
+                prompt_message_id,
+            } => {
+                bot.delete_message(chat_id, prompt_message_id).await.ok();
+                let task_index = user_data.tasks.iter().position(|t| t.name == task_name);
+                let settings = task_index.and_then(|index| {
+                    parse_auto_join_settings(text, &user_data.tasks[index].auto_join)
+                });
+                match (task_index, settings) {
+                    (Some(task_index), Some(settings)) => {
+                        user_data.tasks[task_index].auto_join = settings;
+                        save_user_data(&mut con, data_chat_id, &user_data).await?;
 
                         let task = &user_data.tasks[task_index];
                         let task_text = compose_task_detail_text(
                             redis_client.clone(),
-                            chat_id.0,
+                            data_chat_id,
                             task,
                             sol_price_state.clone(),
                             rpc_clients.clone(),
@@ -458,9 +809,75 @@ pub async fn text_handler(
                             .reply_markup(task_detail_keyboard(task))
                             .await?;
                     }
+                    (Some(_), None) => {
+                        let _ = send_cleanup_msg(
+                            &bot,
+                            chat_id,
+                            "⚠️ Invalid auto-join settings. Use: daily_limit leave_after monitor (e.g., 5 24h on). Limit is 1-50; leave_after 0 disables auto-leave.",
+                            5,
+                        )
+                        .await;
+                    }
+                    (None, _) => {}
                 }
                 dialogue.update(State::TasksMenu).await?;
             }
+            State::TaskReceiveDedupTtl {
+                task_name,
+                menu_message_id,
+                prompt_message_id,
+            } => {
+                bot.delete_message(chat_id, prompt_message_id).await.ok();
+                if let Some(ttl_secs) = parse_duration_secs(text) {
+                    if let Some(task_index) =
+                        user_data.tasks.iter().position(|t| t.name == task_name)
+                    {
+                        user_data.tasks[task_index].dedup_ttl_secs = ttl_secs;
+                        save_user_data(&mut con, data_chat_id, &user_data).await?;
+
+                        let task = &user_data.tasks[task_index];
+                        let task_text = compose_task_detail_text(
+                            redis_client.clone(),
+                            data_chat_id,
+                            task,
+                            sol_price_state.clone(),
+                            rpc_clients.clone(),
+                        )
+                        .await;
+                        bot.edit_message_text(chat_id, menu_message_id, task_text)
+                            .parse_mode(teloxide::types::ParseMode::MarkdownV2)
+                            .reply_markup(task_detail_keyboard(task))
+                            .await?;
+                    }
+                } else {
+                    let _ = send_cleanup_msg(
+                        &bot,
+                        chat_id,
+                        "⚠️ Invalid duration. Use values like 30m, 24h or 7d.",
+                        5,
+                    )
+                    .await;
+                }
+                dialogue.update(State::TasksMenu).await?;
+            }
+            State::TaskReceiveDedupClear {
+                task_name,
+                menu_message_id: _,
+                prompt_message_id,
+            } => {
+                bot.delete_message(chat_id, prompt_message_id).await.ok();
+                let mint = text.trim();
+                let feedback = if !SOLANA_ADDRESS_REGEX.is_match(mint) {
+                    "⚠️ That does not look like a valid mint address.".to_string()
+                } else {
+                    match clear_mint(data_chat_id, &task_name, mint).await {
+                        Ok(_) => format!("♻️ Mint {} cleared. It can be bought again.", mint),
+                        Err(e) => format!("❌ Failed to clear mint: {}", e),
+                    }
+                };
+                let _ = send_cleanup_msg(&bot, chat_id, &feedback, 5).await;
+                dialogue.update(State::TasksMenu).await?;
+            }
             State::TaskReceiveBlacklist {
                 task_name,
                 menu_message_id,
@@ -470,12 +887,12 @@ pub async fn text_handler(
                 let words: Vec<String> = text.split(',').map(|s| s.trim().to_lowercase()).collect();
                 if let Some(task_index) = user_data.tasks.iter().position(|t| t.name == task_name) {
                     user_data.tasks[task_index].blacklist_words = words;
-                    save_user_data(&mut con, chat_id.0, &user_data).await?;
+                    save_user_data(&mut con, data_chat_id, &user_data).await?;
 
                     let task = &user_data.tasks[task_index];
                     let task_text = compose_task_detail_text(
                         redis_client.clone(),
-                        chat_id.0,
+                        data_chat_id,
                         task,
                         sol_price_state.clone(),
                         rpc_clients.clone(),
@@ -543,12 +960,12 @@ pub async fn text_handler(
                             {
                                 user_data.tasks[task_index].discord_token = Some(token);
                                 user_data.tasks[task_index].discord_username = Some(username);
-                                save_user_data(&mut con, chat_id.0, &user_data).await?;
+                                save_user_data(&mut con, data_chat_id, &user_data).await?;
 
                                 let task = &user_data.tasks[task_index];
                                 let task_text = compose_task_detail_text(
                                     redis_client.clone(),
-                                    chat_id.0,
+                                    data_chat_id,
                                     task,
                                     sol_price_state.clone(),
                                     rpc_clients.clone(),
@@ -583,12 +1000,12 @@ pub async fn text_handler(
                 let channel_id = text.trim().to_string();
                 if let Some(task_index) = user_data.tasks.iter().position(|t| t.name == task_name) {
                     user_data.tasks[task_index].discord_channel_id = Some(channel_id);
-                    save_user_data(&mut con, chat_id.0, &user_data).await?;
+                    save_user_data(&mut con, data_chat_id, &user_data).await?;
 
                     let task = &user_data.tasks[task_index];
                     let task_text = compose_task_detail_text(
                         redis_client.clone(),
-                        chat_id.0,
+                        data_chat_id,
                         task,
                         sol_price_state.clone(),
                         rpc_clients.clone(),
@@ -614,12 +1031,12 @@ pub async fn text_handler(
                     .collect();
                 if let Some(task_index) = user_data.tasks.iter().position(|t| t.name == task_name) {
                     user_data.tasks[task_index].discord_users = usernames;
-                    save_user_data(&mut con, chat_id.0, &user_data).await?;
+                    save_user_data(&mut con, data_chat_id, &user_data).await?;
 
                     let task = &user_data.tasks[task_index];
                     let task_text = compose_task_detail_text(
                         redis_client.clone(),
-                        chat_id.0,
+                        data_chat_id,
                         task,
                         sol_price_state.clone(),
                         rpc_clients.clone(),
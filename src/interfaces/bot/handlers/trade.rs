@@ -1,13 +1,12 @@
-use grammers_client::Client as TelegramClient;
 use parking_lot::Mutex;
 use redis::Client as RedisClient;
 use std::sync::Arc;
 use teloxide::prelude::*;
-use tokio::sync::mpsc::Sender;
 
 use super::text::{format_token_info_message, get_parsed_token_info, parse_mint_from_text_robust};
 use crate::application::pricing::SolPriceState;
 use crate::infrastructure::blockchain::RpcClients;
+use crate::interfaces::bot::data::access::data_scope;
 use crate::interfaces::bot::user::client::UserClientHandle;
 use crate::interfaces::bot::{State, send_cleanup_msg, token_info_keyboard};
 
@@ -22,7 +21,6 @@ pub async fn handle_trade_callback(
     sol_price_state: SolPriceState,
     user_client_handle: Arc<Mutex<Option<UserClientHandle>>>,
     rpc_clients: RpcClients,
-    _client_sender: Sender<TelegramClient>,
 ) -> HandlerResult {
     if let Some(message) = q.message.clone() {
         let chat_id = message.chat.id;
@@ -39,7 +37,7 @@ pub async fn handle_trade_callback(
                         let new_text = format_token_info_message(
                             &mint,
                             &token_info,
-                            chat_id.0,
+                            data_scope(Some(q.from.id.0), chat_id.0),
                             redis_client.clone(),
                             sol_price_state.clone(),
                             rpc_clients.clone(),
@@ -0,0 +1,335 @@
+use crate::infrastructure::database::{
+    RedisResult, decr, get_connection, hgetall_strings, hset_multi, incr_ex,
+};
+use crate::interfaces::bot::tasks::append_task_log;
+use crate::interfaces::bot::user::client::leave_chat;
+use crate::interfaces::bot::user::sessions::task_client;
+use crate::interfaces::bot::{
+    TelegramChannel, UserData, escape_markdown, get_all_user_ids, get_user_data, save_user_data,
+};
+use anyhow::Result;
+use chrono::{Local, TimeZone, Utc};
+use redis::Client as RedisClient;
+use redis::aio::MultiplexedConnection;
+use serde::{Deserialize, Serialize};
+use teloxide::prelude::*;
+use teloxide::types::{ChatId, ParseMode};
+use tokio::time::{Duration, MissedTickBehavior};
+
+const AUTO_LEAVE_INTERVAL: Duration = Duration::from_secs(300);
+const QUOTA_TTL_SECS: u64 = 2 * 86_400;
+const JOIN_LOG_DISPLAY_LIMIT: usize = 20;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AutoJoinRecord {
+    pub chat_id: i64,
+    pub chat_name: String,
+    pub task_name: String,
+    pub invite_hash: String,
+    pub joined_at: i64,
+    #[serde(default)]
+    pub leave_at: Option<i64>,
+    #[serde(default)]
+    pub left_at: Option<i64>,
+    #[serde(default)]
+    pub monitored: bool,
+}
+
+pub struct JoinSlot {
+    key: String,
+}
+
+fn quota_key(account_id: i64) -> String {
+    format!(
+        "autojoin:quota:{}:{}",
+        account_id,
+        Utc::now().format("%Y%m%d")
+    )
+}
+
+fn log_key(chat_id: i64) -> String {
+    format!("autojoin:log:{}", chat_id)
+}
+
+pub async fn reserve_join_slot(account_id: i64, daily_limit: u32) -> RedisResult<Option<JoinSlot>> {
+    let key = quota_key(account_id);
+    let used = incr_ex(&key, QUOTA_TTL_SECS).await?;
+    if used <= daily_limit as i64 {
+        return Ok(Some(JoinSlot { key }));
+    }
+    decr(&key).await?;
+    Ok(None)
+}
+
+pub async fn release_join_slot(slot: JoinSlot) {
+    if let Err(e) = decr(&slot.key).await {
+        log::warn!("autojoin.quota_release_failed key={} err={}", slot.key, e);
+    }
+}
+
+pub async fn record_join(chat_id: i64, record: &AutoJoinRecord) -> Result<()> {
+    hset_multi(
+        &log_key(chat_id),
+        &[(record.chat_id.to_string(), serde_json::to_string(record)?)],
+    )
+    .await?;
+    Ok(())
+}
+
+pub async fn load_join_log(chat_id: i64) -> RedisResult<Vec<AutoJoinRecord>> {
+    let entries = hgetall_strings(&log_key(chat_id)).await?;
+    let mut records: Vec<AutoJoinRecord> = entries
+        .values()
+        .filter_map(|raw| serde_json::from_str(raw).ok())
+        .collect();
+    records.sort_by_key(|record| std::cmp::Reverse(record.joined_at));
+    Ok(records)
+}
+
+pub async fn monitor_joined_chat(
+    chat_id: i64,
+    task_name: &str,
+    channel: TelegramChannel,
+) -> Result<bool> {
+    let redis_url = std::env::var("REDIS_URL")?;
+    let mut con = get_connection(&redis_url).await?;
+    let Some(mut user_data) = get_user_data(&mut con, chat_id).await? else {
+        return Ok(false);
+    };
+    if user_data
+        .tasks
+        .iter()
+        .any(|task| task.channel(channel.id).is_some())
+    {
+        return Ok(false);
+    }
+    let Some(task) = user_data
+        .tasks
+        .iter_mut()
+        .find(|task| task.name == task_name)
+    else {
+        return Ok(false);
+    };
+    task.channels.push(channel);
+    save_user_data(&mut con, chat_id, &user_data).await?;
+    Ok(true)
+}
+
+fn format_timestamp(ts: i64) -> String {
+    Local
+        .timestamp_opt(ts, 0)
+        .single()
+        .map(|time| time.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|| ts.to_string())
+}
+
+pub async fn build_join_log_report(chat_id: i64, task_name: &str) -> String {
+    let heading = format!(
+        "🔗 *{}*",
+        escape_markdown(&format!("Auto-Joined Chats - {}", task_name))
+    );
+    let records = match load_join_log(chat_id).await {
+        Ok(records) => records,
+        Err(e) => {
+            return format!(
+                "{}\n\n{}",
+                heading,
+                escape_markdown(&format!("Failed to load the join log: {}", e))
+            );
+        }
+    };
+    let lines: Vec<String> = records
+        .iter()
+        .filter(|record| record.task_name == task_name)
+        .take(JOIN_LOG_DISPLAY_LIMIT)
+        .map(|record| {
+            let status = match (record.left_at, record.leave_at) {
+                (Some(left_at), _) => format!("left {}", format_timestamp(left_at)),
+                (None, Some(leave_at)) => format!("leaves {}", format_timestamp(leave_at)),
+                (None, None) => "stays joined".to_string(),
+            };
+            let monitoring = if record.monitored { ", monitored" } else { "" };
+            format!(
+                "• `{}` \\(`{}`\\)\n  {}",
+                escape_markdown(&record.chat_name),
+                record.chat_id,
+                escape_markdown(&format!(
+                    "joined {}, {}{}",
+                    format_timestamp(record.joined_at),
+                    status,
+                    monitoring
+                ))
+            )
+        })
+        .collect();
+    if lines.is_empty() {
+        return format!(
+            "{}\n\n{}",
+            heading,
+            escape_markdown("No chats joined via invite links yet.")
+        );
+    }
+    format!("{}\n\n{}", heading, lines.join("\n"))
+}
+
+async fn notify(chat_id: i64, message: String) {
+    let bot = Bot::from_env();
+    if let Err(e) = bot
+        .send_message(ChatId(chat_id), message)
+        .parse_mode(ParseMode::MarkdownV2)
+        .await
+    {
+        log::warn!("autojoin.notify_failed chat_id={} err={}", chat_id, e);
+    }
+}
+
+fn still_monitored_elsewhere(user_data: &UserData, record: &AutoJoinRecord) -> Option<String> {
+    user_data
+        .tasks
+        .iter()
+        .filter(|task| !(record.monitored && task.name == record.task_name))
+        .find(|task| task.channel(record.chat_id).is_some())
+        .map(|task| task.name.clone())
+}
+
+async fn process_user_leaves(
+    con: &mut MultiplexedConnection,
+    chat_id: i64,
+    now: i64,
+) -> Result<()> {
+    let due: Vec<AutoJoinRecord> = load_join_log(chat_id)
+        .await?
+        .into_iter()
+        .filter(|record| record.left_at.is_none() && record.leave_at.is_some_and(|at| at <= now))
+        .collect();
+    if due.is_empty() {
+        return Ok(());
+    }
+    let Some(mut user_data) = get_user_data(con, chat_id).await? else {
+        return Ok(());
+    };
+    let mut changed = false;
+
+    for mut record in due {
+        let task = user_data
+            .tasks
+            .iter()
+            .find(|task| task.name == record.task_name)
+            .cloned();
+        let Some(task) = task else {
+            record.left_at = Some(now);
+            record_join(chat_id, &record).await?;
+            log::warn!(
+                "autojoin.leave_skipped chat_id={} task={} reason=task_missing",
+                chat_id,
+                record.task_name
+            );
+            continue;
+        };
+        if let Some(monitoring_task) = still_monitored_elsewhere(&user_data, &record) {
+            record.leave_at = None;
+            record_join(chat_id, &record).await?;
+            log::info!(
+                "autojoin.leave_skipped chat_id={} task={} target={} reason=monitored_by:{}",
+                chat_id,
+                record.task_name,
+                record.chat_id,
+                monitoring_task
+            );
+            append_task_log(
+                chat_id,
+                &record.task_name,
+                format!(
+                    "Auto-leave of {} ({}) skipped: task '{}' still monitors it",
+                    record.chat_name, record.chat_id, monitoring_task
+                ),
+            );
+            continue;
+        }
+        let outcome = match task_client(&task).await {
+            Ok(client) => leave_chat(&client, record.chat_id).await,
+            Err(e) => Err(e),
+        };
+        match outcome {
+            Ok(was_member) => {
+                record.left_at = Some(now);
+                record_join(chat_id, &record).await?;
+                if record.monitored {
+                    if let Some(task) = user_data
+                        .tasks
+                        .iter_mut()
+                        .find(|task| task.name == record.task_name)
+                    {
+                        let before = task.channels.len();
+                        task.channels.retain(|channel| channel.id != record.chat_id);
+                        changed |= task.channels.len() != before;
+                    }
+                }
+                append_task_log(
+                    chat_id,
+                    &record.task_name,
+                    format!(
+                        "Auto-left {} ({}){}",
+                        record.chat_name,
+                        record.chat_id,
+                        if was_member {
+                            ""
+                        } else {
+                            " - already not a member"
+                        }
+                    ),
+                );
+                notify(
+                    chat_id,
+                    format!(
+                        "👋 *Auto\\-Leave*\n\n*Chat:* `{}`\n*Task:* `{}`",
+                        escape_markdown(&record.chat_name),
+                        escape_markdown(&record.task_name)
+                    ),
+                )
+                .await;
+            }
+            Err(e) => {
+                log::warn!(
+                    "autojoin.leave_failed chat_id={} task={} target={} err=\"{}\"",
+                    chat_id,
+                    record.task_name,
+                    record.chat_id,
+                    e
+                );
+            }
+        }
+    }
+
+    if changed {
+        save_user_data(con, chat_id, &user_data).await?;
+    }
+    Ok(())
+}
+
+async fn process_due_leaves(redis_client: &RedisClient, redis_url: &str) -> Result<()> {
+    let now = Utc::now().timestamp();
+    let user_ids = get_all_user_ids(redis_url).await?;
+    let mut con = redis_client.get_multiplexed_async_connection().await?;
+    for chat_id in user_ids {
+        if let Err(e) = process_user_leaves(&mut con, chat_id, now).await {
+            log::warn!(
+                "autojoin.leave_user_failed chat_id={} err=\"{}\"",
+                chat_id,
+                e
+            );
+        }
+    }
+    Ok(())
+}
+
+pub async fn run_auto_leave_worker(redis_client: RedisClient, redis_url: String) {
+    let mut interval = tokio::time::interval(AUTO_LEAVE_INTERVAL);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        if let Err(e) = process_due_leaves(&redis_client, &redis_url).await {
+            log::warn!("autojoin.leave_worker_failed err=\"{}\"", e);
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use crate::interfaces::bot::tasks::dedup::{claim_mint, release_mint};
+use crate::interfaces::bot::tasks::native::run_native_path;
+use crate::interfaces::bot::tasks::notify::send_notification_markdown;
+use crate::interfaces::bot::tasks::paper::run_paper_path;
+use crate::interfaces::bot::tasks::retry::run_bloom_buy_pipeline;
+use crate::interfaces::bot::tasks::safety::check_safety_before_buy;
+use crate::interfaces::bot::tasks::{resolve_bloom_credentials, resolve_task_wallet};
+use crate::interfaces::bot::{ExecutionMode, Task, UserData};
+use std::time::Instant;
+
+async fn execute_native_buy(
+    chat_id: i64,
+    task: &Task,
+    user_data: &UserData,
+    mint: &str,
+    t_all_start: Instant,
+    source_note: &str,
+    log_event: &impl Fn(String),
+) -> bool {
+    let result = run_native_path(task, user_data, mint, t_all_start, log_event).await;
+    send_notification_markdown(chat_id, result.message + source_note).await;
+    result.bought
+}
+
+async fn execute_live_buy(
+    chat_id: i64,
+    task: &Task,
+    user_data: &UserData,
+    mint: &str,
+    t_all_start: Instant,
+    source_note: &str,
+    log_event: &impl Fn(String),
+) -> bool {
+    if task.execution_mode == ExecutionMode::Native {
+        return execute_native_buy(
+            chat_id,
+            task,
+            user_data,
+            mint,
+            t_all_start,
+            source_note,
+            log_event,
+        )
+        .await;
+    }
+
+    if let Some((wallet_address, wallet_label)) = resolve_task_wallet(task, user_data) {
+        let bloom_credentials = resolve_bloom_credentials(chat_id, task, user_data);
+        let result = run_bloom_buy_pipeline(
+            task,
+            bloom_credentials.as_ref(),
+            &wallet_address,
+            &wallet_label,
+            mint,
+            t_all_start,
+            log_event,
+        )
+        .await;
+        send_notification_markdown(chat_id, result.message + source_note).await;
+        if !result.native_fallback {
+            return result.bought;
+        }
+        log_event(format!("Falling back to native buy for mint {}", mint));
+        return execute_native_buy(
+            chat_id,
+            task,
+            user_data,
+            mint,
+            t_all_start,
+            source_note,
+            log_event,
+        )
+        .await;
+    }
+
+    if task.execution_mode == ExecutionMode::BloomWithNativeFallback {
+        log_event(format!(
+            "No Bloom wallet configured; using native buy for mint {}",
+            mint
+        ));
+        return execute_native_buy(
+            chat_id,
+            task,
+            user_data,
+            mint,
+            t_all_start,
+            source_note,
+            log_event,
+        )
+        .await;
+    }
+
+    let total_us = t_all_start.elapsed().as_micros();
+    log::info!("perf.total_us={} no_bloom_wallet=1", total_us);
+    log_event("Bloom buy skipped: no Bloom wallet configured".to_string());
+    send_notification_markdown(
+        chat_id,
+        "❌ *Buy Error*\n\nNo Bloom wallet configured for this task.".to_string() + source_note,
+    )
+    .await;
+    false
+}
+
+pub async fn run_buy_path(
+    chat_id: i64,
+    task: &Task,
+    user_data: Option<&UserData>,
+    mint: &str,
+    t_all_start: Instant,
+    source_note: &str,
+    log_event: impl Fn(String),
+) {
+    if let Some(blocked) = check_safety_before_buy(task, mint, t_all_start, &log_event).await {
+        send_notification_markdown(chat_id, blocked + source_note).await;
+        return;
+    }
+    if !task.dry_run && user_data.is_none() {
+        log_event(format!(
+            "Buy skipped for mint {}: user data unavailable",
+            mint
+        ));
+        return;
+    }
+
+    let t_dedup = Instant::now();
+    let claim = claim_mint(chat_id, task, mint).await;
+    let dedup_us = t_dedup.elapsed().as_micros();
+    log::info!(
+        "perf.dedup_us={} dup={} scope={} persisted={}",
+        dedup_us,
+        if claim.duplicate { 1 } else { 0 },
+        task.dedup_scope.label(),
+        if claim.persisted { 1 } else { 0 }
+    );
+    log_event(format!(
+        "CA deduplication completed in {:.2} ms (scope: {}, duplicate: {})",
+        (dedup_us as f64 / 1000.0).max(0.01),
+        task.dedup_scope.label(),
+        if claim.duplicate { "yes" } else { "no" }
+    ));
+    if claim.duplicate {
+        log::info!("perf.duplicate_ca=1 mint={}", mint);
+        log_event(format!("Duplicate mint {} ignored", mint));
+        return;
+    }
+
+    let bought = match user_data {
+        Some(user_data) if !task.dry_run => {
+            execute_live_buy(
+                chat_id,
+                task,
+                user_data,
+                mint,
+                t_all_start,
+                source_note,
+                &log_event,
+            )
+            .await
+        }
+        _ => {
+            let result = run_paper_path(chat_id, task, mint, t_all_start, &log_event).await;
+            send_notification_markdown(chat_id, result.message + source_note).await;
+            result.bought
+        }
+    };
+    if !bought {
+        release_mint(&claim).await;
+        log_event(format!(
+            "Dedup claim for mint {} released after the buy failed",
+            mint
+        ));
+    }
+}
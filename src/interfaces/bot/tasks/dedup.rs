@@ -0,0 +1,148 @@
+use crate::infrastructure::database::{RedisResult, del_keys, set_nx_ex};
+use crate::interfaces::bot::data::{DedupScope, Task};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+const FALLBACK_CLAIMS_CAPACITY: usize = 4096;
+
+static FALLBACK_CLAIMS: Lazy<Mutex<HashMap<String, Option<Instant>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+pub struct MintClaim {
+    pub key: String,
+    pub duplicate: bool,
+    pub persisted: bool,
+}
+
+fn namespace(paper: bool) -> &'static str {
+    if paper { "dedup:paper" } else { "dedup" }
+}
+
+fn task_key(paper: bool, chat_id: i64, task_name: &str, mint: &str) -> String {
+    format!(
+        "{}:task:{}:{}:{}",
+        namespace(paper),
+        chat_id,
+        task_name,
+        mint
+    )
+}
+
+fn user_key(paper: bool, chat_id: i64, mint: &str) -> String {
+    format!("{}:user:{}:{}", namespace(paper), chat_id, mint)
+}
+
+fn global_key(paper: bool, mint: &str) -> String {
+    format!("{}:global:{}", namespace(paper), mint)
+}
+
+fn alert_key(chat_id: i64, task_name: &str, mint: &str) -> String {
+    format!("dedup:alert:{}:{}:{}", chat_id, task_name, mint)
+}
+
+fn claim_live(expires_at: Option<Instant>, now: Instant) -> bool {
+    expires_at.is_none_or(|expires_at| expires_at > now)
+}
+
+fn claim_fallback(key: String, ttl_secs: u64) -> bool {
+    let now = Instant::now();
+    let mut claims = FALLBACK_CLAIMS.lock();
+    if claims
+        .get(&key)
+        .is_some_and(|expires_at| claim_live(*expires_at, now))
+    {
+        return false;
+    }
+    if claims.len() >= FALLBACK_CLAIMS_CAPACITY {
+        claims.retain(|_, expires_at| claim_live(*expires_at, now));
+    }
+    if claims.len() >= FALLBACK_CLAIMS_CAPACITY {
+        let mut by_expiry: Vec<(String, Option<Instant>)> = claims.drain().collect();
+        by_expiry.sort_by_key(|(_, expires_at)| {
+            std::cmp::Reverse(expires_at.map_or((1, now), |expires_at| (0, expires_at)))
+        });
+        by_expiry.truncate(FALLBACK_CLAIMS_CAPACITY / 2);
+        claims.extend(by_expiry);
+    }
+    let expires_at = (ttl_secs > 0).then(|| now + Duration::from_secs(ttl_secs));
+    claims.insert(key, expires_at);
+    true
+}
+
+fn dedup_key(chat_id: i64, task: &Task, mint: &str) -> String {
+    match task.dedup_scope {
+        DedupScope::Task => task_key(task.dry_run, chat_id, &task.name, mint),
+        DedupScope::User => user_key(task.dry_run, chat_id, mint),
+        DedupScope::Global => global_key(task.dry_run, mint),
+    }
+}
+
+async fn claim_key(key: String, owner: &str, ttl_secs: u64) -> MintClaim {
+    match set_nx_ex(&key, owner, ttl_secs).await {
+        Ok(claimed) => MintClaim {
+            key,
+            duplicate: !claimed,
+            persisted: true,
+        },
+        Err(e) => {
+            log::warn!("dedup.redis_unavailable=1 err=\"{}\" key={}", e, key);
+            let inserted = claim_fallback(key.clone(), ttl_secs);
+            MintClaim {
+                key,
+                duplicate: !inserted,
+                persisted: false,
+            }
+        }
+    }
+}
+
+pub async fn claim_mint(chat_id: i64, task: &Task, mint: &str) -> MintClaim {
+    let owner = format!("{}:{}", chat_id, task.name);
+    claim_key(dedup_key(chat_id, task, mint), &owner, task.dedup_ttl_secs).await
+}
+
+pub async fn claim_alert(chat_id: i64, task: &Task, mint: &str) -> MintClaim {
+    let owner = format!("{}:{}", chat_id, task.name);
+    claim_key(
+        alert_key(chat_id, &task.name, mint),
+        &owner,
+        task.dedup_ttl_secs,
+    )
+    .await
+}
+
+pub async fn release_mint(claim: &MintClaim) {
+    if claim.duplicate {
+        return;
+    }
+    if !claim.persisted {
+        FALLBACK_CLAIMS.lock().remove(&claim.key);
+        return;
+    }
+    if let Err(e) = del_keys(std::slice::from_ref(&claim.key)).await {
+        log::warn!("dedup.release_failed=1 err=\"{}\" key={}", e, claim.key);
+    }
+}
+
+pub async fn clear_mint(chat_id: i64, task_name: &str, mint: &str) -> RedisResult<u64> {
+    let keys: Vec<String> = [false, true]
+        .into_iter()
+        .flat_map(|paper| {
+            [
+                task_key(paper, chat_id, task_name, mint),
+                user_key(paper, chat_id, mint),
+                global_key(paper, mint),
+            ]
+        })
+        .chain(std::iter::once(alert_key(chat_id, task_name, mint)))
+        .collect();
+    {
+        let mut fallback = FALLBACK_CLAIMS.lock();
+        for key in &keys {
+            fallback.remove(key);
+        }
+    }
+    del_keys(&keys).await
+}
@@ -1,18 +1,18 @@
-use crate::BloomBuyAck;
-use crate::PENDING_BLOOM_RESPONSES;
-use crate::infrastructure::blockchain::bloom_buy;
 use crate::interfaces::bot::escape_markdown;
-use crate::interfaces::bot::tasks::{append_task_log, resolve_task_wallet, state};
+use crate::interfaces::bot::tasks::buy::run_buy_path;
+use crate::interfaces::bot::tasks::dedup::claim_alert;
+use crate::interfaces::bot::tasks::market::{
+    MarketGateDecision, build_market_fallback_block, check_market_before_buy,
+};
+use crate::interfaces::bot::tasks::notify::send_notification_markdown;
+use crate::interfaces::bot::tasks::{append_task_log, state};
 use crate::interfaces::bot::{Task, UserData, log_buffer_to_ca_detection};
 use chrono::Local;
 use futures_util::{SinkExt, StreamExt};
 use serde_json::json;
-use std::collections::HashSet;
 use std::sync::Arc;
 use std::time::Instant;
-use teloxide::prelude::*;
-use teloxide::types::{ChatId, InlineKeyboardButton, InlineKeyboardMarkup, ParseMode};
-use tokio::sync::{Mutex, RwLock, oneshot};
+use tokio::sync::{Mutex, RwLock};
 use tokio::time::{Duration, sleep};
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 
@@ -22,133 +22,6 @@ fn log_task_event(chat_id: i64, task_name: &str, message: impl Into<String>) {
     append_task_log(chat_id, task_name, message);
 }
 
-async fn send_notification_markdown(chat_id: i64, message: String) {
-    let bot = Bot::from_env();
-    let keyboard = InlineKeyboardMarkup::new(vec![vec![InlineKeyboardButton::callback(
-        "🗑️ Remove",
-        "rm",
-    )]]);
-    let t_send_start = Instant::now();
-    let res = bot
-        .send_message(ChatId(chat_id), message.clone())
-        .parse_mode(ParseMode::MarkdownV2)
-        .disable_web_page_preview(true)
-        .reply_markup(keyboard.clone())
-        .await;
-    let send_us = t_send_start.elapsed().as_micros();
-    match res {
-        Ok(_) => {
-            log::info!("perf.telegram_send_us={} chat_id={}", send_us, chat_id);
-        }
-        Err(e) => {
-            let plain = strip_markdown_for_fallback(&message);
-            let t_send2 = Instant::now();
-            let _ = bot
-                .send_message(ChatId(chat_id), plain.clone())
-                .disable_web_page_preview(true)
-                .reply_markup(keyboard)
-                .await;
-            let send2_us = t_send2.elapsed().as_micros();
-            log::warn!(
-                "perf.telegram_send_fallback_us={} err=\"{}\" chat_id={}",
-                send2_us,
-                e,
-                chat_id
-            );
-        }
-    }
-}
-
-fn strip_markdown_for_fallback(s: &str) -> String {
-    let mut t = s
-        .replace("\\|", "|")
-        .replace("\\-", "-")
-        .replace("\\_", "_")
-        .replace("\\*", "*")
-        .replace("\\[", "[")
-        .replace("\\]", "]")
-        .replace("\\(", "(")
-        .replace("\\)", ")")
-        .replace("\\~", "~")
-        .replace("\\`", "`")
-        .replace("\\>", ">")
-        .replace("\\#", "#")
-        .replace("\\+", "+")
-        .replace("\\=", "=")
-        .replace("\\{", "{")
-        .replace("\\}", "}")
-        .replace("\\.", ".")
-        .replace("\\!", "!");
-    t = t.replace("*", "");
-    t
-}
-
-fn build_buy_success_message(
-    mint: &str,
-    amount_sol: f64,
-    api_ms: u128,
-    prio_fee_sol: f64,
-    processor_tip_sol: f64,
-    ack: Option<BloomBuyAck>,
-) -> String {
-    let t0 = Instant::now();
-    let time_str = escape_markdown(&Local::now().format("%H:%M:%S").to_string());
-    let header = format!("🟢 *Buy Successful \\| {}*", time_str);
-
-    let token_line = if let Some(ref a) = ack {
-        if let Some(ref t) = a.token_name {
-            format!("🔹Token: *{}*\n", escape_markdown(t))
-        } else {
-            "".to_string()
-        }
-    } else {
-        "".to_string()
-    };
-
-    let mint_line = format!("🔹`{}`", escape_markdown(mint));
-
-    let amount_str = escape_markdown(&format!("{:.3}", amount_sol));
-    let prio_str = escape_markdown(&format!("{:.3}", prio_fee_sol));
-    let tip_str = escape_markdown(&format!("{:.3}", processor_tip_sol));
-    let value_line = format!(
-        "💰 Value: *{} SOL* \\- Prio Fee: *{} SOL* \\- Processor Tip: *{} SOL*\n",
-        amount_str, prio_str, tip_str
-    );
-
-    let durations_line = if let Some(ref a) = ack {
-        let conf_ms = a.success_time.duration_since(a.pending_time).as_millis();
-        let api_s = escape_markdown(&api_ms.to_string());
-        let conf_s = escape_markdown(&conf_ms.to_string());
-        format!(
-            "⏱️ Durations: *API {} ms* • *Confirmation {} ms*\n",
-            api_s, conf_s
-        )
-    } else {
-        let api_s = escape_markdown(&api_ms.to_string());
-        format!("⏱️ Durations: *API {} ms*\n", api_s)
-    };
-
-    let sig_block = if let Some(ref a) = ack {
-        if let Some(sig) = &a.signature {
-            let sig_esc = escape_markdown(sig);
-            let link = format!("https://solscan.io/tx/{}", sig);
-            format!("\n`{}`\n🔗 [View on Solscan]({})", sig_esc, link)
-        } else {
-            "".to_string()
-        }
-    } else {
-        "".to_string()
-    };
-
-    let s = format!(
-        "{}\n\n{}{}{}\n{}{}",
-        header, token_line, mint_line, "\n", value_line, durations_line
-    ) + &sig_block;
-    let render_us = t0.elapsed().as_micros();
-    log::info!("perf.render_us={} mint={}", render_us, mint);
-    s
-}
-
 async fn process_discord_message(
     message_content: String,
     message_author: String,
@@ -156,7 +29,6 @@ async fn process_discord_message(
     task: Task,
     chat_id: i64,
     user_data_option: Option<UserData>,
-    sent_cas: Arc<Mutex<HashSet<String>>>,
     arrival_ts: Instant,
 ) {
     let hub_queue_us = Instant::now().duration_since(arrival_ts).as_micros();
@@ -272,40 +144,50 @@ async fn process_discord_message(
             format!("Detected potential mint {} from {}", mint, message_author),
         );
 
-        let t_dedup = Instant::now();
-        let mut sent_cas_guard = sent_cas.lock().await;
-        let is_dup = sent_cas_guard.contains(&mint);
-        if !is_dup {
-            sent_cas_guard.insert(mint.clone());
-        }
-        drop(sent_cas_guard);
-        let dedup_us = t_dedup.elapsed().as_micros();
-        log::info!(
-            "perf.dedup_us={} dup={}",
-            dedup_us,
-            if is_dup { 1 } else { 0 }
-        );
-        let dedup_ms = (dedup_us as f64 / 1000.0).max(0.01);
-        log_task_event(
-            chat_id,
-            &task_name,
-            format!(
-                "CA deduplication completed in {:.2} ms (duplicate: {})",
-                dedup_ms,
-                if is_dup { "yes" } else { "no" }
-            ),
-        );
-        if is_dup {
-            log::info!("perf.duplicate_ca=1 mint={}", mint);
+        let market_fallback_reason =
+            match check_market_before_buy(&task, &mint, t_all_start, |message| {
+                log_task_event(chat_id, &task_name, message)
+            })
+            .await
+            {
+                MarketGateDecision::Proceed => None,
+                MarketGateDecision::Skip(message) => {
+                    send_notification_markdown(chat_id, message).await;
+                    return;
+                }
+                MarketGateDecision::InformOnly(reason) => Some(reason),
+            };
+
+        if task.inform_only || market_fallback_reason.is_some() {
+            let t_dedup = Instant::now();
+            let alert = claim_alert(chat_id, &task, &mint).await;
+            let dedup_us = t_dedup.elapsed().as_micros();
+            log::info!(
+                "perf.dedup_us={} dup={} scope=alert persisted={}",
+                dedup_us,
+                if alert.duplicate { 1 } else { 0 },
+                if alert.persisted { 1 } else { 0 }
+            );
+            let dedup_ms = (dedup_us as f64 / 1000.0).max(0.01);
             log_task_event(
                 chat_id,
                 &task_name,
-                format!("Duplicate mint {} ignored", mint),
+                format!(
+                    "Alert deduplication completed in {:.2} ms (duplicate: {})",
+                    dedup_ms,
+                    if alert.duplicate { "yes" } else { "no" }
+                ),
             );
-            return;
-        }
+            if alert.duplicate {
+                log::info!("perf.duplicate_ca=1 mint={}", mint);
+                log_task_event(
+                    chat_id,
+                    &task_name,
+                    format!("Duplicate mint {} ignored", mint),
+                );
+                return;
+            }
 
-        if task.inform_only {
             let time_str = escape_markdown(&Local::now().format("%H:%M:%S").to_string());
             let total_ms = t_all_start.elapsed().as_millis();
 
@@ -333,13 +215,19 @@ async fn process_discord_message(
                 message_content[..end].to_string()
             };
 
+            let fallback_block = market_fallback_reason
+                .as_deref()
+                .map(build_market_fallback_block)
+                .unwrap_or_default();
+
             let notification = format!(
-                "{}\n\n{}\n{}\n{}\n\n{}\n\n📝 *Message Preview*\n```\n{}\n```",
+                "{}\n\n{}\n{}\n{}\n\n{}{}\n\n📝 *Message Preview*\n```\n{}\n```",
                 header,
                 mint_line,
                 channel_line,
                 sender_line,
                 perf_lines,
+                fallback_block,
                 escape_markdown(&preview_text)
             );
 
@@ -363,210 +251,16 @@ async fn process_discord_message(
             );
             send_notification_markdown(chat_id, notification).await;
         } else {
-            if let Some(user_data) = user_data_option {
-                if let Some((wallet_address, wallet_label)) = resolve_task_wallet(&task, &user_data)
-                {
-                    let (tx, rx) = oneshot::channel();
-                    PENDING_BLOOM_RESPONSES.lock().insert(mint.clone(), tx);
-
-                    let api_request_start_time = Instant::now();
-                    let buy_result = bloom_buy(
-                        &mint,
-                        task.buy_amount_sol,
-                        task.buy_slippage_percent,
-                        task.buy_priority_fee_sol,
-                        wallet_address.as_str(),
-                        wallet_label.as_str(),
-                    )
-                    .await;
-                    let api_duration = api_request_start_time.elapsed();
-                    let api_us = api_duration.as_micros();
-                    let api_ms = (api_us as f64 / 1000.0).max(0.01);
-                    log_task_event(
-                        chat_id,
-                        &task_name,
-                        format!(
-                            "Bloom buy initiated for mint {} ({} SOL, slippage {}%)",
-                            mint, task.buy_amount_sol, task.buy_slippage_percent
-                        ),
-                    );
-
-                    match buy_result {
-                        Ok(_) => {
-                            let ack_wait_start = Instant::now();
-                            let bot_response_result =
-                                tokio::time::timeout(Duration::from_secs(20), rx).await;
-                            match bot_response_result {
-                                Ok(Ok(ack)) => {
-                                    let ack_wait_us = ack_wait_start.elapsed().as_micros();
-                                    let signature_opt = ack.signature.clone();
-                                    let msg_text = build_buy_success_message(
-                                        &mint,
-                                        task.buy_amount_sol,
-                                        api_duration.as_millis(),
-                                        task.buy_priority_fee_sol,
-                                        task.buy_priority_fee_sol,
-                                        Some(ack),
-                                    );
-                                    let total_us = t_all_start.elapsed().as_micros();
-                                    log::info!(
-                                        "perf.api_us={} perf.ack_wait_us={} perf.total_us={} mint={}",
-                                        api_us,
-                                        ack_wait_us,
-                                        total_us,
-                                        mint
-                                    );
-                                    let ack_wait_ms = (ack_wait_us as f64 / 1000.0).max(0.01);
-                                    let total_ms = (total_us as f64 / 1000.0).max(0.01);
-                                    log_task_event(
-                                        chat_id,
-                                        &task_name,
-                                        format!(
-                                            "Bloom buy timings -> API: {:.2} ms | ACK: {:.2} ms (success) | Pipeline total: {:.2} ms",
-                                            api_ms, ack_wait_ms, total_ms
-                                        ),
-                                    );
-                                    if let Some(signature) = signature_opt {
-                                        log_task_event(
-                                            chat_id,
-                                            &task_name,
-                                            format!(
-                                                "Bloom buy confirmed for mint {} (signature {})",
-                                                mint, signature
-                                            ),
-                                        );
-                                    } else {
-                                        log_task_event(
-                                            chat_id,
-                                            &task_name,
-                                            format!("Bloom buy confirmed for mint {}", mint),
-                                        );
-                                    }
-                                    send_notification_markdown(chat_id, msg_text).await;
-                                }
-                                Ok(Err(_)) => {
-                                    let ack_wait_us = ack_wait_start.elapsed().as_micros();
-                                    let msg_text = build_buy_success_message(
-                                        &mint,
-                                        task.buy_amount_sol,
-                                        api_duration.as_millis(),
-                                        task.buy_priority_fee_sol,
-                                        task.buy_priority_fee_sol,
-                                        None,
-                                    );
-                                    let total_us = t_all_start.elapsed().as_micros();
-                                    log::info!(
-                                        "perf.api_us={} perf.ack_wait_us={} perf.total_us={} ack_channel_closed=1 mint={}",
-                                        api_us,
-                                        ack_wait_us,
-                                        total_us,
-                                        mint
-                                    );
-                                    let ack_wait_ms = (ack_wait_us as f64 / 1000.0).max(0.01);
-                                    let total_ms = (total_us as f64 / 1000.0).max(0.01);
-                                    log_task_event(
-                                        chat_id,
-                                        &task_name,
-                                        format!(
-                                            "Bloom buy timings -> API: {:.2} ms | ACK: {:.2} ms (channel closed) | Pipeline total: {:.2} ms",
-                                            api_ms, ack_wait_ms, total_ms
-                                        ),
-                                    );
-                                    log_task_event(
-                                        chat_id,
-                                        &task_name,
-                                        format!(
-                                            "Bloom buy finished for mint {} (ACK channel closed)",
-                                            mint
-                                        ),
-                                    );
-                                    send_notification_markdown(chat_id, msg_text).await;
-                                }
-                                Err(_) => {
-                                    let ack_wait_us = ack_wait_start.elapsed().as_micros();
-                                    let msg_text = build_buy_success_message(
-                                        &mint,
-                                        task.buy_amount_sol,
-                                        api_duration.as_millis(),
-                                        task.buy_priority_fee_sol,
-                                        task.buy_priority_fee_sol,
-                                        None,
-                                    );
-                                    let total_us = t_all_start.elapsed().as_micros();
-                                    log::info!(
-                                        "perf.api_us={} perf.ack_wait_us={} perf.total_us={} ack_timeout=1 mint={}",
-                                        api_us,
-                                        ack_wait_us,
-                                        total_us,
-                                        mint
-                                    );
-                                    let ack_wait_ms = (ack_wait_us as f64 / 1000.0).max(0.01);
-                                    let total_ms = (total_us as f64 / 1000.0).max(0.01);
-                                    log_task_event(
-                                        chat_id,
-                                        &task_name,
-                                        format!(
-                                            "Bloom buy timings -> API: {:.2} ms | ACK: {:.2} ms (timeout) | Pipeline total: {:.2} ms",
-                                            api_ms, ack_wait_ms, total_ms
-                                        ),
-                                    );
-                                    log_task_event(
-                                        chat_id,
-                                        &task_name,
-                                        format!(
-                                            "Bloom buy pending for mint {} (ACK timeout)",
-                                            mint
-                                        ),
-                                    );
-                                    send_notification_markdown(chat_id, msg_text).await;
-                                }
-                            };
-                        }
-                        Err(e) => {
-                            PENDING_BLOOM_RESPONSES.lock().remove(&mint);
-                            let total_us = t_all_start.elapsed().as_micros();
-                            log::info!(
-                                "perf.api_us={} perf.total_us={} buy_error=1 err=\"{}\" mint={}",
-                                api_us,
-                                total_us,
-                                e,
-                                mint
-                            );
-                            let total_ms = (total_us as f64 / 1000.0).max(0.01);
-                            log_task_event(
-                                chat_id,
-                                &task_name,
-                                format!(
-                                    "Bloom buy timings -> API: {:.2} ms | Pipeline total: {:.2} ms (failure)",
-                                    api_ms, total_ms
-                                ),
-                            );
-                            log_task_event(
-                                chat_id,
-                                &task_name,
-                                format!("Bloom buy failed for mint {}: {}", mint, e),
-                            );
-                            let error_msg = format!(
-                                "❌ *Buy Request Failed*\n\n*Token:* `{}`\n*Error:* `{}`",
-                                escape_markdown(&mint),
-                                escape_markdown(&e.to_string())
-                            );
-                            send_notification_markdown(chat_id, error_msg).await;
-                        }
-                    }
-                } else {
-                    let total_us = t_all_start.elapsed().as_micros();
-                    log::info!("perf.total_us={} no_bloom_wallet=1", total_us);
-                    let no_wallet_msg =
-                        "❌ *Buy Error*\n\nNo Bloom wallet configured for this task.".to_string();
-                    log_task_event(
-                        chat_id,
-                        &task_name,
-                        "Bloom buy skipped: no Bloom wallet configured".to_string(),
-                    );
-                    send_notification_markdown(chat_id, no_wallet_msg).await;
-                }
-            }
+            run_buy_path(
+                chat_id,
+                &task,
+                user_data_option.as_ref(),
+                &mint,
+                t_all_start,
+                "",
+                |message| log_task_event(chat_id, &task_name, message),
+            )
+            .await;
         }
     } else {
         let total_us = t_all_start.elapsed().as_micros();
@@ -597,7 +291,6 @@ pub async fn start_task_monitor(initial_task: Task, chat_id: i64) {
     let user_data_state = state::get_user_data_state(chat_id);
 
     tokio::spawn(async move {
-        let sent_cas = Arc::new(Mutex::new(HashSet::<String>::new()));
         log::info!(
             "task.discord: worker start user_chat={} task={} session={}",
             chat_id,
@@ -669,7 +362,6 @@ pub async fn start_task_monitor(initial_task: Task, chat_id: i64) {
                 user_data_state.clone(),
                 chat_id,
                 session_id,
-                Arc::clone(&sent_cas),
             )
             .await
             {
@@ -714,7 +406,6 @@ async fn connect_discord_gateway(
     user_data_state: Option<Arc<RwLock<UserData>>>,
     chat_id: i64,
     session_id: uuid::Uuid,
-    sent_cas: Arc<Mutex<HashSet<String>>>,
 ) -> Result<(), String> {
     let (ws_stream, _) = connect_async("wss://gateway.discord.gg/?v=10&encoding=json")
         .await
@@ -852,7 +543,6 @@ async fn connect_discord_gateway(
                             Some(state) => Some(state.read().await.clone()),
                             None => None,
                         };
-                        let sent_cas_clone = Arc::clone(&sent_cas);
                         let channel_id_str = msg_channel_id.to_string();
 
                         tokio::spawn(process_discord_message(
@@ -862,7 +552,6 @@ async fn connect_discord_gateway(
                             task_clone,
                             chat_id,
                             user_data_option,
-                            sent_cas_clone,
                             arrival_ts,
                         ));
                     }
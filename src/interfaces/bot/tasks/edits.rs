@@ -0,0 +1,36 @@
+use clru::CLruCache;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::num::NonZeroUsize;
+use std::time::{Duration, Instant};
+
+const MESSAGE_TEXT_TTL: Duration = Duration::from_secs(60 * 60);
+const MESSAGE_TEXT_CAPACITY: NonZeroUsize = NonZeroUsize::new(4096).unwrap();
+
+type MessageKey = (i64, String, i64, i32);
+
+static MESSAGE_TEXTS: Lazy<Mutex<CLruCache<MessageKey, (u64, Instant)>>> =
+    Lazy::new(|| Mutex::new(CLruCache::new(MESSAGE_TEXT_CAPACITY)));
+
+pub fn message_text_changed(
+    chat_id: i64,
+    task_name: &str,
+    message_chat_id: i64,
+    message_id: i32,
+    text: &str,
+) -> bool {
+    let mut hasher = DefaultHasher::new();
+    text.hash(&mut hasher);
+    let digest = hasher.finish();
+    let now = Instant::now();
+
+    let key = (chat_id, task_name.to_string(), message_chat_id, message_id);
+    match MESSAGE_TEXTS.lock().put(key, (digest, now)) {
+        Some((previous, seen_at)) => {
+            previous != digest || now.duration_since(seen_at) >= MESSAGE_TEXT_TTL
+        }
+        None => true,
+    }
+}
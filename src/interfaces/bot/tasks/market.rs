@@ -0,0 +1,148 @@
+use crate::RPC_CLIENTS;
+use crate::infrastructure::blockchain::{MARKET_FILTER_BUDGET, PoolSnapshot, fetch_pool_snapshot};
+use crate::interfaces::bot::{Task, escape_markdown};
+use std::time::Instant;
+
+pub enum MarketGateOutcome {
+    Passed(PoolSnapshot),
+    Rejected(Option<PoolSnapshot>, String),
+    Unavailable(String),
+}
+
+pub enum MarketGateDecision {
+    Proceed,
+    Skip(String),
+    InformOnly(String),
+}
+
+fn check_bounds(label: &str, value: f64, min: Option<f64>, max: Option<f64>) -> Option<String> {
+    if let Some(min) = min {
+        if value < min {
+            return Some(format!(
+                "{} {:.2} SOL below minimum {} SOL",
+                label, value, min
+            ));
+        }
+    }
+    if let Some(max) = max {
+        if value > max {
+            return Some(format!(
+                "{} {:.2} SOL above maximum {} SOL",
+                label, value, max
+            ));
+        }
+    }
+    None
+}
+
+pub async fn run_market_gate(task: &Task, mint: &str) -> MarketGateOutcome {
+    match fetch_pool_snapshot(&RPC_CLIENTS, mint, MARKET_FILTER_BUDGET).await {
+        Ok(None) => {
+            log::info!("market_filter_no_pool=1 mint={}", mint);
+            MarketGateOutcome::Rejected(None, "No SOL-paired pool found".to_string())
+        }
+        Ok(Some(snapshot)) => {
+            log::info!(
+                "perf.market_filter_ms={} pool={} liquidity_sol={:.2} mcap_sol={:.2} mint={}",
+                snapshot.elapsed_ms,
+                snapshot.source.label(),
+                snapshot.liquidity_sol,
+                snapshot.market_cap_sol,
+                mint
+            );
+            let rejection = check_bounds(
+                "Liquidity",
+                snapshot.liquidity_sol,
+                task.min_liquidity_sol,
+                task.max_liquidity_sol,
+            )
+            .or_else(|| {
+                check_bounds(
+                    "Market cap",
+                    snapshot.market_cap_sol,
+                    task.min_market_cap_sol,
+                    task.max_market_cap_sol,
+                )
+            });
+            match rejection {
+                Some(reason) => MarketGateOutcome::Rejected(Some(snapshot), reason),
+                None => MarketGateOutcome::Passed(snapshot),
+            }
+        }
+        Err(e) => {
+            log::warn!("market_filter_unavailable=1 err=\"{}\" mint={}", e, mint);
+            MarketGateOutcome::Unavailable(e.to_string())
+        }
+    }
+}
+
+pub async fn check_market_before_buy(
+    task: &Task,
+    mint: &str,
+    t_all_start: Instant,
+    log_event: impl Fn(String),
+) -> MarketGateDecision {
+    if task.inform_only || !task.has_market_filters() {
+        return MarketGateDecision::Proceed;
+    }
+    match run_market_gate(task, mint).await {
+        MarketGateOutcome::Passed(snapshot) => {
+            log_event(format!(
+                "Market filters passed for mint {} ({} liquidity {:.2} SOL, market cap {:.2} SOL) in {} ms",
+                mint,
+                snapshot.source.label(),
+                snapshot.liquidity_sol,
+                snapshot.market_cap_sol,
+                snapshot.elapsed_ms
+            ));
+            MarketGateDecision::Proceed
+        }
+        MarketGateOutcome::Rejected(snapshot, reason) => {
+            let total_us = t_all_start.elapsed().as_micros();
+            log::info!("perf.total_us={} market_rejected=1 mint={}", total_us, mint);
+            log_event(format!("Buy skipped for mint {}: {}", mint, reason));
+            MarketGateDecision::Skip(build_market_rejected_message(
+                mint,
+                snapshot.as_ref(),
+                &reason,
+            ))
+        }
+        MarketGateOutcome::Unavailable(reason) => {
+            log_event(format!(
+                "Market filters unavailable for mint {} ({}); falling back to inform only",
+                mint, reason
+            ));
+            MarketGateDecision::InformOnly(reason)
+        }
+    }
+}
+
+pub fn build_market_rejected_message(
+    mint: &str,
+    snapshot: Option<&PoolSnapshot>,
+    reason: &str,
+) -> String {
+    let Some(snapshot) = snapshot else {
+        return format!(
+            "📉 *Buy Skipped by Market Filters*\n\n*Token:* `{}`\n\n{}",
+            escape_markdown(mint),
+            escape_markdown(reason)
+        );
+    };
+    format!(
+        "📉 *Buy Skipped by Market Filters*\n\n*Token:* `{}`\n*Pool:* `{}`\n*Liquidity:* `{}`\n*Market Cap:* `{}`\n\n{}",
+        escape_markdown(mint),
+        escape_markdown(snapshot.source.label()),
+        escape_markdown(&format!("{:.2} SOL", snapshot.liquidity_sol)),
+        escape_markdown(&format!("{:.2} SOL", snapshot.market_cap_sol)),
+        escape_markdown(reason)
+    )
+}
+
+pub fn build_market_fallback_block(reason: &str) -> String {
+    format!(
+        "\n\n⏱️ *Market Filters Unavailable*\n`{}`\n{}",
+        escape_markdown(reason),
+        escape_markdown("Buy skipped; alert sent as inform only.")
+    )
+}
@@ -1,12 +1,30 @@
+pub mod autojoin;
+pub mod buy;
+pub mod dedup;
 pub mod discord;
+pub mod edits;
+pub mod market;
+pub mod native;
+pub mod notify;
+pub mod paper;
+pub mod resume;
+pub mod retry;
+pub mod safety;
+pub mod schedule;
 pub mod scraper;
 pub mod state;
 pub mod tg;
 
+use crate::infrastructure::blockchain::BloomCredentials;
 use crate::interfaces::bot::data::{Task, UserData};
 
 pub use state::{append_task_log, subscribe_task_logs};
 
+pub struct BuyPathResult {
+    pub message: String,
+    pub bought: bool,
+}
+
 pub fn resolve_task_wallet(task: &Task, _user_data: &UserData) -> Option<(String, String)> {
     task.bloom_wallet.as_ref().map(|wallet| {
         let label = wallet
@@ -18,3 +36,19 @@ pub fn resolve_task_wallet(task: &Task, _user_data: &UserData) -> Option<(String
         (wallet.address.clone(), label.to_string())
     })
 }
+
+pub fn resolve_bloom_credentials(
+    chat_id: i64,
+    task: &Task,
+    user_data: &UserData,
+) -> Option<BloomCredentials> {
+    let selected = task
+        .bloom_account
+        .as_deref()
+        .and_then(|name| user_data.bloom_account(name))
+        .or_else(|| user_data.bloom_accounts.first());
+    match selected {
+        Some(account) => Some(account.credentials(chat_id)),
+        None => BloomCredentials::from_env(),
+    }
+}
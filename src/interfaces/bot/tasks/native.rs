@@ -0,0 +1,169 @@
+use crate::infrastructure::blockchain::{
+    NativeBuyResult, execute_native_buy, keypair_from_base58, native_rpc_client,
+};
+use crate::interfaces::bot::tasks::{BuyPathResult, resolve_task_wallet};
+use crate::interfaces::bot::{Task, UserData, escape_markdown};
+use anyhow::{Result, anyhow};
+use chrono::Local;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::signature::Keypair;
+use solana_sdk::signer::Signer;
+use std::time::Instant;
+
+pub fn resolve_native_signer(task: &Task, user_data: &UserData) -> Result<Keypair> {
+    let (address, label) = resolve_task_wallet(task, user_data)
+        .ok_or_else(|| anyhow!("Assign a wallet to this task before using native execution"))?;
+    let wallet = user_data
+        .wallets
+        .iter()
+        .find(|wallet| wallet.public_key == address)
+        .ok_or_else(|| {
+            anyhow!(
+                "{} ({}) has no local signing key; import its private key under Wallets",
+                label,
+                address
+            )
+        })?;
+    let payer = keypair_from_base58(&wallet.private_key)?;
+    if payer.pubkey().to_string() != address {
+        return Err(anyhow!(
+            "Local key for {} does not match wallet address {}",
+            label,
+            address
+        ));
+    }
+    Ok(payer)
+}
+
+pub async fn run_native_buy(
+    task: &Task,
+    user_data: &UserData,
+    mint: &str,
+) -> Result<NativeBuyResult> {
+    let payer = resolve_native_signer(task, user_data)?;
+    let rpc = native_rpc_client();
+    execute_native_buy(
+        &rpc,
+        &payer,
+        mint,
+        task.buy_amount_sol,
+        task.buy_slippage_percent,
+        task.buy_priority_fee_sol,
+    )
+    .await
+}
+
+pub async fn run_native_path(
+    task: &Task,
+    user_data: &UserData,
+    mint: &str,
+    t_all_start: Instant,
+    log_event: impl Fn(String),
+) -> BuyPathResult {
+    let native_start = Instant::now();
+    let result = run_native_buy(task, user_data, mint).await;
+    let native_us = native_start.elapsed().as_micros();
+    let total_us = t_all_start.elapsed().as_micros();
+    match result {
+        Ok(result) => {
+            log::info!(
+                "perf.native_us={} perf.total_us={} route={} confirmed={} mint={}",
+                native_us,
+                total_us,
+                result.route.label(),
+                if result.confirmed { 1 } else { 0 },
+                mint
+            );
+            log_event(format!(
+                "Native buy {} for mint {} via {} (signature {})",
+                if result.confirmed {
+                    "confirmed"
+                } else {
+                    "sent"
+                },
+                mint,
+                result.route.label(),
+                result.signature
+            ));
+            BuyPathResult {
+                message: build_native_buy_message(
+                    mint,
+                    task.buy_amount_sol,
+                    task.buy_priority_fee_sol,
+                    native_us / 1000,
+                    &result,
+                ),
+                bought: true,
+            }
+        }
+        Err(e) => {
+            log::info!(
+                "perf.native_us={} perf.total_us={} native_error=1 err=\"{}\" mint={}",
+                native_us,
+                total_us,
+                e,
+                mint
+            );
+            log_event(format!("Native buy failed for mint {}: {}", mint, e));
+            BuyPathResult {
+                message: build_native_buy_failed_message(mint, &e.to_string()),
+                bought: false,
+            }
+        }
+    }
+}
+
+pub fn build_native_buy_message(
+    mint: &str,
+    amount_sol: f64,
+    prio_fee_sol: f64,
+    elapsed_ms: u128,
+    result: &NativeBuyResult,
+) -> String {
+    let time_str = escape_markdown(&Local::now().format("%H:%M:%S").to_string());
+    let header = if result.confirmed {
+        format!("🟢 *Native Buy Confirmed \\| {}*", time_str)
+    } else {
+        format!("🟡 *Native Buy Sent \\| {}*", time_str)
+    };
+    let value_line = format!(
+        "💰 Value: *{} SOL* \\- Prio Fee: *{} SOL*",
+        escape_markdown(&format!("{:.3}", amount_sol)),
+        escape_markdown(&format!("{:.3}", prio_fee_sol))
+    );
+    let route_line = format!("🛣️ Route: *{}*", escape_markdown(result.route.label()));
+    let quote_line = format!(
+        "🪙 Quoted: *{} tokens* \\- Max Cost: *{} SOL*",
+        escape_markdown(&format!(
+            "{:.2}",
+            result.tokens_out as f64 / 10f64.powi(result.token_decimals as i32)
+        )),
+        escape_markdown(&format!(
+            "{:.4}",
+            result.max_sol_cost_lamports as f64 / LAMPORTS_PER_SOL as f64
+        ))
+    );
+    let durations_line = format!(
+        "⏱️ Durations: *Total {} ms*",
+        escape_markdown(&elapsed_ms.to_string())
+    );
+    format!(
+        "{}\n\n🔹`{}`\n\n{}\n{}\n{}\n{}\n\n`{}`\n🔗 [View on Solscan](https://solscan.io/tx/{})",
+        header,
+        escape_markdown(mint),
+        value_line,
+        route_line,
+        quote_line,
+        durations_line,
+        escape_markdown(&result.signature),
+        result.signature
+    )
+}
+
+pub fn build_native_buy_failed_message(mint: &str, error: &str) -> String {
+    format!(
+        "❌ *Native Buy Failed*\n\n*Token:* `{}`\n*Error:* `{}`",
+        escape_markdown(mint),
+        escape_markdown(error)
+    )
+}
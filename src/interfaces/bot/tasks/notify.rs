@@ -0,0 +1,64 @@
+use std::time::Instant;
+use teloxide::prelude::*;
+use teloxide::types::{ChatId, InlineKeyboardButton, InlineKeyboardMarkup, ParseMode};
+
+pub async fn send_notification_markdown(chat_id: i64, message: String) {
+    let bot = Bot::from_env();
+    let keyboard = InlineKeyboardMarkup::new(vec![vec![InlineKeyboardButton::callback(
+        "🗑️ Remove",
+        "rm",
+    )]]);
+    let t_send_start = Instant::now();
+    let res = bot
+        .send_message(ChatId(chat_id), message.clone())
+        .parse_mode(ParseMode::MarkdownV2)
+        .disable_web_page_preview(true)
+        .reply_markup(keyboard.clone())
+        .await;
+    let send_us = t_send_start.elapsed().as_micros();
+    match res {
+        Ok(_) => {
+            log::info!("perf.telegram_send_us={} chat_id={}", send_us, chat_id);
+        }
+        Err(e) => {
+            let plain = strip_markdown_for_fallback(&message);
+            let t_send2 = Instant::now();
+            let _ = bot
+                .send_message(ChatId(chat_id), plain.clone())
+                .disable_web_page_preview(true)
+                .reply_markup(keyboard)
+                .await;
+            let send2_us = t_send2.elapsed().as_micros();
+            log::warn!(
+                "perf.telegram_send_fallback_us={} err=\"{}\" chat_id={}",
+                send2_us,
+                e,
+                chat_id
+            );
+        }
+    }
+}
+
+fn strip_markdown_for_fallback(s: &str) -> String {
+    let mut t = s
+        .replace("\\|", "|")
+        .replace("\\-", "-")
+        .replace("\\_", "_")
+        .replace("\\*", "*")
+        .replace("\\[", "[")
+        .replace("\\]", "]")
+        .replace("\\(", "(")
+        .replace("\\)", ")")
+        .replace("\\~", "~")
+        .replace("\\`", "`")
+        .replace("\\>", ">")
+        .replace("\\#", "#")
+        .replace("\\+", "+")
+        .replace("\\=", "=")
+        .replace("\\{", "{")
+        .replace("\\}", "}")
+        .replace("\\.", ".")
+        .replace("\\!", "!");
+    t = t.replace("*", "");
+    t
+}
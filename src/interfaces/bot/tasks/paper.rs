@@ -0,0 +1,283 @@
+use crate::RPC_CLIENTS;
+use crate::infrastructure::blockchain::{MARKET_FILTER_BUDGET, PoolSnapshot, fetch_pool_snapshot};
+use crate::infrastructure::database::{RedisResult, del_keys, hgetall_strings, hset_multi};
+use crate::interfaces::bot::tasks::BuyPathResult;
+use crate::interfaces::bot::{Task, escape_markdown};
+use anyhow::{Result, anyhow};
+use chrono::{Local, TimeZone, Utc};
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PaperPosition {
+    pub mint: String,
+    pub pool: String,
+    pub sol_in: f64,
+    pub tokens: f64,
+    pub entry_price_sol: f64,
+    pub price_impact_percent: f64,
+    pub opened_at: i64,
+}
+
+fn positions_key(chat_id: i64, task_name: &str) -> String {
+    format!("paper:{}:{}", chat_id, task_name)
+}
+
+fn simulate_buy(snapshot: &PoolSnapshot, sol_in: f64) -> Option<(f64, f64)> {
+    let tokens = snapshot.token_reserve * sol_in / (snapshot.sol_reserve + sol_in);
+    if tokens <= 0.0 || snapshot.price_sol <= 0.0 {
+        return None;
+    }
+    let fill_price = sol_in / tokens;
+    let impact = (fill_price / snapshot.price_sol - 1.0) * 100.0;
+    Some((tokens, impact))
+}
+
+fn simulate_sell(snapshot: &PoolSnapshot, tokens: f64) -> f64 {
+    snapshot.sol_reserve * tokens / (snapshot.token_reserve + tokens)
+}
+
+pub async fn load_positions(chat_id: i64, task_name: &str) -> RedisResult<Vec<PaperPosition>> {
+    let entries = hgetall_strings(&positions_key(chat_id, task_name)).await?;
+    let mut positions: Vec<PaperPosition> = entries
+        .values()
+        .filter_map(|raw| serde_json::from_str(raw).ok())
+        .collect();
+    positions.sort_by_key(|position| position.opened_at);
+    Ok(positions)
+}
+
+pub async fn record_paper_fill(chat_id: i64, task: &Task, mint: &str) -> Result<PaperPosition> {
+    let snapshot = fetch_pool_snapshot(&RPC_CLIENTS, mint, MARKET_FILTER_BUDGET)
+        .await?
+        .ok_or_else(|| anyhow!("No SOL-paired pool found for mint {}", mint))?;
+    let (tokens, impact) = simulate_buy(&snapshot, task.buy_amount_sol)
+        .ok_or_else(|| anyhow!("Pool for mint {} cannot fill a simulated buy", mint))?;
+    if impact > task.buy_slippage_percent as f64 {
+        return Err(anyhow!(
+            "Price impact {:.2}% exceeds slippage limit {}%",
+            impact,
+            task.buy_slippage_percent
+        ));
+    }
+
+    let key = positions_key(chat_id, &task.name);
+    let existing = load_positions(chat_id, &task.name)
+        .await?
+        .into_iter()
+        .find(|position| position.mint == mint);
+    let position = match existing {
+        Some(previous) => {
+            let sol_in = previous.sol_in + task.buy_amount_sol;
+            let total_tokens = previous.tokens + tokens;
+            PaperPosition {
+                sol_in,
+                tokens: total_tokens,
+                entry_price_sol: sol_in / total_tokens,
+                price_impact_percent: impact,
+                pool: snapshot.source.label().to_string(),
+                ..previous
+            }
+        }
+        None => PaperPosition {
+            mint: mint.to_string(),
+            pool: snapshot.source.label().to_string(),
+            sol_in: task.buy_amount_sol,
+            tokens,
+            entry_price_sol: task.buy_amount_sol / tokens,
+            price_impact_percent: impact,
+            opened_at: Utc::now().timestamp(),
+        },
+    };
+    hset_multi(
+        &key,
+        &[(mint.to_string(), serde_json::to_string(&position)?)],
+    )
+    .await?;
+    Ok(position)
+}
+
+pub async fn reset_paper_positions(chat_id: i64, task_name: &str) -> RedisResult<u64> {
+    del_keys(&[positions_key(chat_id, task_name)]).await
+}
+
+pub async fn run_paper_path(
+    chat_id: i64,
+    task: &Task,
+    mint: &str,
+    t_all_start: Instant,
+    log_event: impl Fn(String),
+) -> BuyPathResult {
+    let paper_start = Instant::now();
+    let result = record_paper_fill(chat_id, task, mint).await;
+    let paper_us = paper_start.elapsed().as_micros();
+    let total_us = t_all_start.elapsed().as_micros();
+    match result {
+        Ok(position) => {
+            log::info!(
+                "perf.paper_us={} perf.total_us={} mode=dry_run pool={} mint={}",
+                paper_us,
+                total_us,
+                position.pool,
+                mint
+            );
+            log_event(format!(
+                "Dry run fill recorded for mint {}: {:.3} SOL -> {:.2} tokens at {:.10} SOL ({} pool, impact {:.2}%)",
+                mint,
+                task.buy_amount_sol,
+                position.tokens,
+                position.entry_price_sol,
+                position.pool,
+                position.price_impact_percent
+            ));
+            BuyPathResult {
+                message: build_paper_fill_message(&position, total_us / 1000),
+                bought: true,
+            }
+        }
+        Err(e) => {
+            log::info!(
+                "perf.paper_us={} perf.total_us={} mode=dry_run paper_error=1 err=\"{}\" mint={}",
+                paper_us,
+                total_us,
+                e,
+                mint
+            );
+            log_event(format!("Dry run fill failed for mint {}: {}", mint, e));
+            BuyPathResult {
+                message: build_paper_fill_failed_message(mint, &e.to_string()),
+                bought: false,
+            }
+        }
+    }
+}
+
+pub fn build_paper_fill_message(position: &PaperPosition, elapsed_ms: u128) -> String {
+    let time_str = escape_markdown(&Local::now().format("%H:%M:%S").to_string());
+    format!(
+        concat!(
+            "📝 *Dry Run Buy \\| {}*\n\n",
+            "🔹`{}`\n\n",
+            "💰 Value: *{} SOL* \\- Pool: *{}*\n",
+            "🪙 Tokens: *{}* \\- Entry: *{} SOL*\n",
+            "📈 Price Impact: *{}%*\n",
+            "⏱️ Durations: *Total {} ms*\n\n",
+            "{}"
+        ),
+        time_str,
+        escape_markdown(&position.mint),
+        escape_markdown(&format!("{:.3}", position.sol_in)),
+        escape_markdown(&position.pool),
+        escape_markdown(&format!("{:.2}", position.tokens)),
+        escape_markdown(&format!("{:.10}", position.entry_price_sol)),
+        escape_markdown(&format!("{:.2}", position.price_impact_percent)),
+        escape_markdown(&elapsed_ms.to_string()),
+        escape_markdown("Simulated fill only; no transaction was sent.")
+    )
+}
+
+pub fn build_paper_fill_failed_message(mint: &str, error: &str) -> String {
+    format!(
+        "❌ *Dry Run Buy Failed*\n\n*Token:* `{}`\n*Error:* `{}`",
+        escape_markdown(mint),
+        escape_markdown(error)
+    )
+}
+
+pub async fn build_paper_pnl_report(chat_id: i64, task_name: &str) -> String {
+    let heading = format!(
+        "📊 *{}*",
+        escape_markdown(&format!("Paper PnL - {}", task_name))
+    );
+    let positions = match load_positions(chat_id, task_name).await {
+        Ok(positions) => positions,
+        Err(e) => {
+            return format!(
+                "{}\n\n{}",
+                heading,
+                escape_markdown(&format!("Failed to load paper positions: {}", e))
+            );
+        }
+    };
+    if positions.is_empty() {
+        return format!(
+            "{}\n\n{}",
+            heading,
+            escape_markdown(
+                "No simulated positions yet. Enable Dry Run and let the task detect a mint."
+            )
+        );
+    }
+
+    let snapshots = join_all(positions.iter().map(|position| async move {
+        fetch_pool_snapshot(&RPC_CLIENTS, &position.mint, MARKET_FILTER_BUDGET)
+            .await?
+            .ok_or_else(|| anyhow!("no SOL-paired pool"))
+    }))
+    .await;
+
+    let mut total_in = 0.0;
+    let mut total_value = 0.0;
+    let mut priced = 0usize;
+    let mut lines = Vec::with_capacity(positions.len());
+    for (position, snapshot) in positions.iter().zip(snapshots) {
+        let opened = Utc
+            .timestamp_opt(position.opened_at, 0)
+            .single()
+            .map(|time| time.with_timezone(&Local).format("%m-%d %H:%M").to_string())
+            .unwrap_or_else(|| "?".to_string());
+        let line = match snapshot {
+            Ok(snapshot) => {
+                let value = simulate_sell(&snapshot, position.tokens);
+                let pnl = value - position.sol_in;
+                let pnl_percent = pnl / position.sol_in * 100.0;
+                total_in += position.sol_in;
+                total_value += value;
+                priced += 1;
+                format!(
+                    "{} `{}`\n   {}",
+                    if pnl >= 0.0 { "🟢" } else { "🔴" },
+                    escape_markdown(&position.mint),
+                    escape_markdown(&format!(
+                        "{} | in {:.3} SOL | now {:.3} SOL | {:+.3} SOL ({:+.1}%)",
+                        opened, position.sol_in, value, pnl, pnl_percent
+                    ))
+                )
+            }
+            Err(e) => format!(
+                "⚪ `{}`\n   {}",
+                escape_markdown(&position.mint),
+                escape_markdown(&format!(
+                    "{} | in {:.3} SOL | price unavailable: {}",
+                    opened, position.sol_in, e
+                ))
+            ),
+        };
+        lines.push(line);
+    }
+
+    let total_pnl = total_value - total_in;
+    let total_percent = if total_in > 0.0 {
+        total_pnl / total_in * 100.0
+    } else {
+        0.0
+    };
+    let summary = escape_markdown(&format!(
+        "Positions: {} ({} priced) | Invested: {:.3} SOL | Value: {:.3} SOL | PnL: {:+.3} SOL ({:+.1}%)",
+        positions.len(),
+        priced,
+        total_in,
+        total_value,
+        total_pnl,
+        total_percent
+    ));
+
+    format!(
+        "{}\n\n{}\n\n{}\n\n{}",
+        heading,
+        lines.join("\n\n"),
+        summary,
+        escape_markdown("Values assume selling the full position into the current pool.")
+    )
+}
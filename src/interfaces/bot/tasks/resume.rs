@@ -0,0 +1,228 @@
+use crate::interfaces::bot::data::types::Platform;
+use crate::interfaces::bot::handlers::tasks::activation_requirement_error;
+use crate::interfaces::bot::tasks::append_task_log;
+use crate::interfaces::bot::user::sessions::{ensure_task_session, session_display_name};
+use crate::interfaces::bot::{
+    Task, escape_markdown, get_all_user_ids, get_user_data, save_user_data,
+};
+use anyhow::Result;
+use redis::Client as RedisClient;
+use teloxide::prelude::*;
+use teloxide::types::{ChatId, ParseMode};
+
+struct ResumeOutcome {
+    chat_id: i64,
+    task_name: String,
+    platform: Platform,
+    detail: String,
+}
+
+#[derive(Default)]
+struct ResumeReport {
+    sessions: Vec<(i64, String)>,
+    resumed: Vec<ResumeOutcome>,
+    failed: Vec<ResumeOutcome>,
+}
+
+fn platform_label(platform: &Platform) -> &'static str {
+    match platform {
+        Platform::Telegram => "Telegram",
+        Platform::Discord => "Discord",
+    }
+}
+
+async fn restore_task_session(task: &mut Task) -> Result<i64> {
+    let account_id = ensure_task_session(task).await?;
+    task.telegram_user_id = Some(account_id);
+    Ok(account_id)
+}
+
+pub async fn start_monitor(task: Task, chat_id: i64) {
+    match task.platform {
+        Platform::Telegram => {
+            crate::interfaces::bot::tasks::tg::start_task_monitor(task, chat_id).await;
+        }
+        Platform::Discord => {
+            crate::interfaces::bot::tasks::discord::start_task_monitor(task, chat_id).await;
+        }
+    }
+}
+
+async fn resume_user_tasks(
+    con: &mut redis::aio::MultiplexedConnection,
+    chat_id: i64,
+    report: &mut ResumeReport,
+) -> Result<()> {
+    let Some(mut user_data) = get_user_data(con, chat_id).await? else {
+        return Ok(());
+    };
+    let mut changed = false;
+    let mut to_start = Vec::new();
+
+    for task in user_data.tasks.iter_mut() {
+        let previous_account = task.telegram_user_id;
+        let session = if task.has_telegram_user_session() {
+            Some(restore_task_session(task).await)
+        } else {
+            None
+        };
+        if task.telegram_user_id != previous_account {
+            changed = true;
+        }
+        if let Some(Ok(account_id)) = &session {
+            if !report.sessions.iter().any(|(id, _)| id == account_id) {
+                let name = session_display_name(*account_id).unwrap_or_else(|| "-".to_string());
+                report.sessions.push((*account_id, name));
+            }
+        }
+
+        if !task.active {
+            if let Some(Err(e)) = &session {
+                log::warn!(
+                    "resume.session_failed chat_id={} task={} err=\"{}\"",
+                    chat_id,
+                    task.name,
+                    e
+                );
+            }
+            continue;
+        }
+
+        let failure = if let Some(error) = activation_requirement_error(task) {
+            Some(error.trim_start_matches("❌ ").to_string())
+        } else if task.platform == Platform::Telegram {
+            match &session {
+                Some(Err(e)) => Some(e.to_string()),
+                _ => None,
+            }
+        } else {
+            None
+        };
+
+        let outcome = ResumeOutcome {
+            chat_id,
+            task_name: task.name.clone(),
+            platform: task.platform.clone(),
+            detail: String::new(),
+        };
+        match failure {
+            Some(reason) => {
+                task.active = false;
+                changed = true;
+                append_task_log(
+                    chat_id,
+                    &task.name,
+                    format!("Task not resumed after restart: {}", reason),
+                );
+                report.failed.push(ResumeOutcome {
+                    detail: reason,
+                    ..outcome
+                });
+            }
+            None => {
+                let detail = match (&task.platform, task.telegram_user_id) {
+                    (Platform::Telegram, Some(account_id)) => format!("account {}", account_id),
+                    _ => String::new(),
+                };
+                append_task_log(chat_id, &task.name, "Task resumed after restart");
+                to_start.push(task.clone());
+                report.resumed.push(ResumeOutcome { detail, ..outcome });
+            }
+        }
+    }
+
+    if changed {
+        save_user_data(con, chat_id, &user_data).await?;
+    }
+    for task in to_start {
+        start_monitor(task, chat_id).await;
+    }
+    Ok(())
+}
+
+fn format_outcome(outcome: &ResumeOutcome) -> String {
+    let mut line = format!(
+        "• `{}` \\({}, chat `{}`\\)",
+        escape_markdown(&outcome.task_name),
+        platform_label(&outcome.platform),
+        outcome.chat_id
+    );
+    if !outcome.detail.is_empty() {
+        line.push_str(&format!(" \\- {}", escape_markdown(&outcome.detail)));
+    }
+    line
+}
+
+fn format_report(report: &ResumeReport) -> String {
+    let mut sections = vec!["🔄 *Startup Resume*".to_string()];
+
+    let mut session_lines = vec![format!(
+        "👤 *Telegram Sessions Restored:* {}",
+        report.sessions.len()
+    )];
+    for (account_id, name) in &report.sessions {
+        session_lines.push(format!(
+            "• {} \\(`{}`\\)",
+            escape_markdown(name),
+            account_id
+        ));
+    }
+    sections.push(session_lines.join("\n"));
+
+    let mut resumed_lines = vec![format!("✅ *Resumed Tasks:* {}", report.resumed.len())];
+    resumed_lines.extend(report.resumed.iter().map(format_outcome));
+    sections.push(resumed_lines.join("\n"));
+
+    if !report.failed.is_empty() {
+        let mut failed_lines = vec![format!("❌ *Not Resumed:* {}", report.failed.len())];
+        failed_lines.extend(report.failed.iter().map(format_outcome));
+        sections.push(failed_lines.join("\n"));
+    }
+
+    sections.join("\n\n")
+}
+
+pub async fn resume_active_tasks(
+    redis_client: RedisClient,
+    redis_url: String,
+    admin_chat_id: Option<i64>,
+) {
+    let mut report = ResumeReport::default();
+    let result: Result<()> = async {
+        let user_ids = get_all_user_ids(&redis_url).await?;
+        let mut con = redis_client.get_multiplexed_async_connection().await?;
+        for chat_id in user_ids {
+            if let Err(e) = resume_user_tasks(&mut con, chat_id, &mut report).await {
+                log::warn!("resume.user_failed chat_id={} err=\"{}\"", chat_id, e);
+            }
+        }
+        Ok(())
+    }
+    .await;
+    if let Err(e) = result {
+        log::error!("resume.failed err=\"{}\"", e);
+        return;
+    }
+
+    log::info!(
+        "resume.done sessions={} resumed={} failed={}",
+        report.sessions.len(),
+        report.resumed.len(),
+        report.failed.len()
+    );
+    if report.sessions.is_empty() && report.resumed.is_empty() && report.failed.is_empty() {
+        return;
+    }
+    let Some(admin_chat_id) = admin_chat_id else {
+        return;
+    };
+
+    let bot = Bot::from_env();
+    if let Err(e) = bot
+        .send_message(ChatId(admin_chat_id), format_report(&report))
+        .parse_mode(ParseMode::MarkdownV2)
+        .await
+    {
+        log::warn!("resume.notify_failed chat_id={} err={}", admin_chat_id, e);
+    }
+}
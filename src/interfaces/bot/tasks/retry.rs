@@ -0,0 +1,466 @@
+use crate::BloomBuyAck;
+use crate::infrastructure::blockchain::{
+    BloomCredentials, BloomFailure, BloomSwapOptions, SwapOutcome, bloom_buy, subscribe_swap,
+};
+use crate::interfaces::bot::{ExecutionMode, RetryPolicy, Task, escape_markdown};
+use anyhow::anyhow;
+use chrono::Local;
+use std::time::{Duration, Instant};
+
+const BLOOM_ACK_TIMEOUT: Duration = Duration::from_secs(20);
+
+pub enum BloomAttemptOutcome {
+    Acked(BloomBuyAck),
+    Rejected(BloomFailure),
+    ChannelClosed,
+    AckTimeout,
+    RequestFailed(anyhow::Error),
+}
+
+impl BloomAttemptOutcome {
+    pub fn retry_reason(&self) -> Option<String> {
+        match self {
+            BloomAttemptOutcome::Acked(_) => None,
+            BloomAttemptOutcome::Rejected(failure) => Some(failure.describe()),
+            BloomAttemptOutcome::ChannelClosed => Some("ACK channel closed".into()),
+            BloomAttemptOutcome::AckTimeout => Some("ACK timeout".into()),
+            BloomAttemptOutcome::RequestFailed(e) => Some(e.to_string()),
+        }
+    }
+
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            BloomAttemptOutcome::Acked(_) => false,
+            BloomAttemptOutcome::Rejected(failure) => failure.kind.is_retryable(),
+            _ => true,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RetryAttempt {
+    pub number: u32,
+    pub priority_fee_sol: f64,
+    pub processor_tip_sol: f64,
+    pub slippage_percent: u32,
+}
+
+impl RetryAttempt {
+    pub fn first(task: &Task) -> Self {
+        RetryAttempt {
+            number: 1,
+            priority_fee_sol: task.buy_priority_fee_sol,
+            processor_tip_sol: task.processor_tip_sol(),
+            slippage_percent: task.buy_slippage_percent,
+        }
+    }
+
+    pub fn describe(&self, result: &str) -> String {
+        format!(
+            "#{} fee {:.4} SOL, slippage {}% -> {}",
+            self.number, self.priority_fee_sol, self.slippage_percent, result
+        )
+    }
+}
+
+pub fn plan_retry(
+    policy: &RetryPolicy,
+    attempt: &RetryAttempt,
+    detected_at: Instant,
+) -> Option<RetryAttempt> {
+    if attempt.number >= policy.max_attempts {
+        return None;
+    }
+    let delay = Duration::from_millis(policy.delay_ms);
+    if policy.cutoff_secs > 0
+        && detected_at.elapsed() + delay > Duration::from_secs(policy.cutoff_secs)
+    {
+        return None;
+    }
+    let multiplier = policy.fee_multiplier.max(1.0);
+    Some(RetryAttempt {
+        number: attempt.number + 1,
+        priority_fee_sol: attempt.priority_fee_sol * multiplier,
+        processor_tip_sol: attempt.processor_tip_sol * multiplier,
+        slippage_percent: (attempt.slippage_percent + policy.slippage_step_percent).min(100),
+    })
+}
+
+pub fn build_retry_summary(history: &[String]) -> String {
+    if history.is_empty() {
+        return String::new();
+    }
+    let lines = history
+        .iter()
+        .map(|line| format!("• {}", escape_markdown(line)))
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!("\n\n🔄 *Retry Attempts*\n{}", lines)
+}
+
+pub fn build_bloom_failure_message(
+    mint: &str,
+    failure: &BloomFailure,
+    retry_block: &str,
+) -> String {
+    let code_line = match failure.error_code {
+        Some(code) => format!("*Code:* `{}`\n", code),
+        None => String::new(),
+    };
+    format!(
+        "❌ *Buy Failed \\| {}*\n\n*Token:* `{}`\n*Error:* `{}`\n{}\n💡 {}{}",
+        escape_markdown(failure.kind.label()),
+        escape_markdown(mint),
+        escape_markdown(
+            failure
+                .error
+                .as_deref()
+                .unwrap_or("No error message from Bloom")
+        ),
+        code_line,
+        escape_markdown(failure.kind.suggested_action()),
+        retry_block
+    )
+}
+
+pub struct BloomPipelineResult {
+    pub message: String,
+    pub bought: bool,
+    pub native_fallback: bool,
+}
+
+fn build_buy_success_message(
+    mint: &str,
+    amount_sol: f64,
+    api_ms: u128,
+    prio_fee_sol: f64,
+    options: &BloomSwapOptions,
+    ack: Option<BloomBuyAck>,
+) -> String {
+    let t0 = Instant::now();
+    let time_str = escape_markdown(&Local::now().format("%H:%M:%S").to_string());
+    let header = format!("🟢 *Buy Successful \\| {}*", time_str);
+
+    let token_line = if let Some(ref a) = ack {
+        if let Some(ref t) = a.token_name {
+            format!("🔹Token: *{}*\n", escape_markdown(t))
+        } else {
+            "".to_string()
+        }
+    } else {
+        "".to_string()
+    };
+
+    let mint_line = format!("🔹`{}`", escape_markdown(mint));
+
+    let amount_str = escape_markdown(&format!("{:.3}", amount_sol));
+    let prio_str = escape_markdown(&format!("{:.3}", prio_fee_sol));
+    let tip_str = escape_markdown(&format!("{:.3}", options.processor_tip));
+    let value_line = format!(
+        "💰 Value: *{} SOL* \\- Prio Fee: *{} SOL* \\- Processor Tip: *{} SOL*\n",
+        amount_str, prio_str, tip_str
+    );
+
+    let on_off = |enabled: bool| if enabled { "On" } else { "Off" };
+    let dev_sell_str = options.dev_sell.as_deref().unwrap_or("Off");
+    let options_line = format!(
+        "🛡️ Anti\\-MEV: *{}* • Auto Tip: *{}* • Skip If Bought: *{}* • Dev Sell: *{}*\n",
+        on_off(options.anti_mev),
+        on_off(options.auto_tip),
+        on_off(options.skip_if_bought),
+        escape_markdown(dev_sell_str)
+    );
+
+    let durations_line = if let Some(ref a) = ack {
+        let conf_ms = a.success_time.duration_since(a.pending_time).as_millis();
+        let api_s = escape_markdown(&api_ms.to_string());
+        let conf_s = escape_markdown(&conf_ms.to_string());
+        format!(
+            "⏱️ Durations: *API {} ms* • *Confirmation {} ms*\n",
+            api_s, conf_s
+        )
+    } else {
+        let api_s = escape_markdown(&api_ms.to_string());
+        format!("⏱️ Durations: *API {} ms*\n", api_s)
+    };
+
+    let sig_block = if let Some(ref a) = ack {
+        if let Some(sig) = &a.signature {
+            let sig_esc = escape_markdown(sig);
+            let link = format!("https://solscan.io/tx/{}", sig);
+            format!("\n`{}`\n🔗 [View on Solscan]({})", sig_esc, link)
+        } else {
+            "".to_string()
+        }
+    } else {
+        "".to_string()
+    };
+
+    let s = format!(
+        "{}\n\n{}{}{}\n{}{}{}",
+        header, token_line, mint_line, "\n", value_line, options_line, durations_line
+    ) + &sig_block;
+    let render_us = t0.elapsed().as_micros();
+    log::info!("perf.render_us={} mint={}", render_us, mint);
+    s
+}
+
+fn build_buy_unconfirmed_message(
+    mint: &str,
+    amount_sol: f64,
+    api_ms: u128,
+    prio_fee_sol: f64,
+    reason: &str,
+) -> String {
+    let time_str = escape_markdown(&Local::now().format("%H:%M:%S").to_string());
+    format!(
+        "⏳ *Buy Submitted, Unconfirmed \\| {}*\n\n🔹`{}`\n\n💰 Value: *{} SOL* \\- Prio Fee: *{} SOL*\n⏱️ Durations: *API {} ms*\n\n⚠️ Bloom accepted the order but no confirmation arrived \\({}\\)\\. Check your wallet before buying this token again\\.",
+        time_str,
+        escape_markdown(mint),
+        escape_markdown(&format!("{:.3}", amount_sol)),
+        escape_markdown(&format!("{:.3}", prio_fee_sol)),
+        escape_markdown(&api_ms.to_string()),
+        escape_markdown(reason)
+    )
+}
+
+pub async fn run_bloom_buy_pipeline(
+    task: &Task,
+    credentials: Option<&BloomCredentials>,
+    wallet_address: &str,
+    wallet_label: &str,
+    mint: &str,
+    t_all_start: Instant,
+    log_event: impl Fn(String),
+) -> BloomPipelineResult {
+    let mut swap_options = task.bloom_swap_options();
+    let mut attempt = RetryAttempt::first(task);
+    let mut attempt_history: Vec<String> = Vec::new();
+    loop {
+        swap_options.processor_tip = attempt.processor_tip_sol;
+        if attempt.number > 1 {
+            swap_options.skip_if_bought = true;
+        }
+        let api_request_start_time = Instant::now();
+        let buy_result = match credentials {
+            Some(credentials) => {
+                bloom_buy(
+                    credentials,
+                    mint,
+                    task.buy_amount_sol,
+                    attempt.slippage_percent,
+                    attempt.priority_fee_sol,
+                    wallet_address,
+                    wallet_label,
+                    &swap_options,
+                )
+                .await
+            }
+            None => Err(anyhow!(
+                "No Bloom account configured; add one from Bloom Accounts"
+            )),
+        };
+        let api_duration = api_request_start_time.elapsed();
+        let api_us = api_duration.as_micros();
+        let api_ms = (api_us as f64 / 1000.0).max(0.01);
+        log_event(format!(
+            "Bloom buy attempt {} initiated for mint {} ({} SOL, fee {} SOL, slippage {}%)",
+            attempt.number,
+            mint,
+            task.buy_amount_sol,
+            attempt.priority_fee_sol,
+            attempt.slippage_percent
+        ));
+
+        let ack_wait_start = Instant::now();
+        let outcome = match buy_result {
+            Ok(swap_id) => match subscribe_swap(&swap_id) {
+                Some(rx) => match tokio::time::timeout(BLOOM_ACK_TIMEOUT, rx).await {
+                    Ok(Ok(SwapOutcome::Confirmed(ack))) => BloomAttemptOutcome::Acked(ack),
+                    Ok(Ok(SwapOutcome::Expired)) | Err(_) => BloomAttemptOutcome::AckTimeout,
+                    Ok(Ok(SwapOutcome::Failed(failure))) => BloomAttemptOutcome::Rejected(failure),
+                    Ok(Err(_)) => BloomAttemptOutcome::ChannelClosed,
+                },
+                None => BloomAttemptOutcome::ChannelClosed,
+            },
+            Err(e) => BloomAttemptOutcome::RequestFailed(e),
+        };
+        let ack_wait_us = ack_wait_start.elapsed().as_micros();
+
+        if let Some(reason) = outcome.retry_reason() {
+            if let Some(next_attempt) = plan_retry(&task.retry_policy, &attempt, t_all_start)
+                .filter(|_| outcome.is_retryable())
+            {
+                log::info!(
+                    "perf.api_us={} retry_attempt={} err=\"{}\" mint={}",
+                    api_us,
+                    attempt.number,
+                    reason,
+                    mint
+                );
+                log_event(format!(
+                    "Bloom buy attempt {} for mint {} failed ({}); retrying in {} ms with fee {} SOL and slippage {}%",
+                    attempt.number,
+                    mint,
+                    reason,
+                    task.retry_policy.delay_ms,
+                    next_attempt.priority_fee_sol,
+                    next_attempt.slippage_percent
+                ));
+                attempt_history.push(attempt.describe(&format!("failed ({})", reason)));
+                tokio::time::sleep(Duration::from_millis(task.retry_policy.delay_ms)).await;
+                attempt = next_attempt;
+                continue;
+            }
+            if attempt.number > 1 {
+                attempt_history.push(attempt.describe(&format!("failed ({})", reason)));
+            }
+        } else if attempt.number > 1 {
+            attempt_history.push(attempt.describe("confirmed"));
+        }
+        let retry_block = build_retry_summary(&attempt_history);
+        let total_us = t_all_start.elapsed().as_micros();
+        let ack_wait_ms = (ack_wait_us as f64 / 1000.0).max(0.01);
+        let total_ms = (total_us as f64 / 1000.0).max(0.01);
+        let fallback_enabled = task.execution_mode == ExecutionMode::BloomWithNativeFallback;
+        let timed_out = matches!(outcome, BloomAttemptOutcome::AckTimeout);
+
+        break match outcome {
+            BloomAttemptOutcome::Acked(ack) => {
+                let signature_opt = ack.signature.clone();
+                let message = build_buy_success_message(
+                    mint,
+                    task.buy_amount_sol,
+                    api_duration.as_millis(),
+                    attempt.priority_fee_sol,
+                    &swap_options,
+                    Some(ack),
+                ) + &retry_block;
+                log::info!(
+                    "perf.api_us={} perf.ack_wait_us={} perf.total_us={} attempts={} mint={}",
+                    api_us,
+                    ack_wait_us,
+                    total_us,
+                    attempt.number,
+                    mint
+                );
+                log_event(format!(
+                    "Bloom buy timings -> API: {:.2} ms | ACK: {:.2} ms (success) | Pipeline total: {:.2} ms",
+                    api_ms, ack_wait_ms, total_ms
+                ));
+                match signature_opt {
+                    Some(signature) => log_event(format!(
+                        "Bloom buy confirmed for mint {} (signature {})",
+                        mint, signature
+                    )),
+                    None => log_event(format!("Bloom buy confirmed for mint {}", mint)),
+                }
+                BloomPipelineResult {
+                    message,
+                    bought: true,
+                    native_fallback: false,
+                }
+            }
+            BloomAttemptOutcome::Rejected(failure) => {
+                log::info!(
+                    "perf.api_us={} perf.ack_wait_us={} perf.total_us={} bloom_failure={:?} attempts={} mint={}",
+                    api_us,
+                    ack_wait_us,
+                    total_us,
+                    failure.kind,
+                    attempt.number,
+                    mint
+                );
+                log_event(format!(
+                    "Bloom buy timings -> API: {:.2} ms | ACK: {:.2} ms (failed) | Pipeline total: {:.2} ms",
+                    api_ms, ack_wait_ms, total_ms
+                ));
+                log_event(format!(
+                    "Bloom buy failed for mint {} ({})",
+                    mint,
+                    failure.describe()
+                ));
+                BloomPipelineResult {
+                    message: build_bloom_failure_message(mint, &failure, &retry_block),
+                    bought: false,
+                    native_fallback: fallback_enabled && failure.kind.allows_native_fallback(),
+                }
+            }
+            BloomAttemptOutcome::ChannelClosed | BloomAttemptOutcome::AckTimeout => {
+                let message = build_buy_unconfirmed_message(
+                    mint,
+                    task.buy_amount_sol,
+                    api_duration.as_millis(),
+                    attempt.priority_fee_sol,
+                    if timed_out {
+                        "ACK timeout"
+                    } else {
+                        "ACK channel closed"
+                    },
+                ) + &retry_block;
+                log::info!(
+                    "perf.api_us={} perf.ack_wait_us={} perf.total_us={} {}=1 attempts={} mint={}",
+                    api_us,
+                    ack_wait_us,
+                    total_us,
+                    if timed_out {
+                        "ack_timeout"
+                    } else {
+                        "ack_channel_closed"
+                    },
+                    attempt.number,
+                    mint
+                );
+                log_event(format!(
+                    "Bloom buy timings -> API: {:.2} ms | ACK: {:.2} ms ({}) | Pipeline total: {:.2} ms",
+                    api_ms,
+                    ack_wait_ms,
+                    if timed_out {
+                        "timeout"
+                    } else {
+                        "channel closed"
+                    },
+                    total_ms
+                ));
+                if timed_out {
+                    log_event(format!("Bloom buy pending for mint {} (ACK timeout)", mint));
+                } else {
+                    log_event(format!(
+                        "Bloom buy finished for mint {} (ACK channel closed)",
+                        mint
+                    ));
+                }
+                BloomPipelineResult {
+                    message,
+                    bought: true,
+                    native_fallback: false,
+                }
+            }
+            BloomAttemptOutcome::RequestFailed(e) => {
+                log::info!(
+                    "perf.api_us={} perf.total_us={} buy_error=1 attempts={} err=\"{}\" mint={}",
+                    api_us,
+                    total_us,
+                    attempt.number,
+                    e,
+                    mint
+                );
+                log_event(format!(
+                    "Bloom buy timings -> API: {:.2} ms | Pipeline total: {:.2} ms (failure)",
+                    api_ms, total_ms
+                ));
+                log_event(format!("Bloom buy failed for mint {}: {}", mint, e));
+                BloomPipelineResult {
+                    message: format!(
+                        "❌ *Buy Request Failed*\n\n*Token:* `{}`\n*Error:* `{}`{}",
+                        escape_markdown(mint),
+                        escape_markdown(&e.to_string()),
+                        retry_block
+                    ),
+                    bought: false,
+                    native_fallback: fallback_enabled,
+                }
+            }
+        };
+    }
+}
@@ -0,0 +1,85 @@
+use crate::RPC_CLIENTS;
+use crate::infrastructure::blockchain::{MintSafetyReport, SAFETY_CHECK_BUDGET, check_mint_safety};
+use crate::interfaces::bot::{Task, escape_markdown};
+use std::time::Instant;
+
+pub enum SafetyGateOutcome {
+    Passed(MintSafetyReport),
+    Blocked(MintSafetyReport),
+    Inconclusive(String),
+}
+
+pub async fn run_safety_gate(mint: &str) -> SafetyGateOutcome {
+    match check_mint_safety(&RPC_CLIENTS, mint, SAFETY_CHECK_BUDGET).await {
+        Ok(report) => {
+            log::info!(
+                "perf.safety_check_ms={} safe={} token_2022={} mint={}",
+                report.elapsed_ms,
+                if report.is_safe() { 1 } else { 0 },
+                if report.is_token_2022 { 1 } else { 0 },
+                mint
+            );
+            if report.is_safe() {
+                SafetyGateOutcome::Passed(report)
+            } else {
+                SafetyGateOutcome::Blocked(report)
+            }
+        }
+        Err(e) => {
+            log::warn!("safety_check_inconclusive=1 err=\"{}\" mint={}", e, mint);
+            SafetyGateOutcome::Inconclusive(e.to_string())
+        }
+    }
+}
+
+pub async fn check_safety_before_buy(
+    task: &Task,
+    mint: &str,
+    t_all_start: Instant,
+    log_event: impl Fn(String),
+) -> Option<String> {
+    if !task.safety_checks_enabled {
+        return None;
+    }
+    match run_safety_gate(mint).await {
+        SafetyGateOutcome::Passed(report) => {
+            log_event(format!(
+                "Safety checks passed for mint {} in {} ms",
+                mint, report.elapsed_ms
+            ));
+            None
+        }
+        SafetyGateOutcome::Blocked(report) => {
+            let total_us = t_all_start.elapsed().as_micros();
+            log::info!("perf.total_us={} safety_blocked=1 mint={}", total_us, mint);
+            log_event(format!(
+                "Buy blocked for mint {} by safety checks: {}",
+                mint,
+                report.issues_summary()
+            ));
+            Some(build_safety_blocked_message(mint, &report))
+        }
+        SafetyGateOutcome::Inconclusive(reason) => {
+            log_event(format!(
+                "Safety checks inconclusive for mint {} ({}); continuing with buy",
+                mint, reason
+            ));
+            None
+        }
+    }
+}
+
+pub fn build_safety_blocked_message(mint: &str, report: &MintSafetyReport) -> String {
+    let issues = report
+        .issues
+        .iter()
+        .map(|issue| format!("• {}", escape_markdown(issue.label())))
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!(
+        "🛡️ *Buy Blocked by Safety Checks*\n\n*Token:* `{}`\n\n{}\n\n_{}_",
+        escape_markdown(mint),
+        issues,
+        escape_markdown(&format!("Checked in {} ms", report.elapsed_ms))
+    )
+}
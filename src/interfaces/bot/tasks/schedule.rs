@@ -0,0 +1,307 @@
+use crate::interfaces::bot::data::types::Platform;
+use crate::interfaces::bot::handlers::tasks::activation_requirement_error;
+use crate::interfaces::bot::tasks::append_task_log;
+use crate::interfaces::bot::{
+    ScheduleWindow, Task, TaskSchedule, escape_markdown, get_all_user_ids, get_user_data,
+    save_user_data,
+};
+use anyhow::Result;
+use chrono::{NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use redis::Client as RedisClient;
+use std::collections::HashMap;
+use teloxide::prelude::*;
+use teloxide::types::{ChatId, ParseMode};
+use tokio::time::{Duration, MissedTickBehavior};
+
+const SCHEDULER_INTERVAL: Duration = Duration::from_secs(30);
+const DAY_NAMES: [&str; 7] = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+
+static WINDOW_STATES: Lazy<Mutex<HashMap<(i64, String), bool>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+enum ScheduleAction {
+    Start,
+    Stop(&'static str),
+    Blocked(&'static str),
+}
+
+fn parse_timezone(text: &str) -> Result<String, String> {
+    let name = text.trim();
+    if name.eq_ignore_ascii_case("utc") {
+        return Ok("UTC".to_string());
+    }
+    name.parse::<Tz>()
+        .map(|tz| tz.name().to_string())
+        .map_err(|_| {
+            format!(
+                "Unknown timezone '{}', expected an IANA name such as Europe/Istanbul",
+                name
+            )
+        })
+}
+
+fn parse_day(text: &str) -> Result<u8, String> {
+    DAY_NAMES
+        .iter()
+        .position(|name| text.starts_with(name))
+        .map(|index| index as u8)
+        .ok_or_else(|| format!("Unknown weekday '{}'", text))
+}
+
+fn parse_days(text: &str) -> Result<Vec<u8>, String> {
+    if matches!(text, "daily" | "all" | "*") {
+        return Ok((0..7).collect());
+    }
+    let mut days = Vec::new();
+    for part in text.split(',').filter(|part| !part.is_empty()) {
+        match part.split_once('-') {
+            Some((start, end)) => {
+                let start = parse_day(start)?;
+                let end = parse_day(end)?;
+                let mut day = start;
+                loop {
+                    if !days.contains(&day) {
+                        days.push(day);
+                    }
+                    if day == end {
+                        break;
+                    }
+                    day = (day + 1) % 7;
+                }
+            }
+            None => {
+                let day = parse_day(part)?;
+                if !days.contains(&day) {
+                    days.push(day);
+                }
+            }
+        }
+    }
+    if days.is_empty() {
+        return Err(format!("No weekdays in '{}'", text));
+    }
+    days.sort_unstable();
+    Ok(days)
+}
+
+fn parse_time(text: &str) -> Result<u16, String> {
+    let (hours, minutes) = text
+        .split_once(':')
+        .ok_or_else(|| format!("Invalid time '{}', expected HH:MM", text))?;
+    let hours = hours
+        .parse::<u16>()
+        .map_err(|_| format!("Invalid time '{}'", text))?;
+    let minutes = minutes
+        .parse::<u16>()
+        .map_err(|_| format!("Invalid time '{}'", text))?;
+    if minutes >= 60 || hours > 24 || (hours == 24 && minutes > 0) {
+        return Err(format!("Time '{}' is out of range", text));
+    }
+    Ok(hours * 60 + minutes)
+}
+
+fn parse_windows(line: &str) -> Result<Vec<ScheduleWindow>, String> {
+    let mut parts = line.split_whitespace();
+    let days = parse_days(parts.next().unwrap_or_default())?;
+    let ranges: String = parts.collect::<Vec<_>>().join("");
+    if ranges.is_empty() {
+        return Err(format!("Missing time range in '{}'", line));
+    }
+    ranges
+        .split(',')
+        .filter(|range| !range.is_empty())
+        .map(|range| {
+            let (start, end) = range
+                .split_once('-')
+                .ok_or_else(|| format!("Invalid time range '{}', expected HH:MM-HH:MM", range))?;
+            Ok(ScheduleWindow {
+                days: days.clone(),
+                start_minute: parse_time(start)?,
+                end_minute: parse_time(end)?,
+            })
+        })
+        .collect()
+}
+
+pub fn parse_schedule(text: &str) -> Result<Option<TaskSchedule>, String> {
+    if text.trim().eq_ignore_ascii_case("off") {
+        return Ok(None);
+    }
+    let mut schedule = TaskSchedule::default();
+    let mut until_text: Option<String> = None;
+    for line in text.lines() {
+        let raw = line.trim();
+        let line = raw.to_lowercase();
+        if line.is_empty() {
+            continue;
+        }
+        if line.starts_with("tz ") {
+            schedule.timezone = parse_timezone(&raw[3..])?;
+        } else if let Some(rest) = line.strip_prefix("until ") {
+            until_text = Some(rest.trim().to_string());
+        } else {
+            schedule.windows.extend(parse_windows(&line)?);
+        }
+    }
+    if let Some(until) = until_text {
+        let naive = NaiveDateTime::parse_from_str(&until, "%Y-%m-%d %H:%M")
+            .map_err(|_| format!("Invalid expiry '{}', expected YYYY-MM-DD HH:MM", until))?;
+        let local = schedule
+            .tz()
+            .from_local_datetime(&naive)
+            .earliest()
+            .ok_or_else(|| format!("Expiry '{}' does not exist in {}", until, schedule.timezone))?;
+        schedule.active_until = Some(local.timestamp());
+    }
+    if schedule.windows.is_empty() && schedule.active_until.is_none() {
+        return Err("A schedule needs at least one time window or an 'until' line.".to_string());
+    }
+    Ok(Some(schedule))
+}
+
+fn decide_action(chat_id: i64, task: &Task, schedule: &TaskSchedule) -> Option<ScheduleAction> {
+    let now = Utc::now();
+    if schedule.is_expired(now) {
+        return task
+            .active
+            .then_some(ScheduleAction::Stop("schedule expired"));
+    }
+    let open = schedule.window_open(now)?;
+    let previous = WINDOW_STATES
+        .lock()
+        .insert((chat_id, task.name.clone()), open);
+    if previous == Some(open) {
+        return None;
+    }
+    if open && !task.active {
+        match activation_requirement_error(task) {
+            Some(error) => Some(ScheduleAction::Blocked(error)),
+            None => Some(ScheduleAction::Start),
+        }
+    } else if !open && task.active {
+        Some(ScheduleAction::Stop("activation window closed"))
+    } else {
+        None
+    }
+}
+
+async fn notify(chat_id: i64, message: String) {
+    let bot = Bot::from_env();
+    if let Err(e) = bot
+        .send_message(ChatId(chat_id), message)
+        .parse_mode(ParseMode::MarkdownV2)
+        .await
+    {
+        log::warn!("scheduler.notify_failed chat_id={} err={}", chat_id, e);
+    }
+}
+
+async fn start_monitor(task: Task, chat_id: i64) {
+    match task.platform {
+        Platform::Telegram => {
+            crate::interfaces::bot::tasks::tg::start_task_monitor(task, chat_id).await;
+        }
+        Platform::Discord => {
+            crate::interfaces::bot::tasks::discord::start_task_monitor(task, chat_id).await;
+        }
+    }
+}
+
+async fn evaluate_schedules(redis_client: &RedisClient, redis_url: &str) -> Result<()> {
+    let user_ids = get_all_user_ids(redis_url).await?;
+    let mut con = redis_client.get_multiplexed_async_connection().await?;
+    for chat_id in user_ids {
+        let Some(mut user_data) = get_user_data(&mut con, chat_id).await? else {
+            continue;
+        };
+        let mut actions = Vec::new();
+        for task in user_data.tasks.iter_mut() {
+            let Some(schedule) = task.schedule.clone() else {
+                continue;
+            };
+            let Some(action) = decide_action(chat_id, task, &schedule) else {
+                continue;
+            };
+            match action {
+                ScheduleAction::Start => task.active = true,
+                ScheduleAction::Stop(_) => task.active = false,
+                ScheduleAction::Blocked(_) => {}
+            }
+            actions.push((task.clone(), action));
+        }
+        if actions.is_empty() {
+            continue;
+        }
+        if actions
+            .iter()
+            .any(|(_, action)| !matches!(action, ScheduleAction::Blocked(_)))
+        {
+            save_user_data(&mut con, chat_id, &user_data).await?;
+        }
+
+        for (task, action) in actions {
+            let task_name = task.name.clone();
+            let message = match action {
+                ScheduleAction::Start => {
+                    append_task_log(chat_id, &task_name, "Task started by schedule");
+                    start_monitor(task, chat_id).await;
+                    format!(
+                        "⏰ *Task Started by Schedule*\n\n*Task:* `{}`",
+                        escape_markdown(&task_name)
+                    )
+                }
+                ScheduleAction::Stop(reason) => {
+                    append_task_log(
+                        chat_id,
+                        &task_name,
+                        format!("Task stopped by schedule ({})", reason),
+                    );
+                    format!(
+                        "⏰ *Task Stopped by Schedule*\n\n*Task:* `{}`\n*Reason:* {}",
+                        escape_markdown(&task_name),
+                        escape_markdown(reason)
+                    )
+                }
+                ScheduleAction::Blocked(error) => {
+                    append_task_log(
+                        chat_id,
+                        &task_name,
+                        format!("Scheduled start skipped: {}", error),
+                    );
+                    format!(
+                        "⏰ *Scheduled Start Skipped*\n\n*Task:* `{}`\n{}",
+                        escape_markdown(&task_name),
+                        escape_markdown(error)
+                    )
+                }
+            };
+            log::info!(
+                "scheduler.transition chat_id={} task={}",
+                chat_id,
+                task_name
+            );
+            notify(chat_id, message).await;
+        }
+    }
+    Ok(())
+}
+
+pub fn reset_schedule_state(chat_id: i64, task_name: &str) {
+    WINDOW_STATES
+        .lock()
+        .remove(&(chat_id, task_name.to_string()));
+}
+
+pub async fn run_task_scheduler(redis_client: RedisClient, redis_url: String) {
+    let mut interval = tokio::time::interval(SCHEDULER_INTERVAL);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        if let Err(e) = evaluate_schedules(&redis_client, &redis_url).await {
+            log::warn!("scheduler.evaluate_failed err=\"{}\"", e);
+        }
+    }
+}
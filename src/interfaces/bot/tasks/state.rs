@@ -1,3 +1,4 @@
+use crate::interfaces::bot::core::update_router::{refresh_task_route, unsubscribe_task};
 use crate::interfaces::bot::{Task, UserData};
 use chrono::Utc;
 use dashmap::DashMap;
@@ -63,6 +64,7 @@ pub async fn sync_active_tasks(chat_id: i64, tasks: &[Task]) {
             active_names.insert(task.name.clone());
         }
         ensure_task_state(chat_id, task.clone()).await;
+        refresh_task_route(chat_id, task);
     }
     let mut removals = Vec::new();
     for entry in ACTIVE_TASK_STATES.iter() {
@@ -79,6 +81,9 @@ pub async fn sync_active_tasks(chat_id: i64, tasks: &[Task]) {
         guard.active = false;
     }
     for (key, _) in removals {
+        if !present_names.contains(&key.1) {
+            unsubscribe_task(chat_id, &key.1);
+        }
         ACTIVE_TASK_STATES.remove(&key);
     }
 }
@@ -1,23 +1,29 @@
-use crate::BloomBuyAck;
 use crate::UserClientHandle;
-use crate::infrastructure::blockchain::bloom_buy;
-use crate::interfaces::bot::core::update_bus;
+use crate::interfaces::bot::core::update_router::{record_hub_queue_us, subscribe_task};
 use crate::interfaces::bot::escape_markdown;
-use crate::interfaces::bot::tasks::{append_task_log, resolve_task_wallet, state};
-use crate::interfaces::bot::{Task, UserData, log_buffer_to_ca_detection};
-use crate::{PENDING_BLOOM_RESPONSES, USER_CLIENT_HANDLE};
+use crate::interfaces::bot::tasks::autojoin::{
+    AutoJoinRecord, monitor_joined_chat, record_join, release_join_slot, reserve_join_slot,
+};
+use crate::interfaces::bot::tasks::buy::run_buy_path;
+use crate::interfaces::bot::tasks::dedup::claim_alert;
+use crate::interfaces::bot::tasks::edits::message_text_changed;
+use crate::interfaces::bot::tasks::market::{
+    MarketGateDecision, build_market_fallback_block, check_market_before_buy,
+};
+use crate::interfaces::bot::tasks::notify::send_notification_markdown;
+use crate::interfaces::bot::tasks::{append_task_log, state};
+use crate::interfaces::bot::user::sessions::{ensure_task_session, session_client};
+use crate::interfaces::bot::{
+    AutoJoinMode, Task, TelegramChannel, UserData, log_buffer_to_ca_detection,
+};
 use anyhow::{Result as AnyhowResult, anyhow};
-use chrono::Local;
+use chrono::{Local, Utc};
 use grammers_client::types::Chat;
 use grammers_client::{InvocationError, grammers_tl_types as tl};
 use regex::Regex;
 use std::collections::HashSet;
-use std::sync::{Arc, OnceLock};
+use std::sync::OnceLock;
 use std::time::{Instant, SystemTime, UNIX_EPOCH};
-use teloxide::prelude::*;
-use teloxide::types::{ChatId, InlineKeyboardButton, InlineKeyboardMarkup, ParseMode};
-use tokio::sync::{Mutex, oneshot};
-use tokio::time::Duration;
 
 fn log_task_event(chat_id: i64, task_name: &str, message: impl Into<String>) {
     let message = message.into();
@@ -25,67 +31,6 @@ fn log_task_event(chat_id: i64, task_name: &str, message: impl Into<String>) {
     append_task_log(chat_id, task_name, message);
 }
 
-async fn send_notification_markdown(chat_id: i64, message: String) {
-    let bot = Bot::from_env();
-    let keyboard = InlineKeyboardMarkup::new(vec![vec![InlineKeyboardButton::callback(
-        "🗑️ Remove",
-        "rm",
-    )]]);
-    let t_send_start = Instant::now();
-    let res = bot
-        .send_message(ChatId(chat_id), message.clone())
-        .parse_mode(ParseMode::MarkdownV2)
-        .disable_web_page_preview(true)
-        .reply_markup(keyboard.clone())
-        .await;
-    let send_us = t_send_start.elapsed().as_micros();
-    match res {
-        Ok(_) => {
-            log::info!("perf.telegram_send_us={} chat_id={}", send_us, chat_id);
-        }
-        Err(e) => {
-            let plain = strip_markdown_for_fallback(&message);
-            let t_send2 = Instant::now();
-            let _ = bot
-                .send_message(ChatId(chat_id), plain.clone())
-                .disable_web_page_preview(true)
-                .reply_markup(keyboard)
-                .await;
-            let send2_us = t_send2.elapsed().as_micros();
-            log::warn!(
-                "perf.telegram_send_fallback_us={} err=\"{}\" chat_id={}",
-                send2_us,
-                e,
-                chat_id
-            );
-        }
-    }
-}
-
-fn strip_markdown_for_fallback(s: &str) -> String {
-    let mut t = s
-        .replace("\\|", "|")
-        .replace("\\-", "-")
-        .replace("\\_", "_")
-        .replace("\\*", "*")
-        .replace("\\[", "[")
-        .replace("\\]", "]")
-        .replace("\\(", "(")
-        .replace("\\)", ")")
-        .replace("\\~", "~")
-        .replace("\\`", "`")
-        .replace("\\>", ">")
-        .replace("\\#", "#")
-        .replace("\\+", "+")
-        .replace("\\=", "=")
-        .replace("\\{", "{")
-        .replace("\\}", "}")
-        .replace("\\.", ".")
-        .replace("\\!", "!");
-    t = t.replace("*", "");
-    t
-}
-
 fn join_link_regex() -> &'static Regex {
     static JOIN_LINK_REGEX: OnceLock<Regex> = OnceLock::new();
     JOIN_LINK_REGEX.get_or_init(|| {
@@ -146,25 +91,87 @@ async fn try_join_invite(client: &UserClientHandle, hash: &str) -> AnyhowResult<
     }
 }
 
-async fn handle_auto_join_links(chat_id: i64, task_name: &str, message_text: &str) {
-    let hashes = extract_invite_hashes(message_text);
-    if hashes.is_empty() {
-        return;
-    }
+async fn record_auto_join(
+    chat_id: i64,
+    task: &Task,
+    source_channel: &TelegramChannel,
+    chat: &Chat,
+    hash: &str,
+) -> bool {
+    let settings = &task.auto_join;
+    let monitored = if settings.monitor_joined {
+        let channel = TelegramChannel {
+            users: source_channel.users.clone(),
+            usernames: source_channel.usernames.clone(),
+            is_broadcast: matches!(chat, Chat::Channel(_)),
+            ..TelegramChannel::new(chat.id(), chat.name().map(str::to_string))
+        };
+        match monitor_joined_chat(chat_id, &task.name, channel).await {
+            Ok(added) => added,
+            Err(e) => {
+                log::warn!(
+                    "task.tg: auto-monitor failed chat_id={} task={} joined={} err={}",
+                    chat_id,
+                    task.name,
+                    chat.id(),
+                    e
+                );
+                false
+            }
+        }
+    } else {
+        false
+    };
 
-    let handle = USER_CLIENT_HANDLE.lock().clone();
-    let Some(client) = handle else {
+    let joined_at = Utc::now().timestamp();
+    let record = AutoJoinRecord {
+        chat_id: chat.id(),
+        chat_name: chat.name().unwrap_or("Unknown Channel").to_string(),
+        task_name: task.name.clone(),
+        invite_hash: hash.to_string(),
+        joined_at,
+        leave_at: (settings.leave_after_secs > 0)
+            .then(|| joined_at + settings.leave_after_secs as i64),
+        left_at: None,
+        monitored,
+    };
+    if let Err(e) = record_join(chat_id, &record).await {
         log::warn!(
-            "task.tg: auto-join skipped no user client chat_id={} task={}",
+            "task.tg: auto-join log write failed chat_id={} task={} err={}",
             chat_id,
-            task_name
+            task.name,
+            e
         );
+    }
+    monitored
+}
+
+async fn handle_auto_join_links(
+    client: &UserClientHandle,
+    account_id: i64,
+    chat_id: i64,
+    task: &Task,
+    source_channel: &TelegramChannel,
+    sender_listened: bool,
+    message_text: &str,
+) {
+    let settings = &task.auto_join;
+    match settings.mode {
+        AutoJoinMode::Off => return,
+        AutoJoinMode::ListenedUsers if !sender_listened => return,
+        _ => {}
+    }
+
+    let hashes = extract_invite_hashes(message_text);
+    if hashes.is_empty() {
         return;
-    };
+    }
 
+    let task_name = task.name.as_str();
     let mut seen = HashSet::new();
-    let mut joined_channels: Vec<(String, Option<i64>, String)> = Vec::new();
+    let mut joined_channels: Vec<(String, Option<i64>, String, bool)> = Vec::new();
     let mut already_joined: Vec<String> = Vec::new();
+    let mut quota_blocked: Vec<String> = Vec::new();
     let mut failures: Vec<(String, String)> = Vec::new();
 
     for hash in hashes {
@@ -172,7 +179,36 @@ async fn handle_auto_join_links(chat_id: i64, task_name: &str, message_text: &st
             continue;
         }
 
-        match try_join_invite(&client, &hash).await {
+        let slot = match reserve_join_slot(account_id, settings.daily_limit).await {
+            Ok(Some(slot)) => slot,
+            Ok(None) => {
+                log_task_event(
+                    chat_id,
+                    task_name,
+                    format!(
+                        "Auto-join quota reached ({}/day); skipped invite hash={}",
+                        settings.daily_limit, hash
+                    ),
+                );
+                quota_blocked.push(hash);
+                continue;
+            }
+            Err(e) => {
+                log::warn!(
+                    "task.tg: auto-join quota unavailable chat_id={} err={}",
+                    chat_id,
+                    e
+                );
+                failures.push((hash, format!("Quota check unavailable: {}", e)));
+                continue;
+            }
+        };
+
+        let join_result = try_join_invite(client, &hash).await;
+        if !matches!(join_result, Ok(JoinResult::Joined(_))) {
+            release_join_slot(slot).await;
+        }
+        match join_result {
             Ok(JoinResult::Joined(chat_opt)) => {
                 let (channel_name, channel_id) = if let Some(chat) = chat_opt.as_ref() {
                     (
@@ -190,7 +226,13 @@ async fn handle_auto_join_links(chat_id: i64, task_name: &str, message_text: &st
                         hash, channel_name
                     ),
                 );
-                joined_channels.push((channel_name, channel_id, hash));
+                let monitored = match chat_opt.as_ref() {
+                    Some(chat) => {
+                        record_auto_join(chat_id, task, source_channel, chat, &hash).await
+                    }
+                    None => false,
+                };
+                joined_channels.push((channel_name, channel_id, hash, monitored));
             }
             Ok(JoinResult::AlreadyJoined) => {
                 log_task_event(
@@ -218,7 +260,11 @@ async fn handle_auto_join_links(chat_id: i64, task_name: &str, message_text: &st
         }
     }
 
-    if joined_channels.is_empty() && already_joined.is_empty() && failures.is_empty() {
+    if joined_channels.is_empty()
+        && already_joined.is_empty()
+        && quota_blocked.is_empty()
+        && failures.is_empty()
+    {
         return;
     }
 
@@ -228,15 +274,18 @@ async fn handle_auto_join_links(chat_id: i64, task_name: &str, message_text: &st
     if !joined_channels.is_empty() {
         sections.push(String::new());
         sections.push("✅ *Joined Channels:*".to_string());
-        for (name, id_opt, hash) in &joined_channels {
+        for (name, id_opt, hash, monitored) in &joined_channels {
             let name_md = escape_markdown(name);
             let hash_md = escape_markdown(hash);
-            let entry = if let Some(id) = id_opt {
+            let mut entry = if let Some(id) = id_opt {
                 let id_md = escape_markdown(&id.to_string());
                 format!("• {} (`hash:{}` · `id:{}`)", name_md, hash_md, id_md)
             } else {
                 format!("• {} (`hash:{}`)", name_md, hash_md)
             };
+            if *monitored {
+                entry.push_str(" · now monitored");
+            }
             sections.push(entry);
         }
     }
@@ -250,6 +299,18 @@ async fn handle_auto_join_links(chat_id: i64, task_name: &str, message_text: &st
         }
     }
 
+    if !quota_blocked.is_empty() {
+        sections.push(String::new());
+        sections.push(format!(
+            "⛔ *Daily Quota Reached \\({}/day\\):*",
+            settings.daily_limit
+        ));
+        for hash in &quota_blocked {
+            let hash_md = escape_markdown(hash);
+            sections.push(format!("• `hash:{}`", hash_md));
+        }
+    }
+
     if !failures.is_empty() {
         sections.push(String::new());
         sections.push("⚠️ *Failed Invites:*".to_string());
@@ -279,81 +340,19 @@ fn format_sender_name(sender: Option<&Chat>) -> String {
     }
 }
 
-fn build_buy_success_message(
-    mint: &str,
-    amount_sol: f64,
-    api_ms: u128,
-    prio_fee_sol: f64,
-    processor_tip_sol: f64,
-    ack: Option<BloomBuyAck>,
-) -> String {
-    let t0 = Instant::now();
-    let time_str = escape_markdown(&Local::now().format("%H:%M:%S").to_string());
-    let header = format!("🟢 *Buy Successful \\| {}*", time_str);
-
-    let token_line = if let Some(ref a) = ack {
-        if let Some(ref t) = a.token_name {
-            format!("🔹Token: *{}*\n", escape_markdown(t))
-        } else {
-            "".to_string()
-        }
-    } else {
-        "".to_string()
-    };
-
-    let mint_line = format!("🔹`{}`", escape_markdown(mint));
-
-    let amount_str = escape_markdown(&format!("{:.3}", amount_sol));
-    let prio_str = escape_markdown(&format!("{:.3}", prio_fee_sol));
-    let tip_str = escape_markdown(&format!("{:.3}", processor_tip_sol));
-    let value_line = format!(
-        "💰 Value: *{} SOL* \\- Prio Fee: *{} SOL* \\- Processor Tip: *{} SOL*\n",
-        amount_str, prio_str, tip_str
-    );
-
-    let durations_line = if let Some(ref a) = ack {
-        let conf_ms = a.success_time.duration_since(a.pending_time).as_millis();
-        let api_s = escape_markdown(&api_ms.to_string());
-        let conf_s = escape_markdown(&conf_ms.to_string());
-        format!(
-            "⏱️ Durations: *API {} ms* • *Confirmation {} ms*\n",
-            api_s, conf_s
-        )
-    } else {
-        let api_s = escape_markdown(&api_ms.to_string());
-        format!("⏱️ Durations: *API {} ms*\n", api_s)
-    };
-
-    let sig_block = if let Some(ref a) = ack {
-        if let Some(sig) = &a.signature {
-            let sig_esc = escape_markdown(sig);
-            let link = format!("https://solscan.io/tx/{}", sig);
-            format!("\n`{}`\n🔗 [View on Solscan]({})", sig_esc, link)
-        } else {
-            "".to_string()
-        }
-    } else {
-        "".to_string()
-    };
-
-    let s = format!(
-        "{}\n\n{}{}{}\n{}{}",
-        header, token_line, mint_line, "\n", value_line, durations_line
-    ) + &sig_block;
-    let render_us = t0.elapsed().as_micros();
-    log::info!("perf.render_us={} mint={}", render_us, mint);
-    s
-}
-
 async fn process_message(
+    client: UserClientHandle,
     msg: grammers_client::types::update::Message,
+    edited: bool,
     task: Task,
+    channel: TelegramChannel,
+    account_id: i64,
     chat_id: i64,
     user_data_option: Option<UserData>,
-    sent_cas: Arc<Mutex<HashSet<String>>>,
     arrival_ts: Instant,
 ) {
     let hub_queue_us = Instant::now().duration_since(arrival_ts).as_micros();
+    record_hub_queue_us(hub_queue_us as u64);
     log::info!(
         "perf.hub_queue_us={} chat_id={} chan_id={} msg_id={}",
         hub_queue_us,
@@ -385,15 +384,26 @@ async fn process_message(
 
     let task_name = task.name.clone();
     let t_blacklist = Instant::now();
-    let should_listen =
-        task.listen_users.is_empty() || task.listen_users.contains(&effective_sender_id);
+    let forward_author_id = if task.match_forward_author {
+        message_forward_author_id(&msg)
+    } else {
+        None
+    };
+    let filter_sender_id = forward_author_id.unwrap_or(effective_sender_id);
+    let should_listen = channel.accepts_sender(filter_sender_id);
     let message_text = msg.text();
-    handle_auto_join_links(chat_id, &task_name, &message_text).await;
+    handle_auto_join_links(
+        &client,
+        account_id,
+        chat_id,
+        &task,
+        &channel,
+        should_listen,
+        message_text,
+    )
+    .await;
     let sender_name = format_sender_name(sender.as_ref());
-    let channel_name = task
-        .listen_channel_name
-        .as_deref()
-        .unwrap_or("Unknown Channel");
+    let channel_name = channel.display_name();
     let detected_words: Vec<_> = task
         .blacklist_words
         .iter()
@@ -411,13 +421,25 @@ async fn process_message(
         chat_id,
         &task_name,
         format!(
-            "Incoming message in channel {} (sender {}, {} chars)",
+            "Incoming {} in channel {} (sender {}, {} chars)",
+            if edited { "edited message" } else { "message" },
             message_chat_id,
             sender_name,
             message_text.len()
         ),
     );
 
+    if let Some(author_id) = forward_author_id {
+        log_task_event(
+            chat_id,
+            &task_name,
+            format!(
+                "Forwarded message; matching original author {} against monitored users",
+                author_id
+            ),
+        );
+    }
+
     if !should_listen {
         log::info!("perf.skip_not_in_listening_set=1");
         log_task_event(
@@ -444,7 +466,7 @@ async fn process_message(
         );
         let notification = format!(
             "🚫 Blacklist word detected in `{}` from `{}`\\. Skipping\\.\\.\n\n`{}`",
-            escape_markdown(channel_name),
+            escape_markdown(&channel_name),
             escape_markdown(&sender_name),
             escape_markdown(&words_str)
         );
@@ -454,9 +476,30 @@ async fn process_message(
 
     let mut log_buffer = Vec::new();
     let t_ca_start = Instant::now();
-    let mint_opt =
+    let mut mint_opt =
         crate::interfaces::bot::tasks::scraper::find_mint_in_text(message_text, &mut log_buffer)
             .await;
+    let mut mint_from_reply = false;
+    if mint_opt.is_none() && task.include_reply_context && msg.reply_to_message_id().is_some() {
+        match msg.get_reply().await {
+            Ok(Some(reply)) => {
+                mint_opt = crate::interfaces::bot::tasks::scraper::find_mint_in_text(
+                    reply.text(),
+                    &mut log_buffer,
+                )
+                .await;
+                mint_from_reply = mint_opt.is_some();
+            }
+            Ok(None) => {}
+            Err(e) => {
+                log::warn!(
+                    "task.tg: failed to fetch replied message msg_id={} err={}",
+                    msg.id(),
+                    e
+                );
+            }
+        }
+    }
     let ca_extract_us = t_ca_start.elapsed().as_micros();
     let used_llm = log_buffer
         .iter()
@@ -482,57 +525,87 @@ async fn process_message(
     );
 
     if let Some(mint) = mint_opt {
+        let mut source_notes = Vec::new();
+        if edited {
+            source_notes.push("✏️ _CA revealed in an edited message_");
+        }
+        if mint_from_reply {
+            source_notes.push("↩️ _CA taken from the replied\\-to message_");
+        }
+        let source_note: String = source_notes
+            .iter()
+            .map(|note| format!("\n\n{}", note))
+            .collect();
         tokio::spawn(log_buffer_to_ca_detection(
             chat_id,
             mint.clone(),
             log_buffer,
         ));
-        log_task_event(
-            chat_id,
-            &task_name,
-            format!("Detected potential mint {} from {}", mint, sender_name),
-        );
-
-        let t_dedup = Instant::now();
-        let mut sent_cas_guard = sent_cas.lock().await;
-        let is_dup = sent_cas_guard.contains(&mint);
-        if !is_dup {
-            sent_cas_guard.insert(mint.clone());
-        }
-        drop(sent_cas_guard);
-        let dedup_us = t_dedup.elapsed().as_micros();
-        log::info!(
-            "perf.dedup_us={} dup={}",
-            dedup_us,
-            if is_dup { 1 } else { 0 }
-        );
-        let dedup_ms = (dedup_us as f64 / 1000.0).max(0.01);
         log_task_event(
             chat_id,
             &task_name,
             format!(
-                "CA deduplication completed in {:.2} ms (duplicate: {})",
-                dedup_ms,
-                if is_dup { "yes" } else { "no" }
+                "Detected potential mint {} from {}{}",
+                mint,
+                sender_name,
+                if mint_from_reply {
+                    " (replied-to message)"
+                } else {
+                    ""
+                }
             ),
         );
-        if is_dup {
-            log::info!("perf.duplicate_ca=1 mint={}", mint);
+
+        let market_fallback_reason =
+            match check_market_before_buy(&task, &mint, t_all_start, |message| {
+                log_task_event(chat_id, &task_name, message)
+            })
+            .await
+            {
+                MarketGateDecision::Proceed => None,
+                MarketGateDecision::Skip(message) => {
+                    send_notification_markdown(chat_id, message + &source_note).await;
+                    return;
+                }
+                MarketGateDecision::InformOnly(reason) => Some(reason),
+            };
+
+        if task.inform_only || market_fallback_reason.is_some() {
+            let t_dedup = Instant::now();
+            let alert = claim_alert(chat_id, &task, &mint).await;
+            let dedup_us = t_dedup.elapsed().as_micros();
+            log::info!(
+                "perf.dedup_us={} dup={} scope=alert persisted={}",
+                dedup_us,
+                if alert.duplicate { 1 } else { 0 },
+                if alert.persisted { 1 } else { 0 }
+            );
+            let dedup_ms = (dedup_us as f64 / 1000.0).max(0.01);
             log_task_event(
                 chat_id,
                 &task_name,
-                format!("Duplicate mint {} ignored", mint),
+                format!(
+                    "Alert deduplication completed in {:.2} ms (duplicate: {})",
+                    dedup_ms,
+                    if alert.duplicate { "yes" } else { "no" }
+                ),
             );
-            return;
-        }
+            if alert.duplicate {
+                log::info!("perf.duplicate_ca=1 mint={}", mint);
+                log_task_event(
+                    chat_id,
+                    &task_name,
+                    format!("Duplicate mint {} ignored", mint),
+                );
+                return;
+            }
 
-        if task.inform_only {
             let time_str = escape_markdown(&Local::now().format("%H:%M:%S").to_string());
             let total_ms = t_all_start.elapsed().as_millis();
 
             let header = format!("🔍 *Token Detected \\| {}*", time_str);
             let mint_line = format!("🪙 `{}`", escape_markdown(&mint));
-            let channel_line = format!("📢 Channel: *{}*", escape_markdown(channel_name));
+            let channel_line = format!("📢 Channel: *{}*", escape_markdown(&channel_name));
             let sender_line = format!("👤 Sender: *{}*", escape_markdown(&sender_name));
 
             let perf_lines = format!(
@@ -554,13 +627,19 @@ async fn process_message(
                 message_text[..end].to_string()
             };
 
+            let fallback_block = market_fallback_reason
+                .as_deref()
+                .map(build_market_fallback_block)
+                .unwrap_or_default();
+
             let notification = format!(
-                "{}\n\n{}\n{}\n{}\n\n{}\n\n📝 *Message Preview*\n```\n{}\n```",
+                "{}\n\n{}\n{}\n{}\n\n{}{}\n\n📝 *Message Preview*\n```\n{}\n```",
                 header,
                 mint_line,
                 channel_line,
                 sender_line,
                 perf_lines,
+                fallback_block,
                 escape_markdown(&preview_text)
             );
 
@@ -582,212 +661,18 @@ async fn process_message(
                 &task_name,
                 format!("Inform-only alert dispatched for mint {}", mint),
             );
-            send_notification_markdown(chat_id, notification).await;
+            send_notification_markdown(chat_id, notification + &source_note).await;
         } else {
-            if let Some(user_data) = user_data_option {
-                if let Some((wallet_address, wallet_label)) = resolve_task_wallet(&task, &user_data)
-                {
-                    let (tx, rx) = oneshot::channel();
-                    PENDING_BLOOM_RESPONSES.lock().insert(mint.clone(), tx);
-
-                    let api_request_start_time = Instant::now();
-                    let buy_result = bloom_buy(
-                        &mint,
-                        task.buy_amount_sol,
-                        task.buy_slippage_percent,
-                        task.buy_priority_fee_sol,
-                        wallet_address.as_str(),
-                        wallet_label.as_str(),
-                    )
-                    .await;
-                    let api_duration = api_request_start_time.elapsed();
-                    let api_us = api_duration.as_micros();
-                    let api_ms = (api_us as f64 / 1000.0).max(0.01);
-                    log_task_event(
-                        chat_id,
-                        &task_name,
-                        format!(
-                            "Bloom buy initiated for mint {} ({} SOL, slippage {}%)",
-                            mint, task.buy_amount_sol, task.buy_slippage_percent
-                        ),
-                    );
-
-                    match buy_result {
-                        Ok(_) => {
-                            let ack_wait_start = Instant::now();
-                            let bot_response_result =
-                                tokio::time::timeout(Duration::from_secs(20), rx).await;
-                            match bot_response_result {
-                                Ok(Ok(ack)) => {
-                                    let ack_wait_us = ack_wait_start.elapsed().as_micros();
-                                    let signature_opt = ack.signature.clone();
-                                    let msg_text = build_buy_success_message(
-                                        &mint,
-                                        task.buy_amount_sol,
-                                        api_duration.as_millis(),
-                                        task.buy_priority_fee_sol,
-                                        task.buy_priority_fee_sol,
-                                        Some(ack),
-                                    );
-                                    let total_us = t_all_start.elapsed().as_micros();
-                                    log::info!(
-                                        "perf.api_us={} perf.ack_wait_us={} perf.total_us={} mint={}",
-                                        api_us,
-                                        ack_wait_us,
-                                        total_us,
-                                        mint
-                                    );
-                                    let ack_wait_ms = (ack_wait_us as f64 / 1000.0).max(0.01);
-                                    let total_ms = (total_us as f64 / 1000.0).max(0.01);
-                                    log_task_event(
-                                        chat_id,
-                                        &task_name,
-                                        format!(
-                                            "Bloom buy timings -> API: {:.2} ms | ACK: {:.2} ms (success) | Pipeline total: {:.2} ms",
-                                            api_ms, ack_wait_ms, total_ms
-                                        ),
-                                    );
-                                    if let Some(signature) = signature_opt {
-                                        log_task_event(
-                                            chat_id,
-                                            &task_name,
-                                            format!(
-                                                "Bloom buy confirmed for mint {} (signature {})",
-                                                mint, signature
-                                            ),
-                                        );
-                                    } else {
-                                        log_task_event(
-                                            chat_id,
-                                            &task_name,
-                                            format!("Bloom buy confirmed for mint {}", mint),
-                                        );
-                                    }
-                                    send_notification_markdown(chat_id, msg_text).await;
-                                }
-                                Ok(Err(_)) => {
-                                    let ack_wait_us = ack_wait_start.elapsed().as_micros();
-                                    let msg_text = build_buy_success_message(
-                                        &mint,
-                                        task.buy_amount_sol,
-                                        api_duration.as_millis(),
-                                        task.buy_priority_fee_sol,
-                                        task.buy_priority_fee_sol,
-                                        None,
-                                    );
-                                    let total_us = t_all_start.elapsed().as_micros();
-                                    log::info!(
-                                        "perf.api_us={} perf.ack_wait_us={} perf.total_us={} ack_channel_closed=1 mint={}",
-                                        api_us,
-                                        ack_wait_us,
-                                        total_us,
-                                        mint
-                                    );
-                                    let ack_wait_ms = (ack_wait_us as f64 / 1000.0).max(0.01);
-                                    let total_ms = (total_us as f64 / 1000.0).max(0.01);
-                                    log_task_event(
-                                        chat_id,
-                                        &task_name,
-                                        format!(
-                                            "Bloom buy timings -> API: {:.2} ms | ACK: {:.2} ms (channel closed) | Pipeline total: {:.2} ms",
-                                            api_ms, ack_wait_ms, total_ms
-                                        ),
-                                    );
-                                    log_task_event(
-                                        chat_id,
-                                        &task_name,
-                                        format!(
-                                            "Bloom buy finished for mint {} (ACK channel closed)",
-                                            mint
-                                        ),
-                                    );
-                                    send_notification_markdown(chat_id, msg_text).await;
-                                }
-                                Err(_) => {
-                                    let ack_wait_us = ack_wait_start.elapsed().as_micros();
-                                    let msg_text = build_buy_success_message(
-                                        &mint,
-                                        task.buy_amount_sol,
-                                        api_duration.as_millis(),
-                                        task.buy_priority_fee_sol,
-                                        task.buy_priority_fee_sol,
-                                        None,
-                                    );
-                                    let total_us = t_all_start.elapsed().as_micros();
-                                    log::info!(
-                                        "perf.api_us={} perf.ack_wait_us={} perf.total_us={} ack_timeout=1 mint={}",
-                                        api_us,
-                                        ack_wait_us,
-                                        total_us,
-                                        mint
-                                    );
-                                    let ack_wait_ms = (ack_wait_us as f64 / 1000.0).max(0.01);
-                                    let total_ms = (total_us as f64 / 1000.0).max(0.01);
-                                    log_task_event(
-                                        chat_id,
-                                        &task_name,
-                                        format!(
-                                            "Bloom buy timings -> API: {:.2} ms | ACK: {:.2} ms (timeout) | Pipeline total: {:.2} ms",
-                                            api_ms, ack_wait_ms, total_ms
-                                        ),
-                                    );
-                                    log_task_event(
-                                        chat_id,
-                                        &task_name,
-                                        format!(
-                                            "Bloom buy pending for mint {} (ACK timeout)",
-                                            mint
-                                        ),
-                                    );
-                                    send_notification_markdown(chat_id, msg_text).await;
-                                }
-                            };
-                        }
-                        Err(e) => {
-                            PENDING_BLOOM_RESPONSES.lock().remove(&mint);
-                            let total_us = t_all_start.elapsed().as_micros();
-                            log::info!(
-                                "perf.api_us={} perf.total_us={} buy_error=1 err=\"{}\" mint={}",
-                                api_us,
-                                total_us,
-                                e,
-                                mint
-                            );
-                            let total_ms = (total_us as f64 / 1000.0).max(0.01);
-                            log_task_event(
-                                chat_id,
-                                &task_name,
-                                format!(
-                                    "Bloom buy timings -> API: {:.2} ms | Pipeline total: {:.2} ms (failure)",
-                                    api_ms, total_ms
-                                ),
-                            );
-                            log_task_event(
-                                chat_id,
-                                &task_name,
-                                format!("Bloom buy failed for mint {}: {}", mint, e),
-                            );
-                            let error_msg = format!(
-                                "❌ *Buy Request Failed*\n\n*Token:* `{}`\n*Error:* `{}`",
-                                escape_markdown(&mint),
-                                escape_markdown(&e.to_string())
-                            );
-                            send_notification_markdown(chat_id, error_msg).await;
-                        }
-                    }
-                } else {
-                    let total_us = t_all_start.elapsed().as_micros();
-                    log::info!("perf.total_us={} no_bloom_wallet=1", total_us);
-                    let no_wallet_msg =
-                        "❌ *Buy Error*\n\nNo Bloom wallet configured for this task.".to_string();
-                    log_task_event(
-                        chat_id,
-                        &task_name,
-                        "Bloom buy skipped: no Bloom wallet configured".to_string(),
-                    );
-                    send_notification_markdown(chat_id, no_wallet_msg).await;
-                }
-            }
+            run_buy_path(
+                chat_id,
+                &task,
+                user_data_option.as_ref(),
+                &mint,
+                t_all_start,
+                &source_note,
+                |message| log_task_event(chat_id, &task_name, message),
+            )
+            .await;
         }
     } else {
         let total_us = t_all_start.elapsed().as_micros();
@@ -800,18 +685,63 @@ async fn process_message(
     }
 }
 
-pub async fn start_task_monitor(initial_task: Task, chat_id: i64) {
-    let handle = USER_CLIENT_HANDLE.lock().clone();
-    if let Some(_client) = handle {
-        if initial_task.listen_channels.is_empty() {
-            log::warn!("task.tg: task has no channels name={}", initial_task.name);
+fn message_forward_author_id(msg: &grammers_client::types::update::Message) -> Option<i64> {
+    let tl::enums::Message::Message(raw) = &msg.raw else {
+        return None;
+    };
+    let tl::enums::MessageFwdHeader::Header(header) = raw.fwd_from.as_ref()?;
+    match header.from_id.as_ref()? {
+        tl::enums::Peer::User(user) => Some(user.user_id),
+        tl::enums::Peer::Chat(chat) => Some(chat.chat_id),
+        tl::enums::Peer::Channel(channel) => Some(channel.channel_id),
+    }
+}
+
+fn message_topic_id(msg: &grammers_client::types::update::Message) -> Option<i32> {
+    let tl::enums::Message::Message(raw) = &msg.raw else {
+        return None;
+    };
+    match raw.reply_to.as_ref()? {
+        tl::enums::MessageReplyHeader::Header(header) if header.forum_topic => {
+            header.reply_to_top_id.or(header.reply_to_msg_id)
+        }
+        _ => None,
+    }
+}
+
+pub async fn start_task_monitor(mut initial_task: Task, chat_id: i64) {
+    if initial_task.enabled_channels().next().is_none() {
+        log::warn!("task.tg: task has no channels name={}", initial_task.name);
+        log_task_event(
+            chat_id,
+            &initial_task.name,
+            "Task has no channels configured; cannot start monitor".to_string(),
+        );
+        return;
+    }
+
+    let account_id = match ensure_task_session(&initial_task).await {
+        Ok(account_id) => account_id,
+        Err(e) => {
+            log::warn!(
+                "task.tg: user session unavailable task={} err={}",
+                initial_task.name,
+                e
+            );
             log_task_event(
                 chat_id,
                 &initial_task.name,
-                "Task has no channels configured; cannot start monitor".to_string(),
+                format!(
+                    "Telegram user session unavailable ({}); task monitor not started",
+                    e
+                ),
             );
             return;
         }
+    };
+
+    if let Some(client) = session_client(account_id) {
+        initial_task.telegram_user_id = Some(account_id);
 
         let task_name = initial_task.name.clone();
         let task_state = state::ensure_task_state(chat_id, initial_task).await;
@@ -822,6 +752,7 @@ pub async fn start_task_monitor(initial_task: Task, chat_id: i64) {
             let mut sessions = crate::ACTIVE_TASK_SESSIONS.lock();
             sessions.insert((chat_id, task_name.clone()), session_id);
         }
+        let mut rx = subscribe_task(account_id, chat_id, &*task_state.read().await);
         log::info!(
             "task.tg: registered session_id={} task={}",
             session_id,
@@ -830,17 +761,16 @@ pub async fn start_task_monitor(initial_task: Task, chat_id: i64) {
         log_task_event(
             chat_id,
             &task_name,
-            "Telegram task monitor started".to_string(),
+            format!("Telegram task monitor started on account {}", account_id),
         );
 
         tokio::spawn(async move {
-            let sent_cas = Arc::new(Mutex::new(HashSet::<String>::new()));
-            let mut rx = update_bus::subscribe();
             log::info!(
-                "task.tg: worker start user_chat={} task={} session={}",
+                "task.tg: worker start user_chat={} task={} session={} account={}",
                 chat_id,
                 task_name,
-                session_id
+                session_id,
+                account_id
             );
             loop {
                 let current_session_id = {
@@ -861,13 +791,27 @@ pub async fn start_task_monitor(initial_task: Task, chat_id: i64) {
                     break;
                 }
 
-                let update = match rx.recv().await {
-                    Ok(u) => u,
-                    Err(e) => {
-                        log::error!("task.tg: bus recv error {}", e);
-                        log_task_event(chat_id, &task_name, format!("Update bus error: {}", e));
-                        break;
+                let Some(update) = rx.recv().await else {
+                    let replaced = crate::ACTIVE_TASK_SESSIONS
+                        .lock()
+                        .get(&(chat_id, task_name.clone()))
+                        .copied()
+                        != Some(session_id);
+                    if replaced {
+                        continue;
                     }
+                    let message = if task_state.read().await.active {
+                        "Update route closed; task monitor stopped"
+                    } else {
+                        "Task deactivated, stopping monitor"
+                    };
+                    log::info!(
+                        "task.tg: route closed stopping session={} task={}",
+                        session_id,
+                        task_name
+                    );
+                    log_task_event(chat_id, &task_name, message.to_string());
+                    break;
                 };
 
                 let task_snapshot = { task_state.read().await.clone() };
@@ -882,45 +826,60 @@ pub async fn start_task_monitor(initial_task: Task, chat_id: i64) {
                     break;
                 }
 
-                let target_channel = match task_snapshot.listen_channels.first().copied() {
-                    Some(id) => id,
-                    None => {
-                        log::warn!("task.tg: no channel configured task={}", task_snapshot.name);
-                        log_task_event(
-                            chat_id,
-                            &task_name,
-                            "No Telegram channel configured; stopping monitor".to_string(),
-                        );
-                        continue;
+                let (msg, edited) = match &update.update {
+                    grammers_client::Update::NewMessage(msg) => (msg, false),
+                    grammers_client::Update::MessageEdited(msg) if task_snapshot.process_edits => {
+                        (msg, true)
                     }
+                    _ => continue,
                 };
-
-                if let grammers_client::Update::NewMessage(msg) = &update.update {
-                    if msg.chat().id() == target_channel {
-                        let user_data_option = match user_data_state.as_ref() {
-                            Some(state) => Some(state.read().await.clone()),
-                            None => None,
-                        };
-                        let sent_cas_clone = Arc::clone(&sent_cas);
-                        let arrival_ts = update.ts;
-                        tokio::spawn(process_message(
-                            msg.clone(),
-                            task_snapshot.clone(),
+                if let Some(channel) = task_snapshot
+                    .monitored_channel(msg.chat().id())
+                    .filter(|channel| channel.accepts_topic(message_topic_id(msg)))
+                {
+                    if task_snapshot.process_edits {
+                        let changed = message_text_changed(
                             chat_id,
-                            user_data_option,
-                            sent_cas_clone,
-                            arrival_ts,
-                        ));
+                            &task_name,
+                            msg.chat().id(),
+                            msg.id(),
+                            msg.text(),
+                        );
+                        if edited && !changed {
+                            log::info!(
+                                "task.tg: unchanged edit ignored chan_id={} msg_id={}",
+                                msg.chat().id(),
+                                msg.id()
+                            );
+                            continue;
+                        }
                     }
+                    let user_data_option = match user_data_state.as_ref() {
+                        Some(state) => Some(state.read().await.clone()),
+                        None => None,
+                    };
+                    let arrival_ts = update.ts;
+                    let client = session_client(account_id).unwrap_or_else(|| client.clone());
+                    tokio::spawn(process_message(
+                        client,
+                        msg.clone(),
+                        edited,
+                        task_snapshot.clone(),
+                        channel.clone(),
+                        account_id,
+                        chat_id,
+                        user_data_option,
+                        arrival_ts,
+                    ));
                 }
             }
         });
     } else {
-        log::warn!("task.tg: user client not logged in");
+        log::warn!("task.tg: user session not running account={}", account_id);
         log_task_event(
             chat_id,
             &initial_task.name,
-            "Telegram user client not logged in; task monitor not started".to_string(),
+            "Telegram user session is not running; task monitor not started".to_string(),
         );
     }
 }
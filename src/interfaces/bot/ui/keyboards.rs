@@ -1,4 +1,4 @@
-use crate::interfaces::bot::data::{BloomWalletInfo, Task};
+use crate::interfaces::bot::data::{BloomAccount, BloomWalletInfo, Task};
 use crate::interfaces::bot::ui::State;
 use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup};
 
@@ -23,6 +23,31 @@ pub fn tasks_menu_keyboard(tasks: &Vec<Task>) -> InlineKeyboardMarkup {
         "➕ Create New Task",
         "create_task",
     )]);
+    buttons.push(vec![InlineKeyboardButton::callback(
+        "🌸 Bloom Accounts",
+        "bloom_accounts",
+    )]);
+    InlineKeyboardMarkup::new(buttons)
+}
+
+pub fn bloom_accounts_keyboard(accounts: &[BloomAccount]) -> InlineKeyboardMarkup {
+    let mut buttons: Vec<Vec<InlineKeyboardButton>> = accounts
+        .iter()
+        .map(|account| {
+            vec![InlineKeyboardButton::callback(
+                format!("🗑 Remove {}", account.name),
+                format!("bloom_account_remove_{}", account.name),
+            )]
+        })
+        .collect();
+    buttons.push(vec![InlineKeyboardButton::callback(
+        "➕ Add / Update Account",
+        "bloom_account_add",
+    )]);
+    buttons.push(vec![
+        InlineKeyboardButton::callback("🔄 Refresh", "bloom_accounts"),
+        InlineKeyboardButton::callback("← Back", "main_menu"),
+    ]);
     InlineKeyboardMarkup::new(buttons)
 }
 
@@ -31,6 +56,23 @@ pub fn task_detail_keyboard(task: &Task) -> InlineKeyboardMarkup {
 
     let active_status_icon = if task.active { "🟢" } else { "🔴" };
     let inform_only_icon = if task.inform_only { "🟢" } else { "🔴" };
+    let safety_checks_icon = if task.safety_checks_enabled {
+        "🟢"
+    } else {
+        "🔴"
+    };
+    let dry_run_icon = if task.dry_run { "🟢" } else { "🔴" };
+    let process_edits_icon = if task.process_edits { "🟢" } else { "🔴" };
+    let reply_context_icon = if task.include_reply_context {
+        "🟢"
+    } else {
+        "🔴"
+    };
+    let forward_author_icon = if task.match_forward_author {
+        "🟢"
+    } else {
+        "🔴"
+    };
 
     let mut buttons: Vec<Vec<InlineKeyboardButton>> = Vec::new();
 
@@ -81,25 +123,14 @@ pub fn task_detail_keyboard(task: &Task) -> InlineKeyboardMarkup {
     ]);
 
     if task.platform == Platform::Telegram {
-        let has_session = task.has_telegram_user_session();
-        buttons.push(vec![InlineKeyboardButton::callback(
-            "👥 Telegram Users to Monitor",
-            format!("task_users_{}", task.name),
-        )]);
-        let has_channel = has_session
-            && task
-                .listen_channel_name
-                .as_ref()
-                .map(|value| !value.trim().is_empty())
-                .unwrap_or_else(|| !task.listen_channels.is_empty());
-        let channel_button_text = if has_session {
-            if has_channel {
-                "📢 Change Channel".to_string()
-            } else {
-                "📢 Set Channel".to_string()
-            }
+        let channel_button_text = if task.has_telegram_user_session() && !task.channels.is_empty() {
+            format!(
+                "📢 Channels ({}/{})",
+                task.enabled_channels().count(),
+                task.channels.len()
+            )
         } else {
-            "📢 Set Channel".to_string()
+            "📢 Set Channels".to_string()
         };
         buttons.push(vec![
             InlineKeyboardButton::callback(
@@ -111,6 +142,31 @@ pub fn task_detail_keyboard(task: &Task) -> InlineKeyboardMarkup {
                 format!("task_toggle_inform_{}", task.name),
             ),
         ]);
+        buttons.push(vec![
+            InlineKeyboardButton::callback(
+                format!("{} Edits", process_edits_icon),
+                format!("task_toggle_edits_{}", task.name),
+            ),
+            InlineKeyboardButton::callback(
+                format!("{} Replies", reply_context_icon),
+                format!("task_toggle_replies_{}", task.name),
+            ),
+            InlineKeyboardButton::callback(
+                format!("{} Forwards", forward_author_icon),
+                format!("task_toggle_forwards_{}", task.name),
+            ),
+        ]);
+        buttons.push(vec![
+            InlineKeyboardButton::callback(
+                format!("🔗 Auto-Join: {}", task.auto_join.mode.label()),
+                format!("task_autojoin_mode_{}", task.name),
+            ),
+            InlineKeyboardButton::callback(
+                "⚙️ Join Limits",
+                format!("task_autojoin_settings_{}", task.name),
+            ),
+            InlineKeyboardButton::callback("📜 Joined", format!("task_autojoin_log_{}", task.name)),
+        ]);
     } else {
         buttons.push(vec![InlineKeyboardButton::callback(
             "👥 Discord Users to Monitor",
@@ -138,11 +194,71 @@ pub fn task_detail_keyboard(task: &Task) -> InlineKeyboardMarkup {
         ]);
     }
 
+    buttons.push(vec![
+        InlineKeyboardButton::callback(
+            format!("🚫 Blacklist Words ({})", task.blacklist_words.len()),
+            format!("task_blacklist_{}", task.name),
+        ),
+        InlineKeyboardButton::callback(
+            format!("{} Safety Checks", safety_checks_icon),
+            format!("task_safety_toggle:{}", task.name),
+        ),
+    ]);
+
+    buttons.push(vec![
+        InlineKeyboardButton::callback(
+            "📉 Market Filters",
+            format!("task_market_filters_{}", task.name),
+        ),
+        InlineKeyboardButton::callback(
+            format!("⚡ {}", task.execution_mode.label()),
+            format!("task_exec_mode_{}", task.name),
+        ),
+    ]);
+
+    let retry_text = if task.retry_policy.is_enabled() {
+        format!("🔄 Retry: {}x", task.retry_policy.max_attempts)
+    } else {
+        "🔄 Retry: Off".to_string()
+    };
+    let schedule_text = if task.schedule.is_some() {
+        "⏰ Schedule: On"
+    } else {
+        "⏰ Schedule: Manual"
+    };
+    buttons.push(vec![
+        InlineKeyboardButton::callback(retry_text, format!("task_retry_{}", task.name)),
+        InlineKeyboardButton::callback(schedule_text, format!("task_schedule_{}", task.name)),
+    ]);
+
     buttons.push(vec![InlineKeyboardButton::callback(
-        format!("🚫 Blacklist Words ({})", task.blacklist_words.len()),
-        format!("task_blacklist_{}", task.name),
+        format!(
+            "🌸 Bloom Account: {}",
+            task.bloom_account.as_deref().unwrap_or("Default")
+        ),
+        format!("task_bloom_account_{}", task.name),
     )]);
 
+    buttons.push(vec![
+        InlineKeyboardButton::callback(
+            format!("🔁 Dedup: {}", task.dedup_scope.label()),
+            format!("task_dedup_scope_{}", task.name),
+        ),
+        InlineKeyboardButton::callback(
+            format!("⏳ TTL: {}", task.dedup_ttl_display()),
+            format!("task_dedup_ttl_{}", task.name),
+        ),
+        InlineKeyboardButton::callback("♻️ Clear Mint", format!("task_dedup_clear_{}", task.name)),
+    ]);
+
+    buttons.push(vec![
+        InlineKeyboardButton::callback(
+            format!("{} Dry Run", dry_run_icon),
+            format!("task_toggle_dryrun_{}", task.name),
+        ),
+        InlineKeyboardButton::callback("📊 Paper PnL", format!("task_paper_pnl_{}", task.name)),
+    ]);
+
     buttons.push(vec![
         InlineKeyboardButton::callback(
             format!("{} Active", active_status_icon),
@@ -156,6 +272,75 @@ pub fn task_detail_keyboard(task: &Task) -> InlineKeyboardMarkup {
     InlineKeyboardMarkup::new(buttons)
 }
 
+pub fn task_channels_keyboard(task: &Task) -> InlineKeyboardMarkup {
+    let mut buttons: Vec<Vec<InlineKeyboardButton>> = task
+        .channels
+        .iter()
+        .map(|channel| {
+            let status_icon = if channel.enabled { "🟢" } else { "🔴" };
+            let mut row = vec![
+                InlineKeyboardButton::callback(
+                    format!("{} {}", status_icon, channel.display_name()),
+                    format!("task_chan_toggle_{}_{}", task.name, channel.id),
+                ),
+                InlineKeyboardButton::callback(
+                    "👥 Users",
+                    format!("task_chan_users_{}_{}", task.name, channel.id),
+                ),
+            ];
+            if !channel.is_broadcast {
+                row.push(InlineKeyboardButton::callback(
+                    "🧵 Topics",
+                    format!("task_chan_topics_{}_{}", task.name, channel.id),
+                ));
+            }
+            row.push(InlineKeyboardButton::callback(
+                "🗑",
+                format!("task_chan_remove_{}_{}", task.name, channel.id),
+            ));
+            row
+        })
+        .collect();
+    buttons.push(vec![InlineKeyboardButton::callback(
+        "➕ Add Channel",
+        format!("task_chan_add_{}", task.name),
+    )]);
+    buttons.push(vec![InlineKeyboardButton::callback(
+        "← Back",
+        format!("task_detail_{}", task.name),
+    )]);
+    InlineKeyboardMarkup::new(buttons)
+}
+
+pub fn task_join_log_keyboard(task_name: &str) -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::new(vec![
+        vec![InlineKeyboardButton::callback(
+            "🔄 Refresh",
+            format!("task_autojoin_log_{}", task_name),
+        )],
+        vec![InlineKeyboardButton::callback(
+            "← Back",
+            format!("task_detail_{}", task_name),
+        )],
+    ])
+}
+
+pub fn task_paper_report_keyboard(task_name: &str) -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::new(vec![
+        vec![
+            InlineKeyboardButton::callback("🔄 Refresh", format!("task_paper_pnl_{}", task_name)),
+            InlineKeyboardButton::callback(
+                "🧹 Reset Paper",
+                format!("task_paper_reset_{}", task_name),
+            ),
+        ],
+        vec![InlineKeyboardButton::callback(
+            "← Back",
+            format!("task_detail_{}", task_name),
+        )],
+    ])
+}
+
 pub fn task_delete_confirmation_keyboard(task_name: &str) -> InlineKeyboardMarkup {
     let clean_task_name = task_name
         .strip_prefix("task_delete_confirm_")
@@ -264,7 +449,69 @@ pub async fn user_selection_keyboard(state: &State) -> Option<InlineKeyboardMark
 
         buttons.push(vec![InlineKeyboardButton::callback(
             "← Back",
-            format!("task_detail_{}", task_name),
+            format!("task_channels_{}", task_name),
+        )]);
+        Some(InlineKeyboardMarkup::new(buttons))
+    } else {
+        None
+    }
+}
+
+pub async fn topic_selection_keyboard(state: &State) -> Option<InlineKeyboardMarkup> {
+    if let State::TaskSelectTopicsFromList {
+        task_name,
+        all_topics,
+        selected_topics,
+        page,
+        ..
+    } = state
+    {
+        let mut buttons: Vec<Vec<InlineKeyboardButton>> = Vec::new();
+        let start = page * ITEMS_PER_PAGE;
+        let end = (start + ITEMS_PER_PAGE).min(all_topics.len());
+
+        let all_check = if selected_topics.is_empty() {
+            "✅"
+        } else {
+            " "
+        };
+        buttons.push(vec![InlineKeyboardButton::callback(
+            format!("{} 🌐 All Topics", all_check),
+            format!("task_topic_clear_{}", task_name),
+        )]);
+
+        for (title, id) in &all_topics[start..end] {
+            let check = if selected_topics.contains(id) {
+                "✅"
+            } else {
+                " "
+            };
+            buttons.push(vec![InlineKeyboardButton::callback(
+                format!("{} {}", check, title),
+                format!("task_topic_toggle_{}_{}", task_name, id),
+            )]);
+        }
+
+        let mut nav_row = Vec::new();
+        if *page > 0 {
+            nav_row.push(InlineKeyboardButton::callback(
+                "< Prev",
+                format!("task_topic_page_{}_{}", task_name, page - 1),
+            ));
+        }
+        if end < all_topics.len() {
+            nav_row.push(InlineKeyboardButton::callback(
+                "Next >",
+                format!("task_topic_page_{}_{}", task_name, page + 1),
+            ));
+        }
+        if !nav_row.is_empty() {
+            buttons.push(nav_row);
+        }
+
+        buttons.push(vec![InlineKeyboardButton::callback(
+            "← Back",
+            format!("task_channels_{}", task_name),
         )]);
         Some(InlineKeyboardMarkup::new(buttons))
     } else {
@@ -303,6 +550,43 @@ pub fn task_settings_keyboard(task: &Task) -> InlineKeyboardMarkup {
         discord_label,
         format!("task_discord_token_{}", task.name),
     )]);
+    let toggle_icon = |enabled: bool| if enabled { "🟢" } else { "🔴" };
+    buttons.push(vec![
+        InlineKeyboardButton::callback(
+            format!("{} Anti-MEV", toggle_icon(task.bloom_anti_mev)),
+            format!("task_bloom_antimev_{}", task.name),
+        ),
+        InlineKeyboardButton::callback(
+            format!("{} Auto Tip", toggle_icon(task.bloom_auto_tip)),
+            format!("task_bloom_autotip_{}", task.name),
+        ),
+    ]);
+    buttons.push(vec![
+        InlineKeyboardButton::callback(
+            format!("{} Skip If Bought", toggle_icon(task.bloom_skip_if_bought)),
+            format!("task_bloom_skipbought_{}", task.name),
+        ),
+        InlineKeyboardButton::callback(
+            format!("{} Hedged Regions", toggle_icon(task.bloom_hedged)),
+            format!("task_bloom_hedge_{}", task.name),
+        ),
+    ]);
+    buttons.push(vec![
+        InlineKeyboardButton::callback(
+            format!("Tip: {} SOL", task.processor_tip_sol()),
+            format!("task_bloom_tip_{}", task.name),
+        ),
+        InlineKeyboardButton::callback(
+            format!(
+                "Dev Sell: {}",
+                task.bloom_swap_options()
+                    .dev_sell
+                    .as_deref()
+                    .unwrap_or("Off")
+            ),
+            format!("task_bloom_devsell_{}", task.name),
+        ),
+    ]);
     buttons.push(vec![InlineKeyboardButton::callback(
         "← Back to Task",
         format!("task_detail_{}", task.name),
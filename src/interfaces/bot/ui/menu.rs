@@ -1,5 +1,7 @@
 use crate::application::pricing::SolPriceState;
-use crate::interfaces::bot::data::{Task, get_user_data};
+use crate::infrastructure::blockchain::bloom::listener_status;
+use crate::interfaces::bot::data::{BloomAccount, Task, TelegramChannel, get_user_data};
+use crate::interfaces::bot::user::sessions::{is_session_running, session_display_name};
 use crate::{BLOOM_WS_CONNECTION, BloomWsConnectionStatus};
 use redis::Client as RedisClient;
 
@@ -83,6 +85,98 @@ pub async fn generate_tasks_text(redis_client: RedisClient, chat_id: i64) -> Str
     }
 }
 
+fn bloom_ws_status_label(status: BloomWsConnectionStatus) -> &'static str {
+    match status {
+        BloomWsConnectionStatus::Connecting => "🟡 Connecting",
+        BloomWsConnectionStatus::Connected => "🟢 Connected",
+        BloomWsConnectionStatus::Disconnected => "🔴 Disconnected",
+        BloomWsConnectionStatus::Unavailable => "⚪ Unavailable",
+    }
+}
+
+pub fn generate_bloom_accounts_text(chat_id: i64, accounts: &[BloomAccount]) -> String {
+    let mut text = "🌸 *Bloom Accounts*\n\n".to_string();
+    if accounts.is_empty() {
+        text.push_str(&escape_markdown(
+            "No Bloom accounts yet. Tasks use the server BLOOM_AUTH_TOKEN until you add one.",
+        ));
+        return text;
+    }
+    let lines = accounts
+        .iter()
+        .enumerate()
+        .map(|(index, account)| {
+            let ws_label = listener_status(&account.credentials(chat_id).account_key)
+                .map(|(status, _)| bloom_ws_status_label(status))
+                .unwrap_or("⚪ Not started");
+            format!(
+                "*{}*{}\n• WS: {}\n• Token: {}",
+                escape_markdown(&account.name),
+                if index == 0 {
+                    escape_markdown(" (default)")
+                } else {
+                    String::new()
+                },
+                escape_markdown(ws_label),
+                escape_markdown(&account.expiry_label())
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    text.push_str(&lines);
+    text.push_str("\n\n");
+    text.push_str(&escape_markdown(
+        "Tasks without an explicit account use the default (first) account.",
+    ));
+    text
+}
+
+fn format_channel_lines(channels: &[TelegramChannel]) -> String {
+    channels
+        .iter()
+        .map(|channel| {
+            let monitoring = if channel.enabled {
+                channel.monitoring_label()
+            } else {
+                "Disabled".to_string()
+            };
+            let mut line = format!(
+                "  • `{}` \\- `{}`",
+                escape_markdown(&channel.display_name()),
+                escape_markdown(&monitoring)
+            );
+            if !channel.topics.is_empty() {
+                line.push_str(&format!(
+                    "\n    🧵 Topics: `{}`",
+                    escape_markdown(&channel.topics_label())
+                ));
+            }
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn generate_task_channels_text(task: &Task) -> String {
+    let heading = format!(
+        "📢 *{}*",
+        escape_markdown(&format!("Telegram Channels - {}", task.name))
+    );
+    let body = if task.channels.is_empty() {
+        escape_markdown("No channels yet. Add a channel or group to start monitoring.")
+    } else {
+        format_channel_lines(&task.channels)
+    };
+    format!(
+        "{}\n\n{}\n\n{}",
+        heading,
+        body,
+        escape_markdown(
+            "Tap a channel to enable or disable it, 👥 to pick the users monitored in that channel, or 🗑 to remove it."
+        )
+    )
+}
+
 pub async fn generate_task_detail_text(
     _redis_client: RedisClient,
     _chat_id: i64,
@@ -104,6 +198,18 @@ pub async fn generate_task_detail_text(
         "".to_string()
     };
 
+    let safety_checks_str = if task.safety_checks_enabled {
+        "Enabled"
+    } else {
+        "Disabled"
+    };
+
+    let dry_run_str = if task.dry_run {
+        "Enabled (paper trading)"
+    } else {
+        "Disabled"
+    };
+
     let platform_str = match task.platform {
         Platform::Telegram => "Telegram",
         Platform::Discord => "Discord",
@@ -125,52 +231,49 @@ pub async fn generate_task_detail_text(
 
     let platform_details = match task.platform {
         Platform::Telegram => {
-            let has_user_session = task.has_telegram_user_session();
-            let channel_name_str = if has_user_session {
-                task.listen_channel_name
-                    .as_deref()
-                    .map(str::trim)
-                    .filter(|value| !value.is_empty())
-                    .map(|value| value.to_string())
-                    .unwrap_or_else(|| "Not Set".to_string())
+            let username_display = task.telegram_username_display().unwrap_or("N/A");
+            let channels_block = if task.has_telegram_user_session() && !task.channels.is_empty() {
+                format!(
+                    "📢 *Telegram Channels:* `{}`\n{}",
+                    escape_markdown(&format!(
+                        "{} of {} enabled",
+                        task.enabled_channels().count(),
+                        task.channels.len()
+                    )),
+                    format_channel_lines(&task.channels)
+                )
             } else {
-                "Not Set".to_string()
+                "📢 *Telegram Channels:* `Not Set`".to_string()
             };
-            let monitoring_str = if has_user_session {
-                if task.listen_users.is_empty() && task.listen_usernames.is_empty() {
-                    if task.telegram_channel_is_broadcast {
-                        "Channel posts (no specific users)".to_string()
-                    } else {
-                        "Not Set".to_string()
-                    }
-                } else if !task.listen_usernames.is_empty() {
-                    format!(
-                        "{} users: {}",
-                        task.listen_usernames.len(),
-                        task.listen_usernames.join(", ")
-                    )
-                } else {
-                    let users = task
-                        .listen_users
-                        .iter()
-                        .map(|id| id.to_string())
-                        .collect::<Vec<_>>()
-                        .join(", ");
-                    format!("{} users: {}", task.listen_users.len(), users)
-                }
+            let edits_str = if task.process_edits {
+                "Processed"
             } else {
-                "Not Set".to_string()
+                "Ignored"
+            };
+            let reply_context_str = if task.include_reply_context {
+                "Included"
+            } else {
+                "Ignored"
+            };
+            let forward_author_str = if task.match_forward_author {
+                "Original author"
+            } else {
+                "Forwarder"
             };
-            let username_display = task.telegram_username_display().unwrap_or("N/A");
             format!(
                 concat!(
-                    "👤 *Telegram Username:* `{}`\n",
-                    "📢 *Telegram Channel Name:* `{}`\n",
-                    "👥 *Monitoring:* `{}`"
+                    "👤 *Telegram Username:* `{}`\n{}\n",
+                    "✏️ *Edited Messages:* `{}`\n",
+                    "↩️ *Reply Context:* `{}`\n",
+                    "⏩ *Forward Sender:* `{}`\n",
+                    "🔗 *Auto\\-Join:* `{}`"
                 ),
                 escape_markdown(username_display),
-                escape_markdown(&channel_name_str),
-                escape_markdown(&monitoring_str)
+                channels_block,
+                edits_str,
+                reply_context_str,
+                forward_author_str,
+                escape_markdown(&task.auto_join.summary())
             )
         }
         Platform::Discord => {
@@ -228,12 +331,23 @@ pub async fn generate_task_detail_text(
             "{}\n",
             "\n",
             "🚫 *Blacklist Words:* `{}`\n",
+            "🛡️ *Safety Checks:* `{}`\n",
+            "🔁 *Dedup:* `{}`\n",
+            "⚡ *Execution:* `{}`\n",
+            "📝 *Dry Run:* `{}`\n",
+            "🔄 *Retry:* `{}`\n",
+            "⏰ *Schedule:* `{}`\n",
+            "🌸 *Bloom Account:* `{}`\n",
             "\n",
             "💰 *Fees & Slippage*\n",
             "• *Buy Amount:* `{}`\n",
             "• *Buy Fee:* `{}`\n",
             "• *Buy Slippage:* `{}%`\n",
             "\n",
+            "📉 *Market Filters*\n",
+            "• *Liquidity:* `{}`\n",
+            "• *Market Cap:* `{}`\n",
+            "\n",
             "{}{}"
         ),
         heading,
@@ -241,9 +355,34 @@ pub async fn generate_task_detail_text(
         wallet_block,
         platform_details,
         escape_markdown(&blacklist_str),
+        safety_checks_str,
+        escape_markdown(&format!(
+            "{} scope, TTL {}",
+            task.dedup_scope.label(),
+            task.dedup_ttl_display()
+        )),
+        escape_markdown(task.execution_mode.label()),
+        escape_markdown(dry_run_str),
+        escape_markdown(&task.retry_policy.summary()),
+        escape_markdown(
+            &task
+                .schedule
+                .as_ref()
+                .map(|schedule| schedule.summary())
+                .unwrap_or_else(|| "Manual".to_string())
+        ),
+        escape_markdown(task.bloom_account.as_deref().unwrap_or("Default")),
         escape_markdown(&buy_amount_display),
         escape_markdown(&buy_fee_display),
         escape_markdown(&task.buy_slippage_percent.to_string()),
+        escape_markdown(&format_sol_range(
+            task.min_liquidity_sol,
+            task.max_liquidity_sol
+        )),
+        escape_markdown(&format_sol_range(
+            task.min_market_cap_sol,
+            task.max_market_cap_sol
+        )),
         inform_only_line,
         bloom_notice_line
     )
@@ -278,6 +417,26 @@ pub async fn generate_task_settings_text(
         "🤖 *Telegram User:* `{}`",
         escape_markdown(&telegram_status)
     ));
+    if has_telegram_session {
+        let account_line = match task.telegram_user_id {
+            Some(account_id) => {
+                let name = session_display_name(account_id)
+                    .or_else(|| task.telegram_username_display().map(str::to_string))
+                    .unwrap_or_else(|| "Unknown".to_string());
+                let state = if is_session_running(account_id) {
+                    "connected"
+                } else {
+                    "offline"
+                };
+                format!("{} ({}) - {}", name, account_id, state)
+            }
+            None => "Not resolved yet".to_string(),
+        };
+        sections.push(format!(
+            "👤 *Telegram Account:* `{}`",
+            escape_markdown(&account_line)
+        ));
+    }
     let has_token = task
         .discord_token
         .as_ref()
@@ -299,6 +458,32 @@ pub async fn generate_task_settings_text(
         "🔑 *Discord Token:* `{}`",
         escape_markdown(&token_status)
     ));
+    let on_off = |enabled: bool| if enabled { "On" } else { "Off" };
+    let swap_options = task.bloom_swap_options();
+    let processor_tip_display = match task.bloom_processor_tip_sol {
+        Some(_) => format!("{} SOL", format_trimmed_sol(swap_options.processor_tip)),
+        None => format!(
+            "{} SOL (same as buy fee)",
+            format_trimmed_sol(swap_options.processor_tip)
+        ),
+    };
+    sections.push(format!(
+        concat!(
+            "🌸 *Bloom Swap Options*\n",
+            "• *Anti\\-MEV:* `{}`\n",
+            "• *Auto Tip:* `{}`\n",
+            "• *Skip If Bought:* `{}`\n",
+            "• *Hedged Regions:* `{}`\n",
+            "• *Processor Tip:* `{}`\n",
+            "• *Dev Sell:* `{}`"
+        ),
+        on_off(swap_options.anti_mev),
+        on_off(swap_options.auto_tip),
+        on_off(swap_options.skip_if_bought),
+        on_off(swap_options.hedged),
+        escape_markdown(&processor_tip_display),
+        escape_markdown(swap_options.dev_sell.as_deref().unwrap_or("Off"))
+    ));
     sections.push(escape_markdown(
         "Choose an option below to configure this task.",
     ));
@@ -430,6 +615,19 @@ fn format_sol_with_usd(amount: f64, price_per_sol: Option<f64>) -> String {
     }
 }
 
+fn format_sol_range(min: Option<f64>, max: Option<f64>) -> String {
+    match (min, max) {
+        (None, None) => "Not Set".to_string(),
+        (Some(min), None) => format!(">= {} SOL", format_trimmed_sol(min)),
+        (None, Some(max)) => format!("<= {} SOL", format_trimmed_sol(max)),
+        (Some(min), Some(max)) => format!(
+            "{} - {} SOL",
+            format_trimmed_sol(min),
+            format_trimmed_sol(max)
+        ),
+    }
+}
+
 fn format_wallet_balance_text(balance_sol: Option<f64>, price_per_sol: Option<f64>) -> String {
     match balance_sol {
         Some(amount) => format_sol_with_usd(amount, price_per_sol),
@@ -36,6 +36,14 @@ pub enum State {
         selected_users: Vec<i64>,
         page: usize,
     },
+    TaskSelectTopicsFromList {
+        task_name: String,
+        menu_message_id: MessageId,
+        channel_id: i64,
+        all_topics: Vec<(String, i32)>,
+        selected_topics: Vec<i32>,
+        page: usize,
+    },
     TaskTelegramLinking {
         task_name: String,
         menu_message_id: MessageId,
@@ -45,6 +53,10 @@ pub enum State {
         task_name: String,
         menu_message_id: MessageId,
     },
+    BloomReceiveAccount {
+        menu_message_id: MessageId,
+        prompt_message_id: MessageId,
+    },
     TaskReceiveName {
         task_name: String,
         menu_message_id: MessageId,
@@ -65,6 +77,46 @@ pub enum State {
         menu_message_id: MessageId,
         prompt_message_id: MessageId,
     },
+    TaskReceiveMarketFilters {
+        task_name: String,
+        menu_message_id: MessageId,
+        prompt_message_id: MessageId,
+    },
+    TaskReceiveProcessorTip {
+        task_name: String,
+        menu_message_id: MessageId,
+        prompt_message_id: MessageId,
+    },
+    TaskReceiveDevSell {
+        task_name: String,
+        menu_message_id: MessageId,
+        prompt_message_id: MessageId,
+    },
+    TaskReceiveSchedule {
+        task_name: String,
+        menu_message_id: MessageId,
+        prompt_message_id: MessageId,
+    },
+    TaskReceiveRetryPolicy {
+        task_name: String,
+        menu_message_id: MessageId,
+        prompt_message_id: MessageId,
+    },
+    TaskReceiveDedupTtl {
+        task_name: String,
+        menu_message_id: MessageId,
+        prompt_message_id: MessageId,
+    },
+    TaskReceiveDedupClear {
+        task_name: String,
+        menu_message_id: MessageId,
+        prompt_message_id: MessageId,
+    },
+    TaskReceiveAutoJoin {
+        task_name: String,
+        menu_message_id: MessageId,
+        prompt_message_id: MessageId,
+    },
     TaskReceiveBlacklist {
         task_name: String,
         menu_message_id: MessageId,
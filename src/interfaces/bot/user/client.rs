@@ -51,6 +51,7 @@ pub fn take_active_qr_message(chat_id: i64, task_name: &str) -> Option<MessageId
 
 pub struct PendingTelegramSession {
     pub client: Client,
+    pub user_id: i64,
     pub encoded_session: String,
     pub display_name: Option<String>,
 }
@@ -94,7 +95,7 @@ fn build_qr_png(data: &str) -> Result<Vec<u8>> {
     Ok(buffer.into_inner())
 }
 
-fn format_telegram_display_name(user: &grammers_client::types::User) -> Option<String> {
+pub fn format_telegram_display_name(user: &grammers_client::types::User) -> Option<String> {
     if let Some(username) = user.username() {
         if username.trim().is_empty() {
             None
@@ -145,10 +146,35 @@ async fn wait_for_login_signal(
     Ok(LoginSignalOutcome::TimedOut)
 }
 
+pub fn build_client_config(session: Session) -> Result<Config> {
+    let api_id = env::var("API_ID")
+        .context("API_ID environment variable missing")?
+        .parse()
+        .context("API_ID must be a valid integer")?;
+    let api_hash = env::var("API_HASH").context("API_HASH environment variable missing")?;
+
+    let params = InitParams {
+        device_model: "PC".to_string(),
+        system_version: "Windows 11".to_string(),
+        app_version: "Telegram Desktop 4.16.8".to_string(),
+        lang_code: "en".to_string(),
+        system_lang_code: "en".to_string(),
+        ..Default::default()
+    };
+
+    Ok(Config {
+        session,
+        api_id,
+        api_hash,
+        params,
+    })
+}
+
 pub async fn authenticate_task_user_via_qr(
     bot: Bot,
     redis_client: RedisClient,
     chat_id: i64,
+    data_chat_id: i64,
     task_name: String,
     menu_message_id: MessageId,
     dialogue: Dialogue<State, InMemStorage<State>>,
@@ -157,10 +183,10 @@ pub async fn authenticate_task_user_via_qr(
         .get_multiplexed_async_connection()
         .await
         .context("Failed to connect to Redis")?;
-    let user_data = get_user_data(&mut con, chat_id)
+    let user_data = get_user_data(&mut con, data_chat_id)
         .await
         .context("Failed to load user data")?
-        .ok_or_else(|| anyhow!("No user data found for chat {}", chat_id))?;
+        .ok_or_else(|| anyhow!("No user data found for chat {}", data_chat_id))?;
     drop(con);
 
     if !user_data.tasks.iter().any(|task| task.name == task_name) {
@@ -170,30 +196,9 @@ pub async fn authenticate_task_user_via_qr(
         ));
     }
 
-    let session = Session::new();
-    let api_id = env::var("API_ID")
-        .context("API_ID environment variable missing")?
-        .parse()
-        .context("API_ID must be a valid integer")?;
-    let api_hash = env::var("API_HASH").context("API_HASH environment variable missing")?;
-
-    let params = InitParams {
-        device_model: "PC".to_string(),
-        system_version: "Windows 11".to_string(),
-        app_version: "Telegram Desktop 4.16.8".to_string(),
-        lang_code: "en".to_string(),
-        system_lang_code: "en".to_string(),
-        ..Default::default()
-    };
-
-    let client = Client::connect(Config {
-        session,
-        api_id,
-        api_hash: api_hash.clone(),
-        params,
-    })
-    .await
-    .context("Failed to connect Telegram client for QR login")?;
+    let client = Client::connect(build_client_config(Session::new())?)
+        .await
+        .context("Failed to connect Telegram client for QR login")?;
 
     let chat = ChatId(chat_id);
     let mut qr_message_id: Option<MessageId> = None;
@@ -318,6 +323,7 @@ pub async fn authenticate_task_user_via_qr(
                         &task_name,
                         PendingTelegramSession {
                             client: client.clone(),
+                            user_id,
                             encoded_session,
                             display_name: display_name.clone(),
                         },
@@ -465,6 +471,73 @@ pub async fn get_chat_admins(
     Ok(admins)
 }
 
+pub async fn get_forum_topics(
+    client: &UserClientHandle,
+    chat_id: i64,
+) -> Result<Vec<(String, i32)>> {
+    let mut target_chat: Option<Chat> = None;
+
+    let mut dialogs = client.iter_dialogs();
+    while let Some(dialog) = dialogs.next().await? {
+        let chat = dialog.chat();
+        if chat.id() == chat_id {
+            target_chat = Some(chat.clone());
+            break;
+        }
+    }
+
+    let chat = target_chat.ok_or_else(|| anyhow!("Chat not found"))?;
+    let channel = chat
+        .pack()
+        .try_to_input_channel()
+        .ok_or_else(|| anyhow!("Chat is not a forum supergroup"))?;
+
+    let result = client
+        .invoke(&tl::functions::channels::GetForumTopics {
+            channel,
+            q: None,
+            offset_date: 0,
+            offset_id: 0,
+            offset_topic: 0,
+            limit: 100,
+        })
+        .await
+        .map_err(|err| {
+            if err.is("CHANNEL_FORUM_MISSING") {
+                anyhow!("Chat is not a forum supergroup")
+            } else {
+                anyhow!(err)
+            }
+        })?;
+
+    let tl::enums::messages::ForumTopics::Topics(result) = result;
+    let topics = result
+        .topics
+        .into_iter()
+        .filter_map(|topic| match topic {
+            tl::enums::ForumTopic::Topic(topic) => Some((topic.title, topic.id)),
+            tl::enums::ForumTopic::Deleted(_) => None,
+        })
+        .collect();
+
+    Ok(topics)
+}
+
+pub async fn leave_chat(client: &UserClientHandle, chat_id: i64) -> Result<bool> {
+    let mut dialogs = client.iter_dialogs();
+    while let Some(dialog) = dialogs.next().await? {
+        let chat = dialog.chat();
+        if chat.id() == chat_id {
+            client
+                .delete_dialog(chat)
+                .await
+                .context("Failed to leave Telegram chat")?;
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
 pub async fn is_channel_member(client: &UserClientHandle, channel_id: i64) -> Result<bool> {
     let mut dialogs = client.iter_dialogs();
     while let Some(dialog) = dialogs.next().await? {
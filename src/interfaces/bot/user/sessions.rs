@@ -0,0 +1,319 @@
+use crate::USER_CLIENT_HANDLE;
+use crate::interfaces::bot::Task;
+use crate::interfaces::bot::core::bloom_listener::run_bloom_listener;
+use crate::interfaces::bot::core::update_bus::{TimedUpdate, UpdateArc};
+use crate::interfaces::bot::core::update_health::{
+    record_hub_error, record_hub_reconnected, record_hub_started, record_hub_update,
+};
+use crate::interfaces::bot::core::update_router::route_update;
+use crate::interfaces::bot::user::client::{
+    UserClientHandle, build_client_config, format_telegram_display_name,
+};
+use anyhow::{Context, Result, anyhow};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use grammers_client::Client;
+use grammers_session::Session;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex as AsyncMutex, broadcast};
+use tokio::task::JoinHandle;
+
+const SESSION_UPDATE_CAPACITY: usize = 1024;
+const HUB_BACKOFF_MIN: Duration = Duration::from_secs(1);
+const HUB_BACKOFF_MAX: Duration = Duration::from_secs(60);
+
+struct ManagedSession {
+    client: UserClientHandle,
+    display_name: Option<String>,
+    source: Option<String>,
+    updates: broadcast::Sender<UpdateArc>,
+    hub: JoinHandle<()>,
+    client_workers: Vec<JoinHandle<()>>,
+}
+
+impl ManagedSession {
+    fn stop(&self) {
+        self.hub.abort();
+        for worker in &self.client_workers {
+            worker.abort();
+        }
+    }
+}
+
+static USER_SESSIONS: Lazy<Mutex<HashMap<i64, ManagedSession>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+static PRIMARY_ACCOUNT: Lazy<Mutex<Option<i64>>> = Lazy::new(|| Mutex::new(None));
+
+static SESSION_CONNECT_LOCKS: Lazy<Mutex<HashMap<String, Arc<AsyncMutex<()>>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn session_connect_lock(key: String) -> Arc<AsyncMutex<()>> {
+    Arc::clone(SESSION_CONNECT_LOCKS.lock().entry(key).or_default())
+}
+
+fn spawn_client_workers(
+    account_id: i64,
+    client: &UserClientHandle,
+    updates: &broadcast::Sender<UpdateArc>,
+) -> Vec<JoinHandle<()>> {
+    let runner_client = client.clone();
+    let runner = tokio::spawn(async move {
+        match runner_client.run_until_disconnected().await {
+            Ok(_) => log::info!("sessions: client disconnected account={}", account_id),
+            Err(e) => log::error!(
+                "sessions: client disconnected with error account={} err={}",
+                account_id,
+                e
+            ),
+        }
+    });
+
+    let bloom = tokio::spawn(run_bloom_listener(client.clone(), updates.subscribe()));
+
+    vec![runner, bloom]
+}
+
+async fn reconnect_session(account_id: i64, source: &str) -> Result<UserClientHandle> {
+    let client = connect_stored_session(source).await?;
+    {
+        let mut sessions = USER_SESSIONS.lock();
+        let session = sessions
+            .get_mut(&account_id)
+            .ok_or_else(|| anyhow!("Telegram session {} is no longer registered", account_id))?;
+        for worker in session.client_workers.drain(..) {
+            worker.abort();
+        }
+        session.client = client.clone();
+        session.client_workers = spawn_client_workers(account_id, &client, &session.updates);
+    }
+    if *PRIMARY_ACCOUNT.lock() == Some(account_id) {
+        *USER_CLIENT_HANDLE.lock() = Some(client.clone());
+    }
+    Ok(client)
+}
+
+async fn run_update_hub(
+    account_id: i64,
+    mut client: UserClientHandle,
+    updates: broadcast::Sender<UpdateArc>,
+    source: Option<String>,
+) {
+    record_hub_started(account_id);
+    let mut backoff = HUB_BACKOFF_MIN;
+    let mut recovering = false;
+    loop {
+        match client.next_update().await {
+            Ok(update) => {
+                if recovering {
+                    recovering = false;
+                    backoff = HUB_BACKOFF_MIN;
+                    record_hub_reconnected(account_id);
+                }
+                record_hub_update(account_id);
+                let timed = Arc::new(TimedUpdate {
+                    ts: Instant::now(),
+                    update,
+                });
+                route_update(account_id, &timed);
+                let _ = updates.send(timed);
+            }
+            Err(e) => {
+                log::error!(
+                    "sessions: update hub error account={} err={}",
+                    account_id,
+                    e
+                );
+                record_hub_error(account_id, &e.to_string());
+                loop {
+                    log::info!(
+                        "sessions: update hub retry account={} backoff_secs={}",
+                        account_id,
+                        backoff.as_secs()
+                    );
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(HUB_BACKOFF_MAX);
+                    let Some(source) = source.as_deref() else {
+                        recovering = true;
+                        break;
+                    };
+                    match reconnect_session(account_id, source).await {
+                        Ok(reconnected) => {
+                            client = reconnected;
+                            backoff = HUB_BACKOFF_MIN;
+                            record_hub_reconnected(account_id);
+                            break;
+                        }
+                        Err(e) => {
+                            log::warn!(
+                                "sessions: reconnect failed account={} err={}",
+                                account_id,
+                                e
+                            );
+                            record_hub_error(account_id, &e.to_string());
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub fn register_session(
+    account_id: i64,
+    display_name: Option<String>,
+    client: UserClientHandle,
+    source: Option<String>,
+) {
+    let updates = USER_SESSIONS
+        .lock()
+        .get(&account_id)
+        .map(|session| session.updates.clone())
+        .unwrap_or_else(|| broadcast::channel::<UpdateArc>(SESSION_UPDATE_CAPACITY).0);
+
+    let hub = tokio::spawn(run_update_hub(
+        account_id,
+        client.clone(),
+        updates.clone(),
+        source.clone(),
+    ));
+    let client_workers = spawn_client_workers(account_id, &client, &updates);
+
+    let previous = USER_SESSIONS.lock().insert(
+        account_id,
+        ManagedSession {
+            client: client.clone(),
+            display_name: display_name.clone(),
+            source,
+            updates,
+            hub,
+            client_workers,
+        },
+    );
+    if let Some(previous) = previous {
+        previous.stop();
+        log::info!("sessions: replaced client account={}", account_id);
+    } else {
+        log::info!(
+            "sessions: registered client account={} name={}",
+            account_id,
+            display_name.as_deref().unwrap_or("-")
+        );
+    }
+
+    let mut primary_account = PRIMARY_ACCOUNT.lock();
+    if primary_account.is_none_or(|primary| primary == account_id) {
+        *primary_account = Some(account_id);
+        *USER_CLIENT_HANDLE.lock() = Some(client);
+    }
+}
+
+pub fn session_client(account_id: i64) -> Option<UserClientHandle> {
+    USER_SESSIONS
+        .lock()
+        .get(&account_id)
+        .map(|session| session.client.clone())
+}
+
+pub fn session_display_name(account_id: i64) -> Option<String> {
+    USER_SESSIONS
+        .lock()
+        .get(&account_id)
+        .and_then(|session| session.display_name.clone())
+}
+
+pub fn is_session_running(account_id: i64) -> bool {
+    USER_SESSIONS.lock().contains_key(&account_id)
+}
+
+fn account_for_source(source: &str) -> Option<i64> {
+    USER_SESSIONS
+        .lock()
+        .iter()
+        .find(|(_, session)| session.source.as_deref() == Some(source))
+        .map(|(account_id, _)| *account_id)
+}
+
+pub async fn connect_stored_session(encoded_session: &str) -> Result<UserClientHandle> {
+    let bytes = BASE64_STANDARD
+        .decode(encoded_session.trim())
+        .context("Stored Telegram session is not valid base64")?;
+    let session = Session::load(&bytes)
+        .map_err(|e| anyhow!("Stored Telegram session could not be loaded: {}", e))?;
+    let client = Client::connect(build_client_config(session)?)
+        .await
+        .context("Failed to connect Telegram client from stored session")?;
+    if !client
+        .is_authorized()
+        .await
+        .context("Failed to check Telegram authorization")?
+    {
+        return Err(anyhow!("Telegram session is no longer authorized"));
+    }
+    Ok(client)
+}
+
+pub async fn ensure_task_session(task: &Task) -> Result<i64> {
+    if let Some(account_id) = task.telegram_user_id.filter(|id| is_session_running(*id)) {
+        return Ok(account_id);
+    }
+    let encoded_session = task
+        .grammers_session_data
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| anyhow!("No Telegram user is linked to task '{}'", task.name))?;
+    if let Some(account_id) = account_for_source(encoded_session) {
+        return Ok(account_id);
+    }
+
+    let lock_key = match task.telegram_user_id {
+        Some(account_id) => format!("account:{}", account_id),
+        None => format!("session:{}", encoded_session),
+    };
+    let connect_lock = session_connect_lock(lock_key);
+    let _connect_guard = connect_lock.lock().await;
+    if let Some(account_id) = task.telegram_user_id.filter(|id| is_session_running(*id)) {
+        return Ok(account_id);
+    }
+    if let Some(account_id) = account_for_source(encoded_session) {
+        return Ok(account_id);
+    }
+
+    let client = connect_stored_session(encoded_session).await?;
+    let me = client
+        .get_me()
+        .await
+        .context("Failed to fetch the linked Telegram account")?;
+    let account_id = me.id();
+    if task
+        .telegram_user_id
+        .is_some_and(|expected| expected != account_id)
+    {
+        log::warn!(
+            "sessions: task={} stored account={:?} but session belongs to {}",
+            task.name,
+            task.telegram_user_id,
+            account_id
+        );
+    }
+    if !is_session_running(account_id) {
+        register_session(
+            account_id,
+            format_telegram_display_name(&me),
+            client,
+            Some(encoded_session.to_string()),
+        );
+    }
+    Ok(account_id)
+}
+
+pub async fn task_client(task: &Task) -> Result<UserClientHandle> {
+    let account_id = ensure_task_session(task).await?;
+    session_client(account_id)
+        .ok_or_else(|| anyhow!("Telegram session for task '{}' is not running", task.name))
+}
@@ -1,4 +1,6 @@
 use crate::HTTP_CLIENT;
+use crate::infrastructure::blockchain::BloomCredentials;
+use crate::infrastructure::blockchain::bloom::regions::{ranked_regions, region_wallets_url};
 use crate::interfaces::bot::data::BloomWalletInfo;
 use anyhow::{Result, anyhow};
 use serde::{Deserialize, Serialize};
@@ -25,22 +27,21 @@ fn resolve_region(region_hint: Option<&str>) -> String {
     if let Some(region) = region_hint {
         return region.to_lowercase();
     }
-    if let Ok(region) = std::env::var("BLOOM_REGION") {
-        if !region.is_empty() {
-            return region.to_lowercase();
-        }
-    }
-    "eu1".to_string()
+    ranked_regions()
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| "eu1".to_string())
 }
 
-pub async fn fetch_bloom_wallets(region_hint: Option<&str>) -> Result<Vec<BloomWalletInfo>> {
-    let auth_token = std::env::var("BLOOM_AUTH_TOKEN")
-        .map_err(|_| anyhow!("BLOOM_AUTH_TOKEN environment variable not set"))?;
+pub async fn fetch_bloom_wallets(
+    credentials: &BloomCredentials,
+    region_hint: Option<&str>,
+) -> Result<Vec<BloomWalletInfo>> {
+    let auth_token = credentials.usable_token()?;
     let region = resolve_region(region_hint);
-    let url = format!("https://{}-tg.bloom-ext.app/get-wallets", region);
     let response = HTTP_CLIENT
-        .post(url)
-        .json(&WalletFetchRequest { auth: &auth_token })
+        .post(region_wallets_url(&region))
+        .json(&WalletFetchRequest { auth: auth_token })
         .send()
         .await?;
     if !response.status().is_success() {
@@ -3,7 +3,12 @@ use crate::application::indexer::{
     IndexerMintLogEntry, indexer_mint_log_counters, ram_index_stats, redis_index_stats,
     subscribe_indexer_mint_logs,
 };
+use crate::infrastructure::blockchain::endpoint_ranking;
 use crate::infrastructure::logging::suppress_stdout_logs;
+use crate::interfaces::bot::core::update_health::{
+    HubStatus, hub_health_snapshot, worker_lag_snapshot,
+};
+use crate::interfaces::bot::core::update_router::{active_route_count, hub_queue_stats};
 use crate::interfaces::bot::data::storage::get_user_tasks;
 use crate::interfaces::bot::tasks::subscribe_task_logs;
 use crate::interfaces::bot::{clear_user_logs, get_all_user_ids, get_user_logs};
@@ -154,6 +159,53 @@ impl MenuManager {
                 }
             }
         }
+        for hub in hub_health_snapshot() {
+            let last_update = hub
+                .last_update
+                .map(|at| format!("{}s ago", at.elapsed().as_secs()))
+                .unwrap_or_else(|| "none yet".to_string());
+            match hub.status {
+                HubStatus::Connected => println!(
+                    "  {} {}",
+                    "✓".bright_green().bold(),
+                    format!(
+                        "Telegram update hub {} connected (reconnects: {}, last update: {}).",
+                        hub.account_id, hub.reconnects, last_update
+                    )
+                    .bright_green()
+                ),
+                HubStatus::Reconnecting => ConsoleUI::print_warning(&format!(
+                    "Telegram update hub {} reconnecting (attempt {}): {}",
+                    hub.account_id,
+                    hub.failed_attempts,
+                    hub.last_error.as_deref().unwrap_or("unknown error")
+                )),
+            }
+        }
+        let hub_queue = hub_queue_stats();
+        if hub_queue.samples > 0 {
+            let bound = |value: Option<u64>| {
+                value
+                    .map(|us| format!("≤{}µs", us))
+                    .unwrap_or_else(|| "-".to_string())
+            };
+            ConsoleUI::print_info(&format!(
+                "Hub queue latency (perf.hub_queue_us) over {} updates: p50 {}, p95 {}, p99 {}, mean {}µs, max {}µs. Routed tasks: {}.",
+                hub_queue.samples,
+                bound(hub_queue.p50_us),
+                bound(hub_queue.p95_us),
+                bound(hub_queue.p99_us),
+                hub_queue.mean_us,
+                hub_queue.max_us,
+                active_route_count()
+            ));
+        }
+        for lag in worker_lag_snapshot() {
+            ConsoleUI::print_warning(&format!(
+                "Task monitor {} (user {}) dropped {} updates while lagging.",
+                lag.task_name, lag.chat_id, lag.dropped
+            ));
+        }
         println!();
         ConsoleUI::print_option(1, "Server Logs");
         ConsoleUI::print_option(2, "Warmer Status");
@@ -260,6 +312,45 @@ impl MenuManager {
                 status_w = status_w
             );
         }
+        drop(state);
+
+        let ranking = endpoint_ranking();
+        if !ranking.is_empty() {
+            println!();
+            println!("  {}", "Broadcast Endpoint Ranking".bold().cyan());
+            println!(
+                "  {:<4}{:<12}{:<8}{:<8}{:<11}{:<10}{:<14}{:<14}{}",
+                "#".bold().cyan(),
+                "Endpoint".bold().cyan(),
+                "Sends".bold().cyan(),
+                "Errors".bold().cyan(),
+                "1st Acc".bold().cyan(),
+                "Acc+Land".bold().cyan(),
+                "Accept Avg".bold().cyan(),
+                "Accept Last".bold().cyan(),
+                "Confirm Avg".bold().cyan()
+            );
+            println!("  {}", "─".repeat(url_w + status_w + 12));
+            for (index, entry) in ranking.iter().enumerate() {
+                let format_ms = |value: Option<f64>| {
+                    value
+                        .map(|ms| format!("{:.2}ms", ms))
+                        .unwrap_or_else(|| "N/A".to_string())
+                };
+                println!(
+                    "  {:<4}{:<12}{:<8}{:<8}{:<11}{:<10}{:<14}{:<14}{}",
+                    index + 1,
+                    entry.endpoint.white(),
+                    entry.sends,
+                    entry.errors,
+                    entry.first_accepts,
+                    entry.accepted_landed.to_string().green(),
+                    format_ms(entry.avg_accept_latency_ms),
+                    format_ms(entry.last_accept_latency_ms),
+                    format_ms(entry.avg_accept_to_confirm_ms)
+                );
+            }
+        }
 
         println!();
         ConsoleUI::print_exit_option('0', "Back to Main Menu");
@@ -0,0 +1,59 @@
+use parking_lot::Mutex;
+use reqwest::Client as ReqwestClient;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::oneshot;
+
+pub mod application;
+pub mod infrastructure;
+pub mod interfaces;
+
+use infrastructure::blockchain::{RpcClients, create_rpc_clients};
+use interfaces::bot::user::client::UserClientHandle;
+
+#[derive(Clone, Debug)]
+pub struct BloomBuyAck {
+    pub pending_time: Instant,
+    pub success_time: Instant,
+    pub token_name: Option<String>,
+    pub signature: Option<String>,
+}
+
+pub static USER_CLIENT_HANDLE: once_cell::sync::Lazy<Arc<Mutex<Option<UserClientHandle>>>> =
+    once_cell::sync::Lazy::new(|| Arc::new(Mutex::new(None)));
+pub static PENDING_BLOOM_INFO: once_cell::sync::Lazy<
+    Arc<Mutex<HashMap<String, oneshot::Sender<String>>>>,
+> = once_cell::sync::Lazy::new(|| Arc::new(Mutex::new(HashMap::new())));
+pub static ACTIVE_TASK_SESSIONS: once_cell::sync::Lazy<
+    Arc<Mutex<HashMap<(i64, String), uuid::Uuid>>>,
+> = once_cell::sync::Lazy::new(|| Arc::new(Mutex::new(HashMap::new())));
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BloomWsConnectionStatus {
+    Connecting,
+    Connected,
+    Disconnected,
+    Unavailable,
+}
+
+impl Default for BloomWsConnectionStatus {
+    fn default() -> Self {
+        BloomWsConnectionStatus::Connecting
+    }
+}
+
+#[derive(Default)]
+pub struct BloomWsConnectionState {
+    pub status: BloomWsConnectionStatus,
+    pub message: String,
+}
+pub static BLOOM_WS_CONNECTION: once_cell::sync::Lazy<Arc<Mutex<BloomWsConnectionState>>> =
+    once_cell::sync::Lazy::new(|| Arc::new(Mutex::new(BloomWsConnectionState::default())));
+pub static HTTP_CLIENT: once_cell::sync::Lazy<ReqwestClient> = once_cell::sync::Lazy::new(|| {
+    ReqwestClient::builder()
+        .tcp_keepalive(Duration::from_secs(60))
+        .build()
+        .expect("Failed to create HTTP client")
+});
+pub static RPC_CLIENTS: once_cell::sync::Lazy<RpcClients> =
+    once_cell::sync::Lazy::new(create_rpc_clients);
@@ -1,86 +1,31 @@
 use parking_lot::Mutex;
 use redis::Client as RedisClient;
-use reqwest::Client as ReqwestClient;
-use std::collections::HashMap;
 use std::env;
 use std::sync::Arc;
-use std::time::{Duration, Instant};
 use teloxide::{dispatching::dialogue::InMemStorage, prelude::*};
-use tokio::sync::{RwLock, broadcast, mpsc, oneshot};
-
-mod application;
-mod infrastructure;
-mod interfaces;
-
-use application::health::worker::{WarmerState, WarmupResult, WarmupStatus, run_warmer};
-use application::indexer::{preload_from_redis, run_raydium_pool_ingest, run_ws_ingest};
-use application::pricing::{SolPriceState, run_price_fetcher};
-use infrastructure::blockchain::{RpcClients, create_rpc_clients, run_bloom_ws_listener};
-use infrastructure::logging;
-use interfaces::bot::State;
-use interfaces::bot::handlers::{
+use tokio::sync::RwLock;
+
+use project_ilanoria::application::health::worker::{
+    WarmerState, WarmupResult, WarmupStatus, run_warmer,
+};
+use project_ilanoria::application::indexer::{
+    preload_from_redis, run_raydium_pool_ingest, run_ws_ingest,
+};
+use project_ilanoria::application::pricing::{SolPriceState, run_price_fetcher};
+use project_ilanoria::infrastructure::blockchain::{RpcClients, run_bloom_ws_listener};
+use project_ilanoria::infrastructure::logging;
+use project_ilanoria::interfaces::bot::State;
+use project_ilanoria::interfaces::bot::data::access::{
+    Role, load_access_control, owner_ids, role_for, set_bootstrap_owner,
+};
+use project_ilanoria::interfaces::bot::handlers::{
     callbacks::callback_handler,
+    commands::{handle_command, register_bot_commands, required_role_for_command},
     start::{Command, start},
     text::text_handler,
 };
-use interfaces::bot::user::client::UserClientHandle;
-use interfaces::console::menu::MenuManager;
-
-pub struct BloomBuyAck {
-    pub pending_time: Instant,
-    pub success_time: Instant,
-    pub token_name: Option<String>,
-    pub signature: Option<String>,
-}
-
-#[derive(Clone)]
-pub struct BloomSwapTracker {
-    pub mint: String,
-    pub side: String,
-    pub started_at: Instant,
-}
-
-pub static USER_CLIENT_HANDLE: once_cell::sync::Lazy<Arc<Mutex<Option<UserClientHandle>>>> =
-    once_cell::sync::Lazy::new(|| Arc::new(Mutex::new(None)));
-pub static PENDING_BLOOM_RESPONSES: once_cell::sync::Lazy<
-    Arc<Mutex<HashMap<String, oneshot::Sender<BloomBuyAck>>>>,
-> = once_cell::sync::Lazy::new(|| Arc::new(Mutex::new(HashMap::new())));
-pub static PENDING_BLOOM_INFO: once_cell::sync::Lazy<
-    Arc<Mutex<HashMap<String, oneshot::Sender<String>>>>,
-> = once_cell::sync::Lazy::new(|| Arc::new(Mutex::new(HashMap::new())));
-pub static ACTIVE_TASK_SESSIONS: once_cell::sync::Lazy<
-    Arc<Mutex<HashMap<(i64, String), uuid::Uuid>>>,
-> = once_cell::sync::Lazy::new(|| Arc::new(Mutex::new(HashMap::new())));
-pub static ACTIVE_BLOOM_SWAPS: once_cell::sync::Lazy<
-    Arc<Mutex<HashMap<String, BloomSwapTracker>>>,
-> = once_cell::sync::Lazy::new(|| Arc::new(Mutex::new(HashMap::new())));
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub enum BloomWsConnectionStatus {
-    Connecting,
-    Connected,
-    Disconnected,
-    Unavailable,
-}
-
-impl Default for BloomWsConnectionStatus {
-    fn default() -> Self {
-        BloomWsConnectionStatus::Connecting
-    }
-}
-
-#[derive(Default)]
-pub struct BloomWsConnectionState {
-    pub status: BloomWsConnectionStatus,
-    pub message: String,
-}
-pub static BLOOM_WS_CONNECTION: once_cell::sync::Lazy<Arc<Mutex<BloomWsConnectionState>>> =
-    once_cell::sync::Lazy::new(|| Arc::new(Mutex::new(BloomWsConnectionState::default())));
-pub static HTTP_CLIENT: once_cell::sync::Lazy<ReqwestClient> = once_cell::sync::Lazy::new(|| {
-    ReqwestClient::builder()
-        .tcp_keepalive(Duration::from_secs(60))
-        .build()
-        .expect("Failed to create HTTP client")
-});
+use project_ilanoria::interfaces::console::menu::MenuManager;
+use project_ilanoria::{RPC_CLIENTS, USER_CLIENT_HANDLE, application, infrastructure, interfaces};
 
 type MyDialogue = Dialogue<State, teloxide::dispatching::dialogue::InMemStorage<State>>;
 type HandlerResult = Result<(), Box<dyn std::error::Error + Send + Sync>>;
@@ -89,10 +34,6 @@ fn message_author_id(message: &Message) -> Option<u64> {
     message.from().map(|user| user.id.0)
 }
 
-fn is_authorized_user(user_id: Option<u64>, admin_id: u64) -> bool {
-    user_id.map(|id| id == admin_id).unwrap_or(false)
-}
-
 fn setup_console_ui(warmer_state: WarmerState, redis_url: String) {
     tokio::spawn(async move {
         let mut menu_manager = MenuManager::new(warmer_state, redis_url);
@@ -112,20 +53,40 @@ async fn handle_commands(
     bot: Bot,
     dialogue: MyDialogue,
     msg: Message,
-    _cmd: Command,
+    cmd: Command,
+    role: Role,
     redis_client: RedisClient,
     sol_price_state: SolPriceState,
     rpc_clients: RpcClients,
+    warmer_state: WarmerState,
 ) -> HandlerResult {
-    start(
-        bot,
-        dialogue,
-        msg,
-        redis_client,
-        sol_price_state,
-        rpc_clients,
-    )
-    .await?;
+    let required = required_role_for_command(&cmd);
+    if !role.allows(required) {
+        bot.send_message(
+            msg.chat.id,
+            format!(
+                "🚫 This command requires the {} role; you are a {}.",
+                required.label(),
+                role.label()
+            ),
+        )
+        .await?;
+        return Ok(());
+    }
+    match cmd {
+        Command::Start => {
+            start(
+                bot,
+                dialogue,
+                msg,
+                redis_client,
+                sol_price_state,
+                rpc_clients,
+            )
+            .await?;
+        }
+        cmd => handle_command(bot, msg, cmd, role, redis_client, warmer_state).await?,
+    }
     Ok(())
 }
 
@@ -140,7 +101,17 @@ async fn forbidden_message(bot: Bot, msg: Message) -> HandlerResult {
     );
     bot.send_message(
         msg.chat.id,
-        "🚫 Access forbidden: this bot is restricted to the administrator.",
+        "🚫 Access forbidden: ask the bot owner to authorise your Telegram account.",
+    )
+    .await
+    .map_err(|err| -> Box<dyn std::error::Error + Send + Sync> { Box::new(err) })?;
+    Ok(())
+}
+
+async fn read_only_message(bot: Bot, msg: Message) -> HandlerResult {
+    bot.send_message(
+        msg.chat.id,
+        "👁️ Read-only access: viewers can browse tasks and logs but cannot change settings.",
     )
     .await
     .map_err(|err| -> Box<dyn std::error::Error + Send + Sync> { Box::new(err) })?;
@@ -157,7 +128,7 @@ async fn forbidden_callback(bot: Bot, q: CallbackQuery) -> HandlerResult {
     if let Some(chat) = chat_id {
         bot.send_message(
             chat,
-            "🚫 Access forbidden: this bot is restricted to the administrator.",
+            "🚫 Access forbidden: ask the bot owner to authorise your Telegram account.",
         )
         .await
         .map_err(|err| -> Box<dyn std::error::Error + Send + Sync> { Box::new(err) })?;
@@ -178,29 +149,45 @@ async fn main() {
     rustls::crypto::CryptoProvider::install_default(rustls::crypto::ring::default_provider())
         .expect("install rustls ring provider");
 
-    let admin_user_id = env::var("ADMIN_TG_ID")
+    match env::var("ADMIN_TG_ID")
         .or_else(|_| env::var("ADMIN_TG_IG"))
         .ok()
         .and_then(|value| value.trim().parse::<u64>().ok())
-        .unwrap_or(7_507_740_649);
-    log::info!("Admin Telegram user id configured as {}", admin_user_id);
-    let admin_user_id = Arc::new(admin_user_id);
+    {
+        Some(owner_id) => {
+            log::info!("Owner Telegram user id configured as {}", owner_id);
+            set_bootstrap_owner(owner_id);
+        }
+        None => {
+            log::warn!("ADMIN_TG_ID is not set; only users authorised in Redis can access the bot")
+        }
+    }
 
     if let Err(e) = application::filter::init_word_filter().await {
         log::error!("Failed to initialize word filter: {}", e);
     }
 
-    let rpc_clients = create_rpc_clients();
+    let rpc_clients = RPC_CLIENTS.clone();
+
+    #[cfg(feature = "mock-bloom")]
+    if let Some(addr) = infrastructure::blockchain::bloom::mock_bind_addr() {
+        if let Err(e) = infrastructure::blockchain::bloom::start_mock_bloom_server(addr).await {
+            log::error!("Failed to start mock Bloom server on {}: {}", addr, e);
+        }
+    }
 
     tokio::spawn(run_bloom_ws_listener());
 
-    let urls_to_warm = vec![
-        "http://eu1.bloom-ext.app".to_string(),
+    let mut urls_to_warm: Vec<String> = infrastructure::blockchain::bloom::regions::bloom_regions()
+        .iter()
+        .map(|region| infrastructure::blockchain::bloom::regions::region_base_url(region))
+        .collect();
+    urls_to_warm.extend([
         infrastructure::blockchain::ZEROSLOT_RPC_URL.to_string(),
         infrastructure::blockchain::NODE1_RPC_URL.to_string(),
         infrastructure::blockchain::SHYFT_RPC_URL.to_string(),
         infrastructure::blockchain::HELIUS_RPC_URL.to_string(),
-    ];
+    ]);
 
     let initial_warmer_state: Vec<WarmupResult> = urls_to_warm
         .into_iter()
@@ -214,56 +201,28 @@ async fn main() {
 
     let warmer_state = Arc::new(Mutex::new(initial_warmer_state));
     let redis_url = env::var("REDIS_URL").expect("REDIS_URL must be set");
+    if let Err(e) = infrastructure::database::ensure_initialized(&redis_url).await {
+        log::error!("Failed to initialize shared Redis client: {}", e);
+    }
+
+    match load_access_control().await {
+        Ok(count) => log::info!("Loaded {} authorised Telegram users", count),
+        Err(e) => log::error!("Failed to load authorised Telegram users: {}", e),
+    }
+    let admin_chat_id = owner_ids().first().map(|owner_id| *owner_id as i64);
+    if let Some(chat_id) = admin_chat_id {
+        interfaces::bot::core::update_health::set_admin_chat_id(chat_id);
+    }
 
     if let Err(e) = preload_from_redis(&redis_url).await {
         log::error!("Failed to preload from Redis: {}", e);
     }
 
     let user_client_handle = Arc::clone(&USER_CLIENT_HANDLE);
-    let (client_sender, mut client_receiver) = mpsc::channel::<grammers_client::Client>(1);
-    let client_sender_for_dispatcher = client_sender.clone();
 
     let ui_state_clone = Arc::clone(&warmer_state);
     setup_console_ui(ui_state_clone, redis_url.clone());
 
-    tokio::spawn(async move {
-        if let Some(user_client) = client_receiver.recv().await {
-            log::info!("Received Grammers client, starting update hub and network loop.");
-
-            let (tx, _rx) =
-                broadcast::channel::<interfaces::bot::core::update_bus::UpdateArc>(1024);
-            interfaces::bot::core::update_bus::init_with_sender(tx.clone());
-
-            let client_for_loop = user_client.clone();
-            tokio::spawn(async move {
-                loop {
-                    match client_for_loop.next_update().await {
-                        Ok(update) => {
-                            let timed = interfaces::bot::core::update_bus::TimedUpdate {
-                                ts: Instant::now(),
-                                update,
-                            };
-                            let _ = tx.send(Arc::new(timed));
-                        }
-                        Err(e) => {
-                            log::error!("Update hub error: {}", e);
-                            break;
-                        }
-                    }
-                }
-            });
-
-            tokio::spawn(interfaces::bot::core::bloom_listener::run_bloom_listener(
-                user_client.clone(),
-            ));
-
-            match user_client.run_until_disconnected().await {
-                Ok(_) => log::info!("Grammers client disconnected gracefully."),
-                Err(e) => log::error!("Grammers client disconnected with an error: {}", e),
-            }
-        }
-    });
-
     tokio::spawn(run_ws_ingest());
     tokio::spawn(run_raydium_pool_ingest());
 
@@ -275,23 +234,46 @@ async fn main() {
     tokio::spawn(run_price_fetcher_task(price_fetcher_task_state_clone));
 
     let bot = Bot::from_env();
-    let redis_client = RedisClient::open(redis_url).expect("Failed to create Redis client");
+    register_bot_commands(&bot).await;
+    let redis_client = RedisClient::open(redis_url.clone()).expect("Failed to create Redis client");
+    tokio::spawn(interfaces::bot::tasks::resume::resume_active_tasks(
+        redis_client.clone(),
+        redis_url.clone(),
+        admin_chat_id,
+    ));
+    tokio::spawn(interfaces::bot::handlers::commands::run_indexer_rate_sampler());
+    tokio::spawn(interfaces::bot::tasks::schedule::run_task_scheduler(
+        redis_client.clone(),
+        redis_url.clone(),
+    ));
+    tokio::spawn(interfaces::bot::tasks::autojoin::run_auto_leave_worker(
+        redis_client.clone(),
+        redis_url.clone(),
+    ));
+    tokio::spawn(
+        interfaces::bot::core::bloom_accounts::run_bloom_account_monitor(
+            redis_client.clone(),
+            redis_url,
+        ),
+    );
 
     let unauthorized_message_handler = Update::filter_message()
-        .filter(|msg: Message, admin_user: Arc<u64>| {
-            !is_authorized_user(message_author_id(&msg), *admin_user)
-        })
+        .filter(|msg: Message| role_for(message_author_id(&msg)).is_none())
         .endpoint(forbidden_message);
 
     let unauthorized_callback_handler = Update::filter_callback_query()
-        .filter(|q: CallbackQuery, admin_user: Arc<u64>| q.from.id.0 != *admin_user)
+        .filter(|q: CallbackQuery| role_for(Some(q.from.id.0)).is_none())
         .endpoint(forbidden_callback);
 
     let dialogue_handler = Update::filter_message()
-        .filter(|msg: Message, admin_user: Arc<u64>| {
-            is_authorized_user(message_author_id(&msg), *admin_user)
-        })
+        .filter_map(|msg: Message| role_for(message_author_id(&msg)))
         .enter_dialogue::<Message, InMemStorage<State>, State>()
+        .branch(
+            dptree::filter(|role: Role, state: State| {
+                role == Role::Viewer && !matches!(state, State::Start | State::TasksMenu)
+            })
+            .endpoint(read_only_message),
+        )
         .branch(
             dptree::filter_map(|state: State| match state {
                 State::TaskSelectChannelSearch { .. } => Some(()),
@@ -302,15 +284,13 @@ async fn main() {
         .branch(dptree::endpoint(text_handler));
 
     let command_handler = Update::filter_message()
-        .filter(|msg: Message, admin_user: Arc<u64>| {
-            is_authorized_user(message_author_id(&msg), *admin_user)
-        })
+        .filter_map(|msg: Message| role_for(message_author_id(&msg)))
         .filter_command::<Command>()
         .enter_dialogue::<Message, InMemStorage<State>, State>()
         .endpoint(handle_commands);
 
     let callback_query_handler = Update::filter_callback_query()
-        .filter(|q: CallbackQuery, admin_user: Arc<u64>| q.from.id.0 == *admin_user)
+        .filter_map(|q: CallbackQuery| role_for(Some(q.from.id.0)))
         .enter_dialogue::<CallbackQuery, InMemStorage<State>, State>()
         .endpoint(callback_handler);
 
@@ -328,8 +308,7 @@ async fn main() {
             sol_price_state.clone(),
             user_client_handle,
             rpc_clients,
-            Arc::clone(&admin_user_id),
-            client_sender_for_dispatcher
+            Arc::clone(&warmer_state)
         ])
         .enable_ctrlc_handler()
         .build();
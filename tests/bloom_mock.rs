@@ -0,0 +1,79 @@
+use project_ilanoria::infrastructure::blockchain::bloom::types::BloomFailureKind;
+use project_ilanoria::infrastructure::blockchain::bloom::ws::is_listener_connected;
+use project_ilanoria::infrastructure::blockchain::bloom::{
+    BloomCredentials, BloomFailure, BloomSwapOptions, SwapOutcome, ensure_bloom_ws_listener,
+    serve_mock_bloom, subscribe_swap,
+};
+use project_ilanoria::infrastructure::blockchain::bloom_buy;
+use std::time::Duration;
+use tokio::time::{sleep, timeout};
+
+const MOCK_SCRIPT: &str = "confirm@50;fail@50=Slippage exceeded;reject=Insufficient balance";
+const MINT: &str = "9BB6NFEcjBCtnNLFko2FqVQBq8HHM13kCyYcdQbgpump";
+const WALLET: &str = "MockWa11et1111111111111111111111111111111111";
+const ACK_TIMEOUT: Duration = Duration::from_secs(5);
+
+async fn buy(credentials: &BloomCredentials) -> anyhow::Result<String> {
+    bloom_buy(
+        credentials,
+        MINT,
+        0.1,
+        10,
+        0.001,
+        WALLET,
+        "mock",
+        &BloomSwapOptions::default(),
+    )
+    .await
+}
+
+async fn await_outcome(swap_id: &str) -> SwapOutcome {
+    let rx = subscribe_swap(swap_id).expect("swap registered");
+    timeout(ACK_TIMEOUT, rx)
+        .await
+        .expect("ack within timeout")
+        .expect("ack channel open")
+}
+
+fn expect_failure(outcome: SwapOutcome) -> BloomFailure {
+    match outcome {
+        SwapOutcome::Failed(failure) => failure,
+        other => panic!("expected a failed swap, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn swaps_are_acked_over_websocket() {
+    let addr = serve_mock_bloom("127.0.0.1:0", MOCK_SCRIPT).await.unwrap();
+    unsafe {
+        std::env::set_var("BLOOM_API_BASE_URL", format!("http://{}", addr));
+        std::env::set_var("BLOOM_WS_URL", format!("ws://{}", addr));
+    }
+
+    let credentials = BloomCredentials::new("mock", "mock-token", None);
+    ensure_bloom_ws_listener(credentials.clone());
+    timeout(ACK_TIMEOUT, async {
+        while !is_listener_connected(&credentials.account_key) {
+            sleep(Duration::from_millis(20)).await;
+        }
+    })
+    .await
+    .expect("websocket listener connects to the mock");
+
+    let swap_id = buy(&credentials).await.unwrap();
+    match await_outcome(&swap_id).await {
+        SwapOutcome::Confirmed(ack) => {
+            assert_eq!(ack.token_name.as_deref(), Some("Mock Token"));
+            assert!(ack.signature.is_some_and(|sig| sig.starts_with("mock")));
+        }
+        other => panic!("expected a confirmed swap, got {:?}", other),
+    }
+
+    let swap_id = buy(&credentials).await.unwrap();
+    let failure = expect_failure(await_outcome(&swap_id).await);
+    assert_eq!(failure.kind, BloomFailureKind::SlippageExceeded);
+    assert_eq!(failure.error.as_deref(), Some("Slippage exceeded"));
+
+    let error = buy(&credentials).await.unwrap_err();
+    assert!(error.to_string().contains("Insufficient balance"));
+}